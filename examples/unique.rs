@@ -36,6 +36,10 @@ fn unique2(input: &[u32]) -> Option<HashSet<&u32>> {
         })
 }
 
+fn unique3(input: &[u32]) -> HashSet<&u32> {
+    input.into_par_iter().collect()
+}
+
 const SIZE: usize = 10_000_000;
 
 fn main() {
@@ -51,4 +55,7 @@ fn main() {
     let start = std::time::Instant::now();
     assert_eq!(count, unique2(&v).map(|h| h.len()).unwrap_or(0));
     println!("we took in par with fold: {:?}", start.elapsed());
+    let start = std::time::Instant::now();
+    assert_eq!(count, unique3(&v).len());
+    println!("we took in par with collect: {:?}", start.elapsed());
 }
@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use crate::trace::{traced, EventKind};
 
 pub struct ByBlocks<I, S> {
     pub(crate) base: I,
@@ -119,7 +120,9 @@ impl<Item, C: Consumer<Item>, S: Iterator<Item = usize> + Clone + Send + Sync> C
                     } else {
                         s
                     };
-                    let (left, right) = remaining_producer.divide_at(capped_size);
+                    let (left, right) = traced(EventKind::Division, || {
+                        remaining_producer.divide_at(capped_size)
+                    });
                     *p = Some(right);
                     Some(left)
                 } else {
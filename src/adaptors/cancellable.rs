@@ -0,0 +1,439 @@
+//! Stop computations from outside.
+use crate::prelude::*;
+use crate::{BlockPanic, Try};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Shared stop signal for running parallel computations.
+/// Any thread can trigger it, all cancellable iterators using it
+/// stop dividing and stop yielding elements.
+#[derive(Debug, Default)]
+pub struct CancellationToken {
+    cancelled: AtomicBool,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        CancellationToken {
+            cancelled: AtomicBool::new(false),
+        }
+    }
+    /// Ask all computations using this token to stop.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed)
+    }
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+    fn check<T>(&self, result: T) -> Result<T, Cancelled> {
+        if self.is_cancelled() {
+            Err(Cancelled)
+        } else {
+            Ok(result)
+        }
+    }
+}
+
+/// Error returned by computations stopped through their `CancellationToken`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl std::fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "parallel computation was cancelled")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+/// Iterator stopping once its token is triggered.
+/// All final calls return `Err(Cancelled)` if the token got triggered before they complete,
+/// so `cancellable` needs to be the last adaptor before the final call.
+pub struct Cancellable<'t, I> {
+    pub(crate) base: I,
+    pub(crate) token: &'t CancellationToken,
+}
+
+// final calls
+
+impl<'t, I: ParallelIterator> Cancellable<'t, I> {
+    fn finish<R, F>(self, final_call: F) -> Result<R, Cancelled>
+    where
+        F: FnOnce(Self) -> R,
+    {
+        let token = self.token;
+        token.check(final_call(self))
+    }
+    pub fn reduce<OP, ID>(self, identity: ID, op: OP) -> Result<I::Item, Cancelled>
+    where
+        OP: Fn(I::Item, I::Item) -> I::Item + Sync + Send,
+        ID: Fn() -> I::Item + Send + Sync,
+    {
+        self.finish(|iter| ParallelIterator::reduce(iter, identity, op))
+    }
+    pub fn reduce_with<OP>(self, op: OP) -> Result<Option<I::Item>, Cancelled>
+    where
+        OP: Fn(I::Item, I::Item) -> I::Item + Sync + Send,
+    {
+        self.finish(|iter| ParallelIterator::reduce_with(iter, op))
+    }
+    pub fn try_reduce<T, OP, ID>(self, identity: ID, op: OP) -> Result<I::Item, Cancelled>
+    where
+        I: ParallelIterator<Controlled = True>,
+        OP: Fn(T, T) -> I::Item + Sync + Send,
+        ID: Fn() -> T + Sync + Send,
+        I::Item: Try<Ok = T>,
+    {
+        self.finish(|iter| TryReducible::try_reduce(iter, identity, op))
+    }
+    pub fn for_each<OP>(self, op: OP) -> Result<(), Cancelled>
+    where
+        OP: Fn(I::Item) + Sync + Send,
+    {
+        self.finish(|iter| ParallelIterator::for_each(iter, op))
+    }
+    pub fn find_first<P>(self, predicate: P) -> Result<Option<I::Item>, Cancelled>
+    where
+        P: Fn(&I::Item) -> bool + Send + Sync,
+    {
+        self.finish(|iter| ParallelIterator::find_first(iter, predicate))
+    }
+    pub fn find_any<P>(self, predicate: P) -> Result<Option<I::Item>, Cancelled>
+    where
+        P: Fn(&I::Item) -> bool + Send + Sync,
+    {
+        self.finish(|iter| ParallelIterator::find_any(iter, predicate))
+    }
+    pub fn find_last<P>(self, predicate: P) -> Result<Option<I::Item>, Cancelled>
+    where
+        P: Fn(&I::Item) -> bool + Send + Sync,
+    {
+        self.finish(|iter| ParallelIterator::find_last(iter, predicate))
+    }
+    pub fn any<P>(self, predicate: P) -> Result<bool, Cancelled>
+    where
+        P: Fn(I::Item) -> bool + Sync + Send,
+    {
+        self.finish(|iter| ParallelIterator::any(iter, predicate))
+    }
+    pub fn all<P>(self, predicate: P) -> Result<bool, Cancelled>
+    where
+        I: ParallelIterator<Controlled = True>,
+        P: Fn(I::Item) -> bool + Sync + Send,
+    {
+        self.finish(|iter| TryReducible::all(iter, predicate))
+    }
+    pub fn min_by<F>(self, compare: F) -> Result<Option<I::Item>, Cancelled>
+    where
+        F: Fn(&I::Item, &I::Item) -> std::cmp::Ordering + Sync,
+    {
+        self.finish(|iter| ParallelIterator::min_by(iter, compare))
+    }
+    pub fn max_by<F>(self, compare: F) -> Result<Option<I::Item>, Cancelled>
+    where
+        F: Fn(&I::Item, &I::Item) -> std::cmp::Ordering + Sync + Send,
+    {
+        self.finish(|iter| ParallelIterator::max_by(iter, compare))
+    }
+    pub fn min(self) -> Result<Option<I::Item>, Cancelled>
+    where
+        I::Item: Ord,
+    {
+        self.finish(ParallelIterator::min)
+    }
+    pub fn max(self) -> Result<Option<I::Item>, Cancelled>
+    where
+        I::Item: Ord,
+    {
+        self.finish(ParallelIterator::max)
+    }
+    pub fn min_by_key<K, F>(self, f: F) -> Result<Option<I::Item>, Cancelled>
+    where
+        K: Ord + Send,
+        F: Fn(&I::Item) -> K + Sync + Send,
+    {
+        self.finish(|iter| ParallelIterator::min_by_key(iter, f))
+    }
+    pub fn max_by_key<K, F>(self, f: F) -> Result<Option<I::Item>, Cancelled>
+    where
+        K: Ord + Send,
+        F: Fn(&I::Item) -> K + Sync + Send,
+    {
+        self.finish(|iter| ParallelIterator::max_by_key(iter, f))
+    }
+    pub fn sum<S>(self) -> Result<S, Cancelled>
+    where
+        S: Send + std::iter::Sum<I::Item> + std::iter::Sum<S>,
+    {
+        self.finish(ParallelIterator::sum)
+    }
+    pub fn product<P>(self) -> Result<P, Cancelled>
+    where
+        P: Send + std::iter::Product<I::Item> + std::iter::Product<P>,
+    {
+        self.finish(ParallelIterator::product)
+    }
+    pub fn count(self) -> Result<usize, Cancelled> {
+        self.finish(ParallelIterator::count)
+    }
+    #[allow(clippy::type_complexity)]
+    pub fn partition_into<P>(self, predicate: P) -> Result<(Vec<I::Item>, Vec<I::Item>), Cancelled>
+    where
+        P: Fn(&I::Item) -> bool + Sync + Send,
+    {
+        self.finish(|iter| ParallelIterator::partition_into(iter, predicate))
+    }
+    pub fn top_k<F>(self, k: usize, compare: F) -> Result<Vec<I::Item>, Cancelled>
+    where
+        F: Fn(&I::Item, &I::Item) -> std::cmp::Ordering + Sync + Send,
+    {
+        self.finish(|iter| ParallelIterator::top_k(iter, k, compare))
+    }
+    /// Collect the elements.
+    /// Since the number of elements is not known in advance,
+    /// the collection is built as for non-enumerable iterators.
+    pub fn collect<C>(self) -> Result<C, Cancelled>
+    where
+        C: FromParallelIterator<I::Item>,
+        I::Item: Sync,
+    {
+        self.finish(C::from_par_iter)
+    }
+    /// Positions are computed on the base iterator, before cancellation.
+    pub fn try_for_each_unwind<OP>(self, op: OP) -> Result<Result<(), BlockPanic>, Cancelled>
+    where
+        I: ParallelIterator<Enumerable = True>,
+        OP: Fn(I::Item) + Sync + Send,
+    {
+        let token = self.token;
+        let result = Cancellable {
+            base: self.base.enumerate(),
+            token,
+        }
+        .fold(
+            || Ok(None),
+            |state: Result<Option<usize>, BlockPanic>, (index, e)| {
+                let block_start = state?.unwrap_or(index);
+                catch_unwind(AssertUnwindSafe(|| op(e)))
+                    .map(|_| Some(block_start))
+                    .map_err(|payload| BlockPanic {
+                        block_start,
+                        index,
+                        payload,
+                    })
+            },
+        )
+        .reduce(|| Ok(None), |left, right| left.and(right))
+        .map(|_| ());
+        token.check(result)
+    }
+    pub fn position_first<P>(self, predicate: P) -> Result<Option<usize>, Cancelled>
+    where
+        I: ParallelIterator<Enumerable = True>,
+        P: Fn(I::Item) -> bool + Sync + Send,
+    {
+        Cancellable {
+            base: self.base.enumerate().map(|(i, e)| (i, predicate(e))),
+            token: self.token,
+        }
+        .find_first(|(_, found)| *found)
+        .map(|position| position.map(|(i, _)| i))
+    }
+    pub fn position_any<P>(self, predicate: P) -> Result<Option<usize>, Cancelled>
+    where
+        I: ParallelIterator<Enumerable = True>,
+        P: Fn(I::Item) -> bool + Sync + Send,
+    {
+        Cancellable {
+            base: self.base.enumerate().map(|(i, e)| (i, predicate(e))),
+            token: self.token,
+        }
+        .find_any(|(_, found)| *found)
+        .map(|position| position.map(|(i, _)| i))
+    }
+}
+
+// iterator
+
+impl<'t, I> ParallelIterator for Cancellable<'t, I>
+where
+    I: ParallelIterator,
+{
+    type Item = I::Item;
+    type Controlled = I::Controlled;
+    // a cancelled iterator yields less elements than its base
+    type Enumerable = False;
+    fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+        let cancellable_consumer = Cancellable {
+            base: consumer,
+            token: self.token,
+        };
+        self.base.drive(cancellable_consumer)
+    }
+
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        return self.base.with_producer(Callback {
+            callback,
+            token: self.token,
+        });
+        struct Callback<'t, CB> {
+            callback: CB,
+            token: &'t CancellationToken,
+        }
+
+        impl<'t, T, CB> ProducerCallback<T> for Callback<'t, CB>
+        where
+            CB: ProducerCallback<T>,
+        {
+            type Output = CB::Output;
+            fn call<P>(self, base: P) -> CB::Output
+            where
+                P: Producer<Item = T>,
+            {
+                let producer = CancellableProducer {
+                    base,
+                    token: self.token,
+                };
+                self.callback.call(producer)
+            }
+        }
+    }
+}
+
+// producer
+
+struct CancellableProducer<'t, I> {
+    base: I,
+    token: &'t CancellationToken,
+}
+
+impl<'t, I: Iterator> Iterator for CancellableProducer<'t, I> {
+    type Item = I::Item;
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.token.is_cancelled() {
+            (0, Some(0))
+        } else {
+            self.base.size_hint()
+        }
+    }
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.token.is_cancelled() {
+            None
+        } else {
+            self.base.next()
+        }
+    }
+}
+
+impl<'t, I: DoubleEndedIterator> DoubleEndedIterator for CancellableProducer<'t, I> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.token.is_cancelled() {
+            None
+        } else {
+            self.base.next_back()
+        }
+    }
+}
+
+impl<'t, I: Divisible> Divisible for CancellableProducer<'t, I> {
+    type Controlled = I::Controlled;
+    fn should_be_divided(&self) -> bool {
+        !self.token.is_cancelled() && self.base.should_be_divided()
+    }
+    fn divide(self) -> (Self, Self) {
+        let (left, right) = self.base.divide();
+        (
+            CancellableProducer {
+                base: left,
+                token: self.token,
+            },
+            CancellableProducer {
+                base: right,
+                token: self.token,
+            },
+        )
+    }
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        let (left, right) = self.base.divide_at(index);
+        (
+            CancellableProducer {
+                base: left,
+                token: self.token,
+            },
+            CancellableProducer {
+                base: right,
+                token: self.token,
+            },
+        )
+    }
+}
+
+impl<'t, I: Producer> Producer for CancellableProducer<'t, I> {
+    // once cancelled nothing remains: schedulers stop dividing and folding
+    fn sizes(&self) -> (usize, Option<usize>) {
+        if self.token.is_cancelled() {
+            (0, Some(0))
+        } else {
+            self.base.sizes()
+        }
+    }
+    fn preview(&self, index: usize) -> Self::Item {
+        self.base.preview(index)
+    }
+    fn partial_fold<B, F>(&mut self, init: B, fold_op: F, limit: usize) -> B
+    where
+        B: Send,
+        F: Fn(B, Self::Item) -> B,
+    {
+        if self.token.is_cancelled() {
+            init
+        } else {
+            self.base.partial_fold(init, fold_op, limit)
+        }
+    }
+    fn scheduler<'s, P: 's, R: 's>(&self) -> Box<dyn Scheduler<P, R> + 's>
+    where
+        P: Producer,
+        P::Item: Send,
+        R: Reducer<P::Item>,
+    {
+        self.base.scheduler()
+    }
+    fn micro_block_sizes(&self) -> (usize, usize) {
+        self.base.micro_block_sizes()
+    }
+}
+
+impl<'t, I> PreviewableParallelIterator for Cancellable<'t, I> where I: PreviewableParallelIterator {}
+
+// consumer
+
+impl<'t, C: Clone> Clone for Cancellable<'t, C> {
+    fn clone(&self) -> Self {
+        Cancellable {
+            base: self.base.clone(),
+            token: self.token,
+        }
+    }
+}
+
+impl<'t, Item, C: Consumer<Item>> Consumer<Item> for Cancellable<'t, C> {
+    type Result = C::Result;
+    type Reducer = C::Reducer;
+    fn consume_producer<P>(self, producer: P) -> Self::Result
+    where
+        P: Producer<Item = Item>,
+    {
+        let cancellable_producer = CancellableProducer {
+            base: producer,
+            token: self.token,
+        };
+        self.base.consume_producer(cancellable_producer)
+    }
+    fn to_reducer(self) -> Self::Reducer {
+        self.base.to_reducer()
+    }
+}
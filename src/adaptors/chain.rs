@@ -0,0 +1,207 @@
+//! iterate on a first iterator and then on a second one.
+use crate::prelude::*;
+
+pub struct Chain<A, B> {
+    pub(crate) a: A,
+    pub(crate) b: B,
+}
+
+impl<A, B> ParallelIterator for Chain<A, B>
+where
+    A: ParallelIterator,
+    B: ParallelIterator<Item = A::Item>,
+{
+    type Controlled = A::Controlled;
+    type Enumerable = True;
+    type Item = A::Item;
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        return self.a.with_producer(CallbackA {
+            callback,
+            b: self.b,
+        });
+
+        struct CallbackA<CB, B> {
+            callback: CB,
+            b: B,
+        }
+
+        impl<CB, B> ProducerCallback<B::Item> for CallbackA<CB, B>
+        where
+            B: ParallelIterator,
+            CB: ProducerCallback<B::Item>,
+        {
+            type Output = CB::Output;
+
+            fn call<A>(self, a_producer: A) -> Self::Output
+            where
+                A: Producer<Item = B::Item>,
+            {
+                self.b.with_producer(CallbackB {
+                    a_producer,
+                    callback: self.callback,
+                })
+            }
+        }
+
+        struct CallbackB<CB, A> {
+            a_producer: A,
+            callback: CB,
+        }
+
+        impl<CB, A> ProducerCallback<A::Item> for CallbackB<CB, A>
+        where
+            A: Producer,
+            CB: ProducerCallback<A::Item>,
+        {
+            type Output = CB::Output;
+
+            fn call<B>(self, b_producer: B) -> Self::Output
+            where
+                B: Producer<Item = A::Item>,
+            {
+                self.callback.call(ChainProducer {
+                    a: self.a_producer,
+                    b: b_producer,
+                })
+            }
+        }
+    }
+}
+
+impl<A, B> PreviewableParallelIterator for Chain<A, B>
+where
+    A: PreviewableParallelIterator,
+    B: PreviewableParallelIterator<Item = A::Item>,
+{
+}
+
+struct ChainProducer<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A, B> Iterator for ChainProducer<A, B>
+where
+    A: Iterator,
+    B: Iterator<Item = A::Item>,
+{
+    type Item = A::Item;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.a.next().or_else(|| self.b.next())
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a_min, a_max) = self.a.size_hint();
+        let (b_min, b_max) = self.b.size_hint();
+        (
+            a_min.saturating_add(b_min),
+            a_max.and_then(|a| b_max.and_then(|b| a.checked_add(b))),
+        )
+    }
+}
+
+impl<A, B> DoubleEndedIterator for ChainProducer<A, B>
+where
+    A: DoubleEndedIterator,
+    B: DoubleEndedIterator<Item = A::Item>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.b.next_back().or_else(|| self.a.next_back())
+    }
+}
+
+impl<A, B> Divisible for ChainProducer<A, B>
+where
+    A: Producer,
+    B: Producer<Item = A::Item>,
+{
+    type Controlled = A::Controlled;
+    fn should_be_divided(&self) -> bool {
+        self.a.should_be_divided()
+            || self.b.should_be_divided()
+            || (self.a.length() != 0 && self.b.length() != 0)
+    }
+    fn divide(self) -> (Self, Self) {
+        let mid = self.length() / 2;
+        self.divide_at(mid)
+    }
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        let a_len = self.a.length();
+        if index <= a_len {
+            let (left_a, right_a) = self.a.divide_at(index);
+            let (left_b, right_b) = self.b.divide_at(0);
+            (
+                ChainProducer {
+                    a: left_a,
+                    b: left_b,
+                },
+                ChainProducer {
+                    a: right_a,
+                    b: right_b,
+                },
+            )
+        } else {
+            let (left_a, right_a) = self.a.divide_at(a_len);
+            let (left_b, right_b) = self.b.divide_at(index - a_len);
+            (
+                ChainProducer {
+                    a: left_a,
+                    b: left_b,
+                },
+                ChainProducer {
+                    a: right_a,
+                    b: right_b,
+                },
+            )
+        }
+    }
+}
+
+impl<A, B> Producer for ChainProducer<A, B>
+where
+    A: Producer,
+    B: Producer<Item = A::Item>,
+{
+    fn sizes(&self) -> (usize, Option<usize>) {
+        let (a_min, a_max) = self.a.sizes();
+        let (b_min, b_max) = self.b.sizes();
+        (
+            a_min.saturating_add(b_min),
+            a_max.and_then(|a| b_max.and_then(|b| a.checked_add(b))),
+        )
+    }
+    fn preview(&self, index: usize) -> Self::Item {
+        let a_len = self.a.length();
+        if index < a_len {
+            self.a.preview(index)
+        } else {
+            self.b.preview(index - a_len)
+        }
+    }
+    fn partial_fold<BI, F>(&mut self, init: BI, fold_op: F, limit: usize) -> BI
+    where
+        BI: Send,
+        F: Fn(BI, Self::Item) -> BI,
+    {
+        let a_len = self.a.length();
+        if limit <= a_len {
+            self.a.partial_fold(init, fold_op, limit)
+        } else {
+            let a_output = self.a.partial_fold(init, &fold_op, a_len);
+            self.b.partial_fold(a_output, fold_op, limit - a_len)
+        }
+    }
+    fn scheduler<'s, P: 's, R: 's>(&self) -> Box<dyn Scheduler<P, R> + 's>
+    where
+        P: Producer,
+        P::Item: Send,
+        R: Reducer<P::Item>,
+    {
+        self.a.scheduler()
+    }
+    fn micro_block_sizes(&self) -> (usize, usize) {
+        self.a.micro_block_sizes()
+    }
+}
@@ -48,7 +48,7 @@ impl<I: ParallelIterator> ParallelIterator for ComposedSize<I> {
                     self.callback.call(ComposedSizeProducer {
                         base: producer,
                         reset_counter: self.reset_counter,
-                        created_by: rayon::current_thread_index().unwrap(),
+                        created_by: crate::executor::current_thread_index().unwrap(),
                         counter,
                     })
                 })
@@ -113,7 +113,7 @@ where
     type Controlled = <I as Divisible>::Controlled;
 
     fn divide(self) -> (Self, Self) {
-        let current_thread = rayon::current_thread_index().unwrap_or(usize::MAX);
+        let current_thread = crate::executor::current_thread_index().unwrap_or(usize::MAX);
         let new_counter = if current_thread == self.created_by {
             self.counter.saturating_sub(1)
         } else {
@@ -137,7 +137,7 @@ where
     }
 
     fn divide_at(self, index: usize) -> (Self, Self) {
-        let current_thread = rayon::current_thread_index().unwrap_or(usize::MAX);
+        let current_thread = crate::executor::current_thread_index().unwrap_or(usize::MAX);
         let new_counter = if current_thread == self.created_by {
             self.counter.saturating_sub(1)
         } else {
@@ -162,7 +162,7 @@ where
 
     fn should_be_divided(&self) -> bool {
         (self.counter != 0
-            || self.created_by != rayon::current_thread_index().unwrap_or(usize::MAX))
+            || self.created_by != crate::executor::current_thread_index().unwrap_or(usize::MAX))
             && self.base.should_be_divided()
     }
 }
@@ -217,7 +217,7 @@ impl<Item, C: Consumer<Item>> Consumer<Item> for ComposedSize<C> {
         let composed_sized_producer = ComposedSizeProducer {
             base: producer,
             reset_counter: self.reset_counter,
-            created_by: rayon::current_thread_index().unwrap_or(std::usize::MAX),
+            created_by: crate::executor::current_thread_index().unwrap_or(std::usize::MAX),
             counter,
         };
         self.base.consume_producer(composed_sized_producer)
@@ -0,0 +1,151 @@
+//! yield elements together with their indices.
+use crate::prelude::*;
+use std::cell::Cell;
+
+pub struct Enumerate<I> {
+    pub(crate) base: I,
+}
+
+impl<I> ParallelIterator for Enumerate<I>
+where
+    I: ParallelIterator,
+{
+    type Item = (usize, I::Item);
+    type Controlled = I::Controlled;
+    type Enumerable = True;
+
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        return self.base.with_producer(Callback { callback });
+        struct Callback<CB> {
+            callback: CB,
+        }
+        impl<T, CB> ProducerCallback<T> for Callback<CB>
+        where
+            CB: ProducerCallback<(usize, T)>,
+        {
+            type Output = CB::Output;
+            fn call<P>(self, base: P) -> CB::Output
+            where
+                P: Producer<Item = T>,
+            {
+                self.callback.call(EnumerateProducer { base, offset: 0 })
+            }
+        }
+    }
+}
+
+impl<I> PreviewableParallelIterator for Enumerate<I> where I: PreviewableParallelIterator {}
+
+struct EnumerateProducer<I> {
+    base: I,
+    offset: usize,
+}
+
+impl<I> Iterator for EnumerateProducer<I>
+where
+    I: Iterator,
+{
+    type Item = (usize, I::Item);
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.base.size_hint()
+    }
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.offset;
+        self.base.next().map(|e| {
+            self.offset += 1;
+            (index, e)
+        })
+    }
+}
+
+impl<I> DoubleEndedIterator for EnumerateProducer<I>
+where
+    I: Producer,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let e = self.base.next_back()?;
+        Some((self.offset + self.base.length(), e))
+    }
+}
+
+impl<I> Divisible for EnumerateProducer<I>
+where
+    I: Producer,
+{
+    type Controlled = I::Controlled;
+    fn should_be_divided(&self) -> bool {
+        self.base.should_be_divided()
+    }
+    fn divide(self) -> (Self, Self) {
+        let (left, right) = self.base.divide();
+        let right_offset = self.offset + left.length();
+        (
+            EnumerateProducer {
+                base: left,
+                offset: self.offset,
+            },
+            EnumerateProducer {
+                base: right,
+                offset: right_offset,
+            },
+        )
+    }
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        let (left, right) = self.base.divide_at(index);
+        let right_offset = self.offset + left.length();
+        (
+            EnumerateProducer {
+                base: left,
+                offset: self.offset,
+            },
+            EnumerateProducer {
+                base: right,
+                offset: right_offset,
+            },
+        )
+    }
+}
+
+impl<I> Producer for EnumerateProducer<I>
+where
+    I: Producer,
+{
+    fn sizes(&self) -> (usize, Option<usize>) {
+        self.base.sizes()
+    }
+    fn preview(&self, index: usize) -> Self::Item {
+        (self.offset + index, self.base.preview(index))
+    }
+    fn partial_fold<B, F>(&mut self, init: B, fold_op: F, limit: usize) -> B
+    where
+        B: Send,
+        F: Fn(B, Self::Item) -> B,
+    {
+        let index = Cell::new(self.offset);
+        let output = self.base.partial_fold(
+            init,
+            |acc, e| {
+                let i = index.get();
+                index.set(i + 1);
+                fold_op(acc, (i, e))
+            },
+            limit,
+        );
+        self.offset = index.get();
+        output
+    }
+    fn scheduler<'s, P: 's, R: 's>(&self) -> Box<dyn Scheduler<P, R> + 's>
+    where
+        P: Producer,
+        P::Item: Send,
+        R: Reducer<P::Item>,
+    {
+        self.base.scheduler()
+    }
+    fn micro_block_sizes(&self) -> (usize, usize) {
+        self.base.micro_block_sizes()
+    }
+}
@@ -1,4 +1,7 @@
+use crate::algorithms::partition::with_block_offsets;
+use crate::executor::current_num_threads;
 use crate::prelude::*;
+use crate::utils::slice_utils::SharedSlice;
 use crate::Try;
 
 pub struct Filter<I, F> {
@@ -55,6 +58,104 @@ where
     }
 }
 
+impl<I, F> Filter<I, F>
+where
+    I: ParallelIterator,
+    F: Fn(&I::Item) -> bool + Send + Sync,
+{
+    /// Write all filtered elements, in order, at the start of `output`
+    /// and return their number.
+    /// Blocks are first counted by peeking at their elements, a prefix sum on the counts
+    /// then gives each block the offset at which it writes directly.
+    /// The filter is therefore called twice on each element.
+    /// Panics if `output` is too small.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// let mut output = vec![0u32; 10_000];
+    /// let size = (0u32..10_000)
+    ///     .into_par_iter()
+    ///     .filter(|e| e % 3 == 0)
+    ///     .collect_into_slice(&mut output);
+    /// assert_eq!(size, 3_334);
+    /// assert!(output[..size].iter().enumerate().all(|(i, &e)| e == 3 * i as u32));
+    /// ```
+    pub fn collect_into_slice(self, output: &mut [I::Item]) -> usize
+    where
+        I: PreviewableParallelIterator,
+    {
+        return self.base.with_producer(Callback {
+            filter: &self.filter,
+            output,
+        });
+        struct Callback<'f, 'o, F, T> {
+            filter: &'f F,
+            output: &'o mut [T],
+        }
+        impl<'f, 'o, F, T> ProducerCallback<T> for Callback<'f, 'o, F, T>
+        where
+            F: Fn(&T) -> bool + Sync,
+            T: Send,
+        {
+            type Output = usize;
+            fn call<P>(self, producer: P) -> usize
+            where
+                P: Producer<Item = T>,
+            {
+                let filter = self.filter;
+                let levels = (4 * current_num_threads())
+                    .next_power_of_two()
+                    .trailing_zeros() as usize;
+                let mut blocks = Vec::new();
+                divide_into_blocks(producer, levels, &mut blocks);
+                let shared_blocks = SharedSlice(blocks.as_mut_ptr());
+                let shared_blocks = &shared_blocks;
+                let counts: Vec<usize> = (0..blocks.len())
+                    .into_par_iter()
+                    .map(|index| {
+                        // each task peeks into its own block
+                        let block = unsafe { &*shared_blocks.0.add(index) };
+                        (0..block.length())
+                            .filter(|&index| filter(&block.preview(index)))
+                            .count()
+                    })
+                    .collect();
+                let total: usize = counts.iter().sum();
+                assert!(self.output.len() >= total, "output slice is too small");
+                let output = SharedSlice(self.output.as_mut_ptr());
+                let output = &output;
+                let blocks: Vec<(P, usize)> = blocks.into_iter().zip(counts).collect();
+                with_block_offsets(
+                    blocks,
+                    |(_, count)| *count,
+                    |(block, count), start| {
+                        let end = block.filter(filter).fold(start, |position, e| {
+                            assert!(position < start + count, "filter is not deterministic");
+                            unsafe { *output.0.add(position) = e }
+                            position + 1
+                        });
+                        assert_eq!(end, start + count, "filter is not deterministic");
+                    },
+                )
+            }
+        }
+    }
+}
+
+/// Divide `producer` `levels` times (or until it cannot be divided)
+/// and push all resulting blocks, in order.
+fn divide_into_blocks<P: Producer>(producer: P, levels: usize, blocks: &mut Vec<P>) {
+    if levels == 0 || !producer.should_be_divided() {
+        blocks.push(producer)
+    } else {
+        let (left, right) = producer.divide();
+        divide_into_blocks(left, levels - 1, blocks);
+        divide_into_blocks(right, levels - 1, blocks);
+    }
+}
+
 struct FilterProducer<'f, I, F> {
     base: I,
     filter: &'f F,
@@ -15,7 +15,7 @@ where
 {
     type Item = T;
     type Controlled = I::Controlled;
-    type Enumerable = I::Enumerable;
+    type Enumerable = False;
 
     fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
         let fold_consumer = FoldConsumer {
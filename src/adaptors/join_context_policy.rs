@@ -33,7 +33,7 @@ where
     type Controlled = <I as Divisible>::Controlled;
     fn divide(self) -> (Self, Self) {
         let (left, right) = self.base.divide();
-        let me = rayon::current_thread_index().unwrap_or(0);
+        let me = crate::executor::current_thread_index().unwrap_or(0);
         (
             JoinContextPolicyProducer {
                 base: left,
@@ -51,7 +51,7 @@ where
     }
     fn divide_at(self, index: usize) -> (Self, Self) {
         let (left, right) = self.base.divide_at(index);
-        let me = rayon::current_thread_index().unwrap_or(0);
+        let me = crate::executor::current_thread_index().unwrap_or(0);
         (
             JoinContextPolicyProducer {
                 base: left,
@@ -74,7 +74,7 @@ where
         //  You don't divide if and only if you are on the right side and not stolen.
         //In all cases, it is going to ask the base about division, so basically it just has veto
         //powers
-        let me = rayon::current_thread_index().unwrap_or(0);
+        let me = crate::executor::current_thread_index().unwrap_or(0);
         (me != self.my_creator || !self.is_right) && self.base.should_be_divided() && self.limit > 0
     }
 }
@@ -146,7 +146,7 @@ impl<I: ParallelIterator> ParallelIterator for JoinContextPolicy<I> {
                     limit: self.limit,
                     base: producer,
                     is_right: false,
-                    my_creator: rayon::current_thread_index().unwrap_or(0),
+                    my_creator: crate::executor::current_thread_index().unwrap_or(0),
                 })
             }
         }
@@ -177,7 +177,7 @@ impl<Item, C: Consumer<Item>> Consumer<Item> for JoinContextPolicy<C> {
             limit: self.limit,
             base: producer,
             is_right: false,
-            my_creator: rayon::current_thread_index().unwrap_or(0),
+            my_creator: crate::executor::current_thread_index().unwrap_or(0),
         };
         self.base.consume_producer(join_context_producer)
     }
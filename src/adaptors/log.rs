@@ -1,16 +1,26 @@
-#[cfg(feature = "logs")]
+//! Name a part of the computation in traces.
+//! Each sequential fold of the underlying producer is recorded as a subgraph,
+//! both in kvik's own `Tracer` and in rayon_logs when the `logs` feature is on.
 use crate::prelude::*;
+use crate::trace::{traced, EventKind};
 #[cfg(feature = "logs")]
 extern crate rayon_logs;
 
-#[cfg(feature = "logs")]
+/// Run `op` on a block of given size, recording it under given name.
+fn subgraph<R>(name: &'static str, size: usize, op: impl FnOnce() -> R) -> R {
+    let op = || traced(EventKind::Subgraph(name, size), op);
+    #[cfg(feature = "logs")]
+    return rayon_logs::subgraph(name, size, op);
+    #[cfg(not(feature = "logs"))]
+    op()
+}
+
 pub struct Log<I> {
     pub base: I,
     pub name: &'static str,
 }
 
-#[cfg(feature = "logs")]
-impl<'a, I: ParallelIterator> ParallelIterator for Log<I> {
+impl<I: ParallelIterator> ParallelIterator for Log<I> {
     type Controlled = I::Controlled;
     type Enumerable = I::Enumerable;
     type Item = I::Item;
@@ -56,13 +66,11 @@ impl<'a, I: ParallelIterator> ParallelIterator for Log<I> {
     }
 }
 
-#[cfg(feature = "logs")]
 struct LogProducer<I> {
     base: I,
     name: &'static str,
 }
 
-#[cfg(feature = "logs")]
 impl<I> Iterator for LogProducer<I>
 where
     I: Producer,
@@ -82,11 +90,10 @@ where
         Self: Sized,
         F: FnMut(B, Self::Item) -> B,
     {
-        rayon_logs::subgraph(self.name, self.sizes().0, || self.base.fold(init, f))
+        subgraph(self.name, self.sizes().0, || self.base.fold(init, f))
     }
 }
 
-#[cfg(feature = "logs")]
 impl<I> DoubleEndedIterator for LogProducer<I>
 where
     I: Producer,
@@ -96,7 +103,6 @@ where
     }
 }
 
-#[cfg(feature = "logs")]
 impl<I> Divisible for LogProducer<I>
 where
     I: Producer,
@@ -136,7 +142,6 @@ where
     }
 }
 
-#[cfg(feature = "logs")]
 impl<I> Producer for LogProducer<I>
 where
     I: Producer,
@@ -161,7 +166,7 @@ where
         B: Send,
         F: Fn(B, Self::Item) -> B,
     {
-        rayon_logs::subgraph(self.name, limit, || {
+        subgraph(self.name, limit, || {
             self.base.partial_fold(init, fold_op, limit)
         })
     }
@@ -170,7 +175,6 @@ where
     }
 }
 
-#[cfg(feature = "logs")]
 impl<C: Clone> Clone for Log<C> {
     fn clone(&self) -> Self {
         Log {
@@ -180,7 +184,6 @@ impl<C: Clone> Clone for Log<C> {
     }
 }
 
-#[cfg(feature = "logs")]
 impl<Item, C> Consumer<Item> for Log<C>
 where
     C: Consumer<Item>,
@@ -0,0 +1,240 @@
+use crate::algorithms::kway_merge::multi_sequence_split;
+use crate::prelude::*;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Merge of any number of sorted previewable parallel iterators.
+pub struct MergeAll<I> {
+    pub(crate) iterators: Vec<I>,
+}
+
+/// Merge all given sorted iterators.
+/// Equal elements keep the order of the iterators.
+///
+/// # Example:
+///
+/// ```
+/// use kvik::prelude::*;
+/// let runs: Vec<_> = (0..10u32).map(|r| (0..100u32).into_par_iter().map(move |i| i * 10 + r)).collect();
+/// let merged: Vec<u32> = kvik::merge_all(runs).collect();
+/// assert_eq!(merged, (0..1000).collect::<Vec<u32>>());
+/// ```
+pub fn merge_all<I, C>(iterators: C) -> MergeAll<I>
+where
+    I: PreviewableParallelIterator,
+    I::Item: Ord,
+    C: IntoIterator<Item = I>,
+{
+    MergeAll {
+        iterators: iterators.into_iter().collect(),
+    }
+}
+
+impl<I> ParallelIterator for MergeAll<I>
+where
+    I: PreviewableParallelIterator,
+    I::Item: Ord,
+{
+    type Controlled = False;
+    type Enumerable = True;
+    type Item = I::Item;
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        let mut iterators = self.iterators.into_iter();
+        return match iterators.next() {
+            Some(first) => first.with_producer(Callback {
+                iterators,
+                runs: Vec::new(),
+                callback,
+            }),
+            None => callback.call(MergeAllProducer { runs: Vec::new() }),
+        };
+
+        // we get one producer after the other, each one inside the callback of the previous.
+        // their types are erased so that they can all live in the same vector.
+        struct Callback<'r, I: Iterator, T, CB> {
+            iterators: I,
+            runs: Vec<Box<dyn SortedRun<T> + 'r>>,
+            callback: CB,
+        }
+
+        impl<'r, I, CB> ProducerCallback<<I::Item as ParallelIterator>::Item>
+            for Callback<'r, I, <I::Item as ParallelIterator>::Item, CB>
+        where
+            I: Iterator,
+            I::Item: PreviewableParallelIterator,
+            <I::Item as ParallelIterator>::Item: Ord,
+            CB: ProducerCallback<<I::Item as ParallelIterator>::Item>,
+        {
+            type Output = CB::Output;
+            fn call<P>(self, producer: P) -> Self::Output
+            where
+                P: Producer<Item = <I::Item as ParallelIterator>::Item>,
+            {
+                let mut iterators = self.iterators;
+                let mut runs: Vec<Box<dyn SortedRun<P::Item> + '_>> = self.runs;
+                runs.push(Box::new(producer));
+                match iterators.next() {
+                    Some(next) => next.with_producer(Callback {
+                        iterators,
+                        runs,
+                        callback: self.callback,
+                    }),
+                    None => self.callback.call(MergeAllProducer { runs }),
+                }
+            }
+        }
+    }
+}
+
+/// A sorted producer, with its type erased.
+trait SortedRun<T>: Send {
+    fn length(&self) -> usize;
+    fn preview(&self, index: usize) -> T;
+    fn next(&mut self) -> Option<T>;
+    fn next_back(&mut self) -> Option<T>;
+    #[allow(clippy::type_complexity)]
+    fn divide_at<'s>(
+        self: Box<Self>,
+        index: usize,
+    ) -> (Box<dyn SortedRun<T> + 's>, Box<dyn SortedRun<T> + 's>)
+    where
+        Self: 's;
+}
+
+impl<P: Producer> SortedRun<P::Item> for P {
+    fn length(&self) -> usize {
+        Producer::length(self)
+    }
+    fn preview(&self, index: usize) -> P::Item {
+        Producer::preview(self, index)
+    }
+    fn next(&mut self) -> Option<P::Item> {
+        Iterator::next(self)
+    }
+    fn next_back(&mut self) -> Option<P::Item> {
+        DoubleEndedIterator::next_back(self)
+    }
+    fn divide_at<'s>(
+        self: Box<Self>,
+        index: usize,
+    ) -> (
+        Box<dyn SortedRun<P::Item> + 's>,
+        Box<dyn SortedRun<P::Item> + 's>,
+    )
+    where
+        Self: 's,
+    {
+        let (left, right) = Divisible::divide_at(*self, index);
+        (Box::new(left), Box::new(right))
+    }
+}
+
+struct MergeAllProducer<'r, T> {
+    runs: Vec<Box<dyn SortedRun<T> + 'r>>,
+}
+
+impl<'r, T: Ord + 'r> Iterator for MergeAllProducer<'r, T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.runs.retain(|r| r.length() != 0);
+        // first smallest head, for stability
+        let (index, _) = (0..self.runs.len()).fold(None, |best: Option<(usize, T)>, index| {
+            let head = self.runs[index].preview(0);
+            match best {
+                Some((_, ref value)) if *value <= head => best,
+                _ => Some((index, head)),
+            }
+        })?;
+        self.runs[index].next()
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let size = self.runs.iter().map(|r| r.length()).sum();
+        (size, Some(size))
+    }
+}
+
+impl<'r, T: Ord + 'r> DoubleEndedIterator for MergeAllProducer<'r, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.runs.retain(|r| r.length() != 0);
+        // last largest tail, for stability
+        let (index, _) = (0..self.runs.len()).fold(None, |best: Option<(usize, T)>, index| {
+            let tail = self.runs[index].preview(self.runs[index].length() - 1);
+            match best {
+                Some((_, ref value)) if *value > tail => best,
+                _ => Some((index, tail)),
+            }
+        })?;
+        self.runs[index].next_back()
+    }
+}
+
+impl<'r, T: Ord + 'r> Divisible for MergeAllProducer<'r, T> {
+    type Controlled = False;
+    fn should_be_divided(&self) -> bool {
+        self.runs.iter().filter(|r| r.length() != 0).count() >= 2
+    }
+    fn divide(self) -> (Self, Self) {
+        let runs: Vec<_> = self.runs.into_iter().filter(|r| r.length() != 0).collect();
+        let lengths: Vec<usize> = runs.iter().map(|r| r.length()).collect();
+        let rank = lengths.iter().sum::<usize>() / 2;
+        let splits =
+            multi_sequence_split(&lengths, |run, position| runs[run].preview(position), rank);
+        let (left, right) = runs
+            .into_iter()
+            .zip(splits)
+            .map(|(run, split)| run.divide_at(split))
+            .unzip();
+        (
+            MergeAllProducer { runs: left },
+            MergeAllProducer { runs: right },
+        )
+    }
+    fn divide_at(self, _index: usize) -> (Self, Self) {
+        panic!("you cannot divide_at a merge")
+    }
+}
+
+impl<'r, T: Ord + Send + 'r> Producer for MergeAllProducer<'r, T> {
+    fn sizes(&self) -> (usize, Option<usize>) {
+        self.size_hint()
+    }
+    fn preview(&self, _index: usize) -> Self::Item {
+        panic!("you cannot preview a merge")
+    }
+    fn scheduler<'s, P: 's, R: 's>(&self) -> Box<dyn Scheduler<P, R> + 's>
+    where
+        P: Producer,
+        P::Item: Send,
+        R: Reducer<P::Item>,
+    {
+        Box::new(crate::schedulers::AdaptiveScheduler)
+    }
+    fn partial_fold<B, F>(&mut self, mut init: B, fold_op: F, limit: usize) -> B
+    where
+        B: Send,
+        F: Fn(B, Self::Item) -> B,
+    {
+        let runs = &mut self.runs;
+        let mut heads: BinaryHeap<Reverse<(T, usize)>> = runs
+            .iter()
+            .enumerate()
+            .filter(|(_, run)| run.length() != 0)
+            .map(|(index, run)| Reverse((run.preview(0), index)))
+            .collect();
+        for _ in 0..limit {
+            let index = match heads.pop() {
+                Some(Reverse((_, index))) => index,
+                None => break,
+            };
+            init = fold_op(init, runs[index].next().unwrap());
+            if runs[index].length() != 0 {
+                heads.push(Reverse((runs[index].preview(0), index)));
+            }
+        }
+        runs.retain(|r| r.length() != 0);
+        init
+    }
+}
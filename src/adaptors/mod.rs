@@ -1,7 +1,10 @@
 pub(crate) mod bound_depth;
 pub(crate) mod by_blocks;
+pub(crate) mod cancellable;
 pub(crate) mod cap;
+pub(crate) mod chain;
 pub(crate) mod composition;
+pub(crate) mod enumerate;
 pub(crate) mod even_levels;
 pub(crate) mod filter;
 pub(crate) mod flat_map;
@@ -11,12 +14,20 @@ pub(crate) mod join_context_policy;
 pub(crate) mod log;
 pub(crate) mod map;
 pub(crate) mod merge;
+pub(crate) mod merge_all;
 pub(crate) mod microblocks;
 pub(crate) mod next;
+pub(crate) mod on;
+pub(crate) mod position;
 pub(crate) mod rayon_policy;
 pub(crate) mod rev;
+pub(crate) mod scan;
 pub(crate) mod scheduler_adaptors;
+pub(crate) mod set_operation;
 pub(crate) mod size_limit;
+pub(crate) mod skip;
+pub(crate) mod step_by;
+pub(crate) mod take;
 //pub(crate) mod try_fold;
 pub(crate) mod all;
 pub(crate) mod zip;
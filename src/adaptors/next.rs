@@ -5,6 +5,8 @@ use std::sync::Arc;
 
 pub struct Next<I> {
     pub(crate) base: I,
+    // if not ordered we stop everyone as soon as anything is found
+    pub(crate) ordered: bool,
 }
 
 // producer
@@ -13,6 +15,7 @@ struct NextProducer<'a, P> {
     base: P,
     fake_range: Range<usize>,
     found_at: &'a AtomicUsize,
+    ordered: bool,
 }
 
 impl<'a, P> NextProducer<'a, P> {
@@ -20,8 +23,12 @@ impl<'a, P> NextProducer<'a, P> {
         self.fake_range.start >= self.found_at.load(Ordering::Relaxed)
     }
     fn found(&self) {
-        self.found_at
-            .fetch_min(self.fake_range.start, Ordering::Relaxed);
+        let position = if self.ordered {
+            self.fake_range.start
+        } else {
+            0
+        };
+        self.found_at.fetch_min(position, Ordering::Relaxed);
     }
 }
 
@@ -80,11 +87,13 @@ impl<'a, D: Divisible> Divisible for NextProducer<'a, D> {
                 base: left,
                 fake_range: left_range,
                 found_at: self.found_at,
+                ordered: self.ordered,
             },
             NextProducer {
                 base: right,
                 fake_range: right_range,
                 found_at: self.found_at,
+                ordered: self.ordered,
             },
         )
     }
@@ -97,11 +106,13 @@ impl<'a, D: Divisible> Divisible for NextProducer<'a, D> {
                 base: left,
                 fake_range: left_range,
                 found_at: self.found_at,
+                ordered: self.ordered,
             },
             NextProducer {
                 base: right,
                 fake_range: right_range,
                 found_at: self.found_at,
+                ordered: self.ordered,
             },
         )
     }
@@ -154,6 +165,7 @@ impl<C: Clone> Clone for Next<C> {
     fn clone(&self) -> Self {
         Next {
             base: self.base.clone(),
+            ordered: self.ordered,
         }
     }
 }
@@ -170,6 +182,7 @@ impl<Item: Send, C: Consumer<Item>> Consumer<Item> for Next<C> {
             base: producer,
             fake_range: 0..std::usize::MAX,
             found_at: &found_at,
+            ordered: self.ordered,
         };
         self.base.consume_producer(next_producer)
     }
@@ -185,7 +198,10 @@ impl<I: ParallelIterator> ParallelIterator for Next<I> {
     type Controlled = I::Controlled;
     type Enumerable = False; // TODO: True ?
     fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
-        let next_consumer = Next { base: consumer };
+        let next_consumer = Next {
+            base: consumer,
+            ordered: self.ordered,
+        };
         self.base.drive(next_consumer)
     }
     fn with_producer<CB>(self, _callback: CB) -> CB::Output
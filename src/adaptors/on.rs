@@ -0,0 +1,58 @@
+//! Run the computation on a given executor.
+use crate::executor::{run_on, Executor};
+use crate::prelude::*;
+
+pub struct On<'e, I> {
+    pub(crate) base: I,
+    pub(crate) executor: &'e dyn Executor,
+}
+
+// consumer
+
+impl<'e, C: Clone> Clone for On<'e, C> {
+    fn clone(&self) -> Self {
+        On {
+            base: self.base.clone(),
+            executor: self.executor,
+        }
+    }
+}
+
+impl<'e, Item, C: Consumer<Item>> Consumer<Item> for On<'e, C> {
+    type Result = C::Result;
+    type Reducer = C::Reducer;
+    fn consume_producer<P>(self, producer: P) -> Self::Result
+    where
+        P: Producer<Item = Item>,
+    {
+        let base = self.base;
+        run_on(self.executor, move || base.consume_producer(producer))
+    }
+    fn to_reducer(self) -> Self::Reducer {
+        self.base.to_reducer()
+    }
+}
+
+// iterator
+
+impl<'e, I> ParallelIterator for On<'e, I>
+where
+    I: ParallelIterator,
+{
+    type Item = I::Item;
+    type Controlled = I::Controlled;
+    type Enumerable = False;
+    fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+        let on_consumer = On {
+            base: consumer,
+            executor: self.executor,
+        };
+        self.base.drive(on_consumer)
+    }
+    fn with_producer<CB>(self, _callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        panic!("executors must be called as a consumer")
+    }
+}
@@ -0,0 +1,49 @@
+//! index of matching elements in enumerable iterators.
+use super::filter::FilterConsumer;
+use super::map::MapProducer;
+use super::next::Next;
+use super::zip::ZipProducer;
+use crate::prelude::*;
+use crate::traits::ReduceConsumer;
+
+/// Callback searching for the position of an element satisfying the predicate.
+/// We zip the producer with its indices and search the index with a `Next`
+/// consumer which cancels the remaining tasks once found.
+pub(crate) struct Position<'p, P> {
+    pub(crate) predicate: &'p P,
+    pub(crate) ordered: bool,
+}
+
+impl<'p, T, P> ProducerCallback<T> for Position<'p, P>
+where
+    T: Send,
+    P: Fn(T) -> bool + Sync + Send,
+{
+    type Output = Option<usize>;
+    fn call<Q>(self, producer: Q) -> Self::Output
+    where
+        Q: Producer<Item = T>,
+    {
+        let predicate = self.predicate;
+        let indexed = ZipProducer {
+            b: 0..producer.length(),
+            a: producer,
+        };
+        let matching_index = |(e, i): (T, usize)| if predicate(e) { Some(i) } else { None };
+        let map_producer = MapProducer {
+            base: indexed,
+            op: &matching_index,
+        };
+        let consumer = FilterConsumer {
+            base: Next {
+                base: ReduceConsumer {
+                    op: &|a: Option<usize>, b: Option<usize>| a.or(b),
+                    identity: &|| None,
+                },
+                ordered: self.ordered,
+            },
+            filter: &|i: &Option<usize>| i.is_some(),
+        };
+        consumer.consume_producer(map_producer)
+    }
+}
@@ -51,7 +51,7 @@ impl<I: ParallelIterator> ParallelIterator for Rayon<I> {
                     base,
                     reset_counter: self.reset_counter,
                     counter: self.reset_counter,
-                    created_by: rayon::current_thread_index().unwrap(),
+                    created_by: crate::executor::current_thread_index().unwrap(),
                 };
                 self.callback.call(producer)
                 //TODO: panic once the switch to consumers is done
@@ -87,12 +87,13 @@ impl<I: Divisible> Divisible for RayonProducer<I> {
     type Controlled = I::Controlled;
     fn should_be_divided(&self) -> bool {
         (self.counter != 0
-            || self.created_by != rayon::current_thread_index().unwrap_or(std::usize::MAX))
+            || self.created_by
+                != crate::executor::current_thread_index().unwrap_or(std::usize::MAX))
             && self.base.should_be_divided()
     }
     fn divide(self) -> (Self, Self) {
         let (left, right) = self.base.divide();
-        let current_thread = rayon::current_thread_index().unwrap_or(std::usize::MAX);
+        let current_thread = crate::executor::current_thread_index().unwrap_or(std::usize::MAX);
         let new_counter = if current_thread == self.created_by {
             if self.counter == 0 {
                 0
@@ -119,7 +120,7 @@ impl<I: Divisible> Divisible for RayonProducer<I> {
     }
     fn divide_at(self, index: usize) -> (Self, Self) {
         let (left, right) = self.base.divide_at(index);
-        let current_thread = rayon::current_thread_index().unwrap_or(std::usize::MAX);
+        let current_thread = crate::executor::current_thread_index().unwrap_or(std::usize::MAX);
         let new_counter = if current_thread == self.created_by {
             if self.counter == 0 {
                 0
@@ -195,7 +196,7 @@ where
     {
         let rayon_producer = RayonProducer {
             base: producer,
-            created_by: rayon::current_num_threads(),
+            created_by: crate::executor::current_num_threads(),
             reset_counter: self.reset_counter,
             counter: self.reset_counter,
         };
@@ -33,6 +33,7 @@ where
         (Rev { base: right }, Rev { base: left })
     }
     fn divide_at(self, index: usize) -> (Self, Self) {
+        // mirroring the index needs the exact length
         let index = self.base.length() - index;
         let (left, right) = self.base.divide_at(index);
         (Rev { base: right }, Rev { base: left })
@@ -46,18 +47,27 @@ where
     fn sizes(&self) -> (usize, Option<usize>) {
         self.base.sizes()
     }
-    fn partial_fold<B, F>(&mut self, _init: B, _fold_op: F, _limit: usize) -> B
+    fn partial_fold<B, F>(&mut self, mut init: B, fold_op: F, mut limit: usize) -> B
     where
         B: Send,
         F: Fn(B, Self::Item) -> B,
     {
-        unimplemented!("we need a reversed partial fold!")
+        //TODO: we could divide_at when enumerable
+        while limit > 0 {
+            if let Some(elem) = self.next() {
+                init = fold_op(init, elem);
+                limit -= 1;
+            } else {
+                break;
+            }
+        }
+        init
     }
     fn length(&self) -> usize {
         self.base.length()
     }
     fn preview(&self, index: usize) -> Self::Item {
-        let index = self.length() - index;
+        let index = self.length() - 1 - index;
         self.base.preview(index)
     }
     fn scheduler<'s, Q: 's, R: 's>(&self) -> Box<dyn Scheduler<Q, R> + 's>
@@ -108,6 +118,21 @@ impl<I: ParallelIterator> ParallelIterator for Rev<I> {
     where
         CB: ProducerCallback<Self::Item>,
     {
-        unimplemented!("TODO")
+        return self.base.with_producer(Callback { callback });
+        struct Callback<CB> {
+            callback: CB,
+        }
+        impl<T, CB> ProducerCallback<T> for Callback<CB>
+        where
+            CB: ProducerCallback<T>,
+        {
+            type Output = CB::Output;
+            fn call<P>(self, base: P) -> CB::Output
+            where
+                P: Producer<Item = T>,
+            {
+                self.callback.call(Rev { base })
+            }
+        }
     }
 }
@@ -0,0 +1,44 @@
+//! yield all prefixes of the base iterator.
+//! Elements are gathered in a vector which is scanned in place
+//! with the adaptive `par_prefix_sum_in_place` before being yielded.
+use crate::par_prefix_sum_in_place;
+use crate::prelude::*;
+
+pub struct Scan<I, ID, OP> {
+    pub(crate) base: I,
+    pub(crate) identity: ID,
+    pub(crate) op: OP,
+    pub(crate) exclusive: bool,
+}
+
+impl<I, ID, OP> ParallelIterator for Scan<I, ID, OP>
+where
+    I: ParallelIterator,
+    I::Item: Sync,
+    ID: Fn() -> I::Item + Send,
+    OP: Fn(&I::Item, &I::Item) -> I::Item + Sync + Send,
+{
+    type Item = I::Item;
+    type Controlled = True;
+    type Enumerable = True;
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        let mut prefixes: Vec<I::Item> = self.base.collect();
+        if let Some(first) = prefixes.first_mut() {
+            *first = (self.op)(&(self.identity)(), first);
+        }
+        par_prefix_sum_in_place(&mut prefixes, &self.op);
+        // exclusive prefixes are the inclusive ones shifted by one position:
+        // the last one is not needed and the identity comes first.
+        if self.exclusive && prefixes.pop().is_some() {
+            vec![(self.identity)()]
+                .into_par_iter()
+                .chain(prefixes)
+                .with_producer(callback)
+        } else {
+            prefixes.into_par_iter().with_producer(callback)
+        }
+    }
+}
@@ -0,0 +1,383 @@
+use crate::algorithms::set_operations::{all_equal, value_boundary_split, Operation};
+use crate::prelude::*;
+use std::cmp::Ordering;
+
+/// Set operation between two sorted previewable parallel iterators.
+pub struct SetOperation<A, B> {
+    pub(crate) a: A,
+    pub(crate) b: B,
+    pub(crate) operation: Operation,
+}
+
+impl<A, B> ParallelIterator for SetOperation<A, B>
+where
+    A::Item: Ord,
+    A: ParallelIterator,
+    B: ParallelIterator<Item = A::Item>,
+{
+    type Controlled = False;
+    type Enumerable = False;
+    type Item = A::Item;
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        return self.a.with_producer(CallbackA {
+            callback,
+            b: self.b,
+            operation: self.operation,
+        });
+
+        struct CallbackA<CB, B> {
+            callback: CB,
+            b: B,
+            operation: Operation,
+        }
+
+        impl<CB, B> ProducerCallback<B::Item> for CallbackA<CB, B>
+        where
+            B::Item: Ord,
+            B: ParallelIterator,
+            CB: ProducerCallback<B::Item>,
+        {
+            type Output = CB::Output;
+
+            fn call<A>(self, a_producer: A) -> Self::Output
+            where
+                A: Producer<Item = B::Item>,
+            {
+                self.b.with_producer(CallbackB {
+                    a_producer,
+                    callback: self.callback,
+                    operation: self.operation,
+                })
+            }
+        }
+
+        struct CallbackB<CB, A> {
+            a_producer: A,
+            callback: CB,
+            operation: Operation,
+        }
+
+        impl<CB, A> ProducerCallback<A::Item> for CallbackB<CB, A>
+        where
+            A: Producer,
+            A::Item: Ord,
+            CB: ProducerCallback<A::Item>,
+        {
+            type Output = CB::Output;
+
+            fn call<B>(self, b_producer: B) -> Self::Output
+            where
+                B: Producer<Item = A::Item>,
+            {
+                self.callback.call(SetOperationProducer {
+                    a: self.a_producer,
+                    b: b_producer,
+                    operation: self.operation,
+                })
+            }
+        }
+    }
+}
+
+struct SetOperationProducer<A, B> {
+    a: A,
+    b: B,
+    operation: Operation,
+}
+
+impl<A, B> Iterator for SetOperationProducer<A, B>
+where
+    A: Producer,
+    A::Item: Ord,
+    B: Producer<Item = A::Item>,
+{
+    type Item = A::Item;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let ordering = match (self.a.length(), self.b.length()) {
+                (0, 0) => return None,
+                (_, 0) => Ordering::Less,
+                (0, _) => Ordering::Greater,
+                _ => self.a.preview(0).cmp(&self.b.preview(0)),
+            };
+            match ordering {
+                Ordering::Less => {
+                    let e = self.a.next();
+                    if self.operation.keeps_a() {
+                        return e;
+                    }
+                }
+                Ordering::Greater => {
+                    let e = self.b.next();
+                    if self.operation.keeps_b() {
+                        return e;
+                    }
+                }
+                Ordering::Equal => {
+                    let e = self.a.next();
+                    self.b.next();
+                    if self.operation.keeps_common() {
+                        return e;
+                    }
+                }
+            }
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.operation.sizes(self.a.length(), self.b.length());
+        (lower, Some(upper))
+    }
+}
+
+impl<A, B> DoubleEndedIterator for SetOperationProducer<A, B>
+where
+    A: Producer,
+    A::Item: Ord,
+    B: Producer<Item = A::Item>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            let (a_len, b_len) = (self.a.length(), self.b.length());
+            let ordering = match (a_len, b_len) {
+                (0, 0) => return None,
+                (_, 0) => Ordering::Greater,
+                (0, _) => Ordering::Less,
+                _ => self.a.preview(a_len - 1).cmp(&self.b.preview(b_len - 1)),
+            };
+            match ordering {
+                Ordering::Greater => {
+                    let e = self.a.next_back();
+                    if self.operation.keeps_a() {
+                        return e;
+                    }
+                }
+                Ordering::Less => {
+                    let e = self.b.next_back();
+                    if self.operation.keeps_b() {
+                        return e;
+                    }
+                }
+                Ordering::Equal => {
+                    let e = self.a.next_back();
+                    self.b.next_back();
+                    if self.operation.keeps_common() {
+                        return e;
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<A, B> Divisible for SetOperationProducer<A, B>
+where
+    A: Producer,
+    A::Item: Ord,
+    B: Producer<Item = A::Item>,
+{
+    type Controlled = False;
+    fn should_be_divided(&self) -> bool {
+        let (a_len, b_len) = (self.a.length(), self.b.length());
+        a_len + b_len >= 2 && !all_equal(a_len, b_len, |i| self.a.preview(i), |i| self.b.preview(i))
+    }
+    fn divide(self) -> (Self, Self) {
+        let (a_mid, b_mid) = value_boundary_split(
+            self.a.length(),
+            self.b.length(),
+            |i| self.a.preview(i),
+            |i| self.b.preview(i),
+        );
+        let (left_a, right_a) = self.a.divide_at(a_mid);
+        let (left_b, right_b) = self.b.divide_at(b_mid);
+        (
+            SetOperationProducer {
+                a: left_a,
+                b: left_b,
+                operation: self.operation,
+            },
+            SetOperationProducer {
+                a: right_a,
+                b: right_b,
+                operation: self.operation,
+            },
+        )
+    }
+    fn divide_at(self, _index: usize) -> (Self, Self) {
+        panic!("you cannot divide_at a set operation")
+    }
+}
+
+impl<A, B> Producer for SetOperationProducer<A, B>
+where
+    A: Producer,
+    A::Item: Ord,
+    B: Producer<Item = A::Item>,
+{
+    fn sizes(&self) -> (usize, Option<usize>) {
+        self.size_hint()
+    }
+    fn preview(&self, _index: usize) -> Self::Item {
+        panic!("you cannot preview a set operation")
+    }
+    fn scheduler<'s, P: 's, R: 's>(&self) -> Box<dyn Scheduler<P, R> + 's>
+    where
+        P: Producer,
+        P::Item: Send,
+        R: Reducer<P::Item>,
+    {
+        Box::new(crate::schedulers::AdaptiveScheduler)
+    }
+    fn partial_fold<BI, F>(&mut self, mut init: BI, fold_op: F, mut limit: usize) -> BI
+    where
+        BI: Send,
+        F: Fn(BI, Self::Item) -> BI,
+    {
+        while limit > 0 {
+            if let Some(e) = self.next() {
+                init = fold_op(init, e);
+                limit -= 1;
+            } else {
+                break;
+            }
+        }
+        init
+    }
+}
+
+/// Removal of consecutive duplicates in a sorted previewable parallel iterator.
+pub struct Dedup<I> {
+    pub(crate) base: I,
+}
+
+impl<I> ParallelIterator for Dedup<I>
+where
+    I: ParallelIterator,
+    I::Item: Ord,
+{
+    type Controlled = False;
+    type Enumerable = False;
+    type Item = I::Item;
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        return self.base.with_producer(Callback { callback });
+        struct Callback<CB> {
+            callback: CB,
+        }
+        impl<T, CB> ProducerCallback<T> for Callback<CB>
+        where
+            T: Ord,
+            CB: ProducerCallback<T>,
+        {
+            type Output = CB::Output;
+            fn call<P>(self, base: P) -> CB::Output
+            where
+                P: Producer<Item = T>,
+            {
+                self.callback.call(DedupProducer { base })
+            }
+        }
+    }
+}
+
+struct DedupProducer<P> {
+    base: P,
+}
+
+impl<P> Iterator for DedupProducer<P>
+where
+    P: Producer,
+    P::Item: Ord,
+{
+    type Item = P::Item;
+    fn next(&mut self) -> Option<Self::Item> {
+        let e = self.base.next()?;
+        while self.base.length() != 0 && self.base.preview(0) == e {
+            self.base.next();
+        }
+        Some(e)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = Operation::Dedup.sizes(self.base.length(), 0);
+        (lower, Some(upper))
+    }
+}
+
+impl<P> DoubleEndedIterator for DedupProducer<P>
+where
+    P: Producer,
+    P::Item: Ord,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let e = self.base.next_back()?;
+        while self.base.length() != 0 && self.base.preview(self.base.length() - 1) == e {
+            self.base.next_back();
+        }
+        Some(e)
+    }
+}
+
+impl<P> Divisible for DedupProducer<P>
+where
+    P: Producer,
+    P::Item: Ord,
+{
+    type Controlled = False;
+    fn should_be_divided(&self) -> bool {
+        let len = self.base.length();
+        len >= 2 && !all_equal(len, 0, |i| self.base.preview(i), |i| self.base.preview(i))
+    }
+    fn divide(self) -> (Self, Self) {
+        let (mid, _) = value_boundary_split(
+            self.base.length(),
+            0,
+            |i| self.base.preview(i),
+            |i| self.base.preview(i),
+        );
+        let (left, right) = self.base.divide_at(mid);
+        (DedupProducer { base: left }, DedupProducer { base: right })
+    }
+    fn divide_at(self, _index: usize) -> (Self, Self) {
+        panic!("you cannot divide_at a dedup")
+    }
+}
+
+impl<P> Producer for DedupProducer<P>
+where
+    P: Producer,
+    P::Item: Ord,
+{
+    fn sizes(&self) -> (usize, Option<usize>) {
+        self.size_hint()
+    }
+    fn preview(&self, _index: usize) -> Self::Item {
+        panic!("you cannot preview a dedup")
+    }
+    fn scheduler<'s, Q: 's, R: 's>(&self) -> Box<dyn Scheduler<Q, R> + 's>
+    where
+        Q: Producer,
+        Q::Item: Send,
+        R: Reducer<Q::Item>,
+    {
+        Box::new(crate::schedulers::AdaptiveScheduler)
+    }
+    fn partial_fold<B, F>(&mut self, mut init: B, fold_op: F, mut limit: usize) -> B
+    where
+        B: Send,
+        F: Fn(B, Self::Item) -> B,
+    {
+        while limit > 0 {
+            if let Some(e) = self.next() {
+                init = fold_op(init, e);
+                limit -= 1;
+            } else {
+                break;
+            }
+        }
+        init
+    }
+}
@@ -0,0 +1,166 @@
+//! yield one element every `step` elements.
+use crate::prelude::*;
+
+pub struct StepBy<I> {
+    pub(crate) base: I,
+    pub(crate) step: usize,
+}
+
+impl<I> ParallelIterator for StepBy<I>
+where
+    I: ParallelIterator,
+{
+    type Item = I::Item;
+    type Controlled = I::Controlled;
+    type Enumerable = True;
+
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        return self.base.with_producer(Callback {
+            callback,
+            step: self.step,
+        });
+        struct Callback<CB> {
+            callback: CB,
+            step: usize,
+        }
+        impl<T, CB> ProducerCallback<T> for Callback<CB>
+        where
+            CB: ProducerCallback<T>,
+        {
+            type Output = CB::Output;
+            fn call<P>(self, base: P) -> CB::Output
+            where
+                P: Producer<Item = T>,
+            {
+                self.callback.call(StepByProducer {
+                    base,
+                    step: self.step,
+                })
+            }
+        }
+    }
+}
+
+impl<I> PreviewableParallelIterator for StepBy<I> where I: PreviewableParallelIterator {}
+
+// the base is always positioned on an element we yield.
+struct StepByProducer<I> {
+    base: I,
+    step: usize,
+}
+
+fn div_ceil(size: usize, step: usize) -> usize {
+    if size == 0 {
+        0
+    } else {
+        (size - 1) / step + 1
+    }
+}
+
+impl<I> Iterator for StepByProducer<I>
+where
+    I: Iterator,
+{
+    type Item = I::Item;
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (min, max) = self.base.size_hint();
+        (
+            div_ceil(min, self.step),
+            max.map(|m| div_ceil(m, self.step)),
+        )
+    }
+    fn next(&mut self) -> Option<Self::Item> {
+        let e = self.base.next()?;
+        if self.step > 1 {
+            self.base.nth(self.step - 2);
+        }
+        Some(e)
+    }
+}
+
+impl<I> DoubleEndedIterator for StepByProducer<I>
+where
+    I: Producer,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let len = self.base.length();
+        if len == 0 {
+            None
+        } else {
+            self.base.nth_back((len - 1) % self.step)
+        }
+    }
+}
+
+impl<I> Divisible for StepByProducer<I>
+where
+    I: Producer,
+{
+    type Controlled = I::Controlled;
+    fn should_be_divided(&self) -> bool {
+        self.length() >= 2 && self.base.should_be_divided()
+    }
+    fn divide(self) -> (Self, Self) {
+        let mid = self.length() / 2;
+        self.divide_at(mid)
+    }
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        let base_index = index.saturating_mul(self.step).min(self.base.length());
+        let (left, right) = self.base.divide_at(base_index);
+        (
+            StepByProducer {
+                base: left,
+                step: self.step,
+            },
+            StepByProducer {
+                base: right,
+                step: self.step,
+            },
+        )
+    }
+}
+
+impl<I> Producer for StepByProducer<I>
+where
+    I: Producer,
+{
+    fn sizes(&self) -> (usize, Option<usize>) {
+        let (min, max) = self.base.sizes();
+        (
+            div_ceil(min, self.step),
+            max.map(|m| div_ceil(m, self.step)),
+        )
+    }
+    fn preview(&self, index: usize) -> Self::Item {
+        self.base.preview(index * self.step)
+    }
+    fn partial_fold<B, F>(&mut self, mut init: B, fold_op: F, mut limit: usize) -> B
+    where
+        B: Send,
+        F: Fn(B, Self::Item) -> B,
+    {
+        while limit > 0 {
+            if let Some(elem) = self.next() {
+                init = fold_op(init, elem);
+                limit -= 1;
+            } else {
+                break;
+            }
+        }
+        init
+    }
+    fn scheduler<'s, P: 's, R: 's>(&self) -> Box<dyn Scheduler<P, R> + 's>
+    where
+        P: Producer,
+        P::Item: Send,
+        R: Reducer<P::Item>,
+    {
+        self.base.scheduler()
+    }
+    fn micro_block_sizes(&self) -> (usize, usize) {
+        self.base.micro_block_sizes()
+    }
+}
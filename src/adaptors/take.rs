@@ -0,0 +1,46 @@
+//! only keep the first elements.
+use crate::prelude::*;
+
+pub struct Take<I> {
+    pub(crate) base: I,
+    pub(crate) n: usize,
+}
+
+impl<I> ParallelIterator for Take<I>
+where
+    I: ParallelIterator,
+{
+    type Item = I::Item;
+    type Controlled = I::Controlled;
+    type Enumerable = True;
+
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        return self.base.with_producer(Callback {
+            callback,
+            n: self.n,
+        });
+        struct Callback<CB> {
+            callback: CB,
+            n: usize,
+        }
+        impl<T, CB> ProducerCallback<T> for Callback<CB>
+        where
+            CB: ProducerCallback<T>,
+        {
+            type Output = CB::Output;
+            fn call<P>(self, base: P) -> CB::Output
+            where
+                P: Producer<Item = T>,
+            {
+                let n = self.n.min(base.length());
+                let (kept, _) = base.divide_at(n);
+                self.callback.call(kept)
+            }
+        }
+    }
+}
+
+impl<I> PreviewableParallelIterator for Take<I> where I: PreviewableParallelIterator {}
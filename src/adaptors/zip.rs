@@ -71,9 +71,9 @@ where
     }
 }
 
-struct ZipProducer<A, B> {
-    a: A,
-    b: B,
+pub(crate) struct ZipProducer<A, B> {
+    pub(crate) a: A,
+    pub(crate) b: B,
 }
 
 impl<A, B> Iterator for ZipProducer<A, B>
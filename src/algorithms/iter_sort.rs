@@ -34,8 +34,12 @@ pub fn iter_par_sort<T: Copy + Ord + Send + Sync>(input: &mut [T]) {
             }
         })
         .join_context_policy(std::cmp::min(
-            (2.0 * (rayon::current_num_threads() as f32).log2().ceil()
-                - (rayon::current_num_threads() as f32).log2().floor()) as u32,
+            (2.0 * (crate::executor::current_num_threads() as f32)
+                .log2()
+                .ceil()
+                - (crate::executor::current_num_threads() as f32)
+                    .log2()
+                    .floor()) as u32,
             5u32,
         ))
         .depjoin()
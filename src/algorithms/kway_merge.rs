@@ -0,0 +1,159 @@
+//! Adaptive merge of many sorted runs at once.
+//! Work is divided by multi-sequence selection: all runs are split
+//! at positions such that the left parts contain exactly the first half of the output.
+use crate::prelude::*;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// First position in `start..end` where `predicate` becomes false.
+fn partition_point_in<P: Fn(usize) -> bool>(
+    mut start: usize,
+    mut end: usize,
+    predicate: P,
+) -> usize {
+    while start < end {
+        let mid = (start + end) / 2;
+        if predicate(mid) {
+            start = mid + 1;
+        } else {
+            end = mid;
+        }
+    }
+    start
+}
+
+/// Multi-sequence selection.
+/// Given sorted runs of given lengths, with `value(run, position)` giving access to their elements,
+/// return for each run the number of its elements among the `rank` smallest ones.
+/// Equal elements are ordered by run, then by position, so that merging the left parts
+/// and then the right parts is stable.
+/// PRECONDITION: rank is strictly less than the total length.
+pub(crate) fn multi_sequence_split<V, F>(lengths: &[usize], value: F, rank: usize) -> Vec<usize>
+where
+    V: Ord,
+    F: Fn(usize, usize) -> V,
+{
+    debug_assert!(rank < lengths.iter().sum::<usize>());
+    // all elements before `low` are before the selected one, all elements from `high` are after.
+    let mut low = vec![0; lengths.len()];
+    let mut high = lengths.to_vec();
+    loop {
+        let (pivot_run, _) = low
+            .iter()
+            .zip(high.iter())
+            .enumerate()
+            .max_by_key(|(_, (l, h))| *h - *l)
+            .expect("no runs to split");
+        let pivot_position = (low[pivot_run] + high[pivot_run]) / 2;
+        let pivot = value(pivot_run, pivot_position);
+        let splits: Vec<usize> = (0..lengths.len())
+            .map(|run| {
+                if run < pivot_run {
+                    partition_point_in(low[run], high[run], |p| value(run, p) <= pivot)
+                } else if run > pivot_run {
+                    partition_point_in(low[run], high[run], |p| value(run, p) < pivot)
+                } else {
+                    pivot_position
+                }
+            })
+            .collect();
+        let pivot_rank: usize = splits.iter().sum();
+        if pivot_rank == rank {
+            return splits;
+        } else if pivot_rank < rank {
+            low = splits;
+            low[pivot_run] += 1;
+        } else {
+            high = splits;
+        }
+    }
+}
+
+/// Merge of many sorted slices into one output slice.
+pub struct KWayMerger<'a, T> {
+    runs: Vec<&'a [T]>,
+    out: &'a mut [T],
+}
+
+impl<'a, T: Copy + Ord> KWayMerger<'a, T> {
+    pub fn new(runs: &[&'a [T]], out: &'a mut [T]) -> Self {
+        assert_eq!(runs.iter().map(|r| r.len()).sum::<usize>(), out.len());
+        KWayMerger {
+            runs: runs.iter().copied().filter(|r| !r.is_empty()).collect(),
+            out,
+        }
+    }
+    fn merge(&mut self, limit: usize) {
+        let to_do = limit.min(self.out.len());
+        let (out, remaining) = std::mem::take(&mut self.out).split_at_mut(to_do);
+        self.out = remaining;
+        if self.runs.len() == 1 {
+            let (head, tail) = self.runs[0].split_at(to_do);
+            out.copy_from_slice(head);
+            self.runs[0] = tail;
+        } else {
+            let runs = &mut self.runs;
+            let mut heads: BinaryHeap<Reverse<(&T, usize)>> = runs
+                .iter()
+                .enumerate()
+                .map(|(index, run)| Reverse((&run[0], index)))
+                .collect();
+            for o in out {
+                let Reverse((head, index)) = heads.pop().unwrap();
+                *o = *head;
+                runs[index] = &runs[index][1..];
+                if let Some(next) = runs[index].first() {
+                    heads.push(Reverse((next, index)));
+                }
+            }
+        }
+        self.runs.retain(|r| !r.is_empty());
+    }
+}
+
+impl<'a, T: Copy + Ord> Divisible for KWayMerger<'a, T> {
+    type Controlled = False;
+    fn should_be_divided(&self) -> bool {
+        self.out.len() >= 2 && self.runs.len() >= 2
+    }
+    fn divide(self) -> (Self, Self) {
+        let lengths: Vec<usize> = self.runs.iter().map(|r| r.len()).collect();
+        let rank = self.out.len() / 2;
+        let runs = &self.runs;
+        let splits = multi_sequence_split(&lengths, |run, position| &runs[run][position], rank);
+        let (left_runs, right_runs): (Vec<&[T]>, Vec<&[T]>) = self
+            .runs
+            .iter()
+            .zip(splits)
+            .map(|(run, split)| run.split_at(split))
+            .unzip();
+        let (left_out, right_out) = self.out.split_at_mut(rank);
+        (
+            KWayMerger::new(&left_runs, left_out),
+            KWayMerger::new(&right_runs, right_out),
+        )
+    }
+    fn divide_at(self, _index: usize) -> (Self, Self) {
+        self.divide()
+    }
+}
+
+/// Stable adaptive merge of all given sorted runs into `output`.
+/// Equal elements keep the order of the runs.
+///
+/// # Example:
+///
+/// ```
+/// use kvik::adaptive_kway_merge;
+/// let runs: Vec<Vec<u32>> = (0..100).map(|r| (0..1000).map(|i| i * 100 + r).collect()).collect();
+/// let slices: Vec<&[u32]> = runs.iter().map(|r| r.as_slice()).collect();
+/// let mut output = vec![0; 100_000];
+/// adaptive_kway_merge(&slices, &mut output);
+/// assert!(output.iter().enumerate().all(|(i, &e)| e == i as u32));
+/// ```
+pub fn adaptive_kway_merge<T: Copy + Ord + Send + Sync>(runs: &[&[T]], output: &mut [T]) {
+    KWayMerger::new(runs, output)
+        .work(|m| m.out.is_empty(), |m, limit| m.merge(limit))
+        .micro_block_sizes(1024, 10_000)
+        .for_each(|_| ())
+}
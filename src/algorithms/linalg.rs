@@ -0,0 +1,558 @@
+//! Parallel dense matrix kernels on row-major slices.
+//!
+//! Kernels are divided recursively along their longest dimension.
+//! Parallel divisions happen in a `MatrixBlocks` parallel iterator so that
+//! schedulers and division policies (`bound_depth`, `join_context_policy`,
+//! `size_limit`...) decide where parallelism stops.
+//! Each remaining block then goes on with the same recursion sequentially,
+//! which keeps the kernels cache-oblivious whatever the policy.
+use crate::prelude::*;
+use std::marker::PhantomData;
+use std::ops::{Add, Mul};
+
+/// Blocks with no more cells than this are processed with simple loops.
+const BASE_CELLS: usize = 32 * 32;
+/// Products with no more multiplications than this are computed with simple loops.
+const BASE_VOLUME: usize = 32 * 32 * 32;
+
+// matrix views //
+
+/// Shared view on a rectangular part of a matrix.
+/// Element (i, j) is at `i * row_stride + j * column_stride`.
+struct MatrixRef<'a, T> {
+    data: *const T,
+    rows: usize,
+    columns: usize,
+    row_stride: usize,
+    column_stride: usize,
+    phantom: PhantomData<&'a [T]>,
+}
+
+/// Mutable view on a rectangular part of a matrix.
+struct MatrixMut<'a, T> {
+    data: *mut T,
+    rows: usize,
+    columns: usize,
+    row_stride: usize,
+    column_stride: usize,
+    phantom: PhantomData<&'a mut [T]>,
+}
+
+unsafe impl<'a, T: Sync> Send for MatrixRef<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for MatrixRef<'a, T> {}
+// views produced by splits never overlap
+unsafe impl<'a, T: Send> Send for MatrixMut<'a, T> {}
+
+impl<'a, T> Clone for MatrixRef<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for MatrixRef<'a, T> {}
+
+impl<'a, T> MatrixRef<'a, T> {
+    fn row_major(data: &'a [T], rows: usize, columns: usize) -> Self {
+        assert_eq!(data.len(), rows * columns, "invalid matrix dimensions");
+        MatrixRef {
+            data: data.as_ptr(),
+            rows,
+            columns,
+            row_stride: columns,
+            column_stride: 1,
+            phantom: PhantomData,
+        }
+    }
+    fn get(&self, row: usize, column: usize) -> &'a T {
+        debug_assert!(row < self.rows && column < self.columns);
+        unsafe {
+            &*self
+                .data
+                .add(row * self.row_stride + column * self.column_stride)
+        }
+    }
+}
+
+impl<'a, T> MatrixMut<'a, T> {
+    fn row_major(data: &'a mut [T], rows: usize, columns: usize) -> Self {
+        assert_eq!(data.len(), rows * columns, "invalid matrix dimensions");
+        MatrixMut {
+            data: data.as_mut_ptr(),
+            rows,
+            columns,
+            row_stride: columns,
+            column_stride: 1,
+            phantom: PhantomData,
+        }
+    }
+    /// View on the transpose of a row-major `columns` x `rows` matrix.
+    fn column_major(data: &'a mut [T], rows: usize, columns: usize) -> Self {
+        assert_eq!(data.len(), rows * columns, "invalid matrix dimensions");
+        MatrixMut {
+            data: data.as_mut_ptr(),
+            rows,
+            columns,
+            row_stride: 1,
+            column_stride: rows,
+            phantom: PhantomData,
+        }
+    }
+    fn get_mut(&mut self, row: usize, column: usize) -> &mut T {
+        debug_assert!(row < self.rows && column < self.columns);
+        unsafe {
+            &mut *self
+                .data
+                .add(row * self.row_stride + column * self.column_stride)
+        }
+    }
+    fn reborrow(&mut self) -> MatrixMut<'_, T> {
+        MatrixMut {
+            phantom: PhantomData,
+            ..*self
+        }
+    }
+}
+
+macro_rules! views_division {
+    ($($view: ident),*) => {$(
+        impl<'a, T> $view<'a, T> {
+            fn cells(&self) -> usize {
+                self.rows * self.columns
+            }
+            fn split_rows(self, index: usize) -> (Self, Self) {
+                let right_rows = self.rows - index;
+                (
+                    $view { rows: index, ..self },
+                    $view {
+                        // the pointer is not dereferenced if it goes out of the matrix
+                        data: self.data.wrapping_add(index * self.row_stride),
+                        rows: right_rows,
+                        ..self
+                    },
+                )
+            }
+            fn split_columns(self, index: usize) -> (Self, Self) {
+                let right_columns = self.columns - index;
+                (
+                    $view { columns: index, ..self },
+                    $view {
+                        data: self.data.wrapping_add(index * self.column_stride),
+                        columns: right_columns,
+                        ..self
+                    },
+                )
+            }
+        }
+
+        impl<'a, T> Divisible for $view<'a, T> {
+            type Controlled = True;
+            fn should_be_divided(&self) -> bool {
+                self.cells() > BASE_CELLS
+            }
+            fn divide(self) -> (Self, Self) {
+                // on ties we cut rows, keeping them contiguous
+                if self.rows >= self.columns {
+                    let mid = self.rows - self.rows / 2;
+                    self.split_rows(mid)
+                } else {
+                    let mid = self.columns - self.columns / 2;
+                    self.split_columns(mid)
+                }
+            }
+            fn divide_at(self, index: usize) -> (Self, Self) {
+                let index = index.min(self.rows);
+                self.split_rows(index)
+            }
+        }
+    )*};
+}
+
+views_division!(MatrixRef, MatrixMut);
+
+// kernels //
+
+/// Matrix computations which can be divided into independent blocks.
+pub trait Kernel: Divisible + Send {
+    /// Number of output cells computed by this block.
+    fn cells(&self) -> usize;
+    /// Sequentially compute the whole block.
+    fn run(self);
+}
+
+/// Transposition of a block of a matrix.
+/// The output view is a transposed view so both views share the same shape
+/// and divide in the same way.
+pub struct Transpose<'a, T> {
+    views: (MatrixRef<'a, T>, MatrixMut<'a, T>),
+}
+
+impl<'a, T: Copy + Send + Sync> Divisible for Transpose<'a, T> {
+    type Controlled = True;
+    fn should_be_divided(&self) -> bool {
+        self.views.should_be_divided()
+    }
+    fn divide(self) -> (Self, Self) {
+        let (left, right) = self.views.divide();
+        (Transpose { views: left }, Transpose { views: right })
+    }
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        let (left, right) = self.views.divide_at(index);
+        (Transpose { views: left }, Transpose { views: right })
+    }
+}
+
+impl<'a, T: Copy + Send + Sync> Kernel for Transpose<'a, T> {
+    fn cells(&self) -> usize {
+        self.views.0.cells()
+    }
+    fn run(self) {
+        if self.should_be_divided() {
+            let (left, right) = self.divide();
+            left.run();
+            right.run();
+        } else {
+            let (input, mut output) = self.views;
+            for row in 0..input.rows {
+                for column in 0..input.columns {
+                    *output.get_mut(row, column) = *input.get(row, column);
+                }
+            }
+        }
+    }
+}
+
+/// Computation of a block of `c = a * b`.
+pub struct Product<'a, T> {
+    a: MatrixRef<'a, T>,
+    b: MatrixRef<'a, T>,
+    c: MatrixMut<'a, T>,
+}
+
+impl<'a, T> Divisible for Product<'a, T>
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T> + Send + Sync,
+{
+    type Controlled = True;
+    fn should_be_divided(&self) -> bool {
+        self.c.should_be_divided()
+    }
+    // only output dimensions are cut in parallel,
+    // the inner one would need a reduction on the outputs.
+    fn divide(self) -> (Self, Self) {
+        if self.c.rows >= self.c.columns {
+            let mid = self.c.rows - self.c.rows / 2;
+            self.divide_at(mid)
+        } else {
+            let mid = self.c.columns - self.c.columns / 2;
+            let (left_b, right_b) = self.b.split_columns(mid);
+            let (left_c, right_c) = self.c.split_columns(mid);
+            (
+                Product {
+                    a: self.a,
+                    b: left_b,
+                    c: left_c,
+                },
+                Product {
+                    a: self.a,
+                    b: right_b,
+                    c: right_c,
+                },
+            )
+        }
+    }
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        let index = index.min(self.c.rows);
+        let (left_a, right_a) = self.a.split_rows(index);
+        let (left_c, right_c) = self.c.split_rows(index);
+        (
+            Product {
+                a: left_a,
+                b: self.b,
+                c: left_c,
+            },
+            Product {
+                a: right_a,
+                b: self.b,
+                c: right_c,
+            },
+        )
+    }
+}
+
+impl<'a, T> Kernel for Product<'a, T>
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T> + Send + Sync,
+{
+    fn cells(&self) -> usize {
+        self.c.cells()
+    }
+    fn run(mut self) {
+        for row in 0..self.c.rows {
+            for column in 0..self.c.columns {
+                *self.c.get_mut(row, column) = T::default();
+            }
+        }
+        multiply_add(self.a, self.b, self.c);
+    }
+}
+
+/// Sequential cache-oblivious `c += a * b`, cutting the longest of the three dimensions.
+fn multiply_add<T>(a: MatrixRef<T>, b: MatrixRef<T>, mut c: MatrixMut<T>)
+where
+    T: Copy + Add<Output = T> + Mul<Output = T>,
+{
+    let (n, k, m) = (a.rows, a.columns, b.columns);
+    if n.saturating_mul(k).saturating_mul(m) <= BASE_VOLUME {
+        // i, k, j order to scan rows of b and c
+        for i in 0..n {
+            for l in 0..k {
+                let factor = *a.get(i, l);
+                for j in 0..m {
+                    let cell = c.get_mut(i, j);
+                    *cell = *cell + factor * *b.get(l, j);
+                }
+            }
+        }
+    } else if k >= n && k >= m {
+        let mid = k / 2;
+        let (left_a, right_a) = a.split_columns(mid);
+        let (top_b, bottom_b) = b.split_rows(mid);
+        multiply_add(left_a, top_b, c.reborrow());
+        multiply_add(right_a, bottom_b, c);
+    } else if n >= m {
+        let mid = n / 2;
+        let (top_a, bottom_a) = a.split_rows(mid);
+        let (top_c, bottom_c) = c.split_rows(mid);
+        multiply_add(top_a, b, top_c);
+        multiply_add(bottom_a, b, bottom_c);
+    } else {
+        let mid = m / 2;
+        let (left_b, right_b) = b.split_columns(mid);
+        let (left_c, right_c) = c.split_columns(mid);
+        multiply_add(a, left_b, left_c);
+        multiply_add(a, right_b, right_c);
+    }
+}
+
+// parallel blocks //
+
+/// Parallel iterator on the blocks of a kernel.
+/// Any block could be divided down to single cells so the number of cells
+/// is an upper bound on the number of blocks, allowing size based policies.
+pub struct MatrixBlocks<K> {
+    kernel: K,
+}
+
+struct BlocksProducer<K> {
+    kernel: Option<K>,
+}
+
+impl<K: Kernel> ParallelIterator for MatrixBlocks<K> {
+    type Item = K;
+    type Controlled = True;
+    type Enumerable = False;
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        callback.call(BlocksProducer {
+            kernel: Some(self.kernel),
+        })
+    }
+}
+
+impl<K: Kernel> Iterator for BlocksProducer<K> {
+    type Item = K;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.kernel.take()
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.kernel {
+            Some(kernel) => (1, Some(kernel.cells().max(1))),
+            None => (0, Some(0)),
+        }
+    }
+}
+
+impl<K: Kernel> DoubleEndedIterator for BlocksProducer<K> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.kernel.take()
+    }
+}
+
+impl<K: Kernel> Divisible for BlocksProducer<K> {
+    type Controlled = True;
+    fn should_be_divided(&self) -> bool {
+        self.kernel
+            .as_ref()
+            .map(|k| k.should_be_divided())
+            .unwrap_or(false)
+    }
+    fn divide(self) -> (Self, Self) {
+        match self.kernel {
+            Some(kernel) => {
+                let (left, right) = kernel.divide();
+                (
+                    BlocksProducer { kernel: Some(left) },
+                    BlocksProducer {
+                        kernel: Some(right),
+                    },
+                )
+            }
+            None => (
+                BlocksProducer { kernel: None },
+                BlocksProducer { kernel: None },
+            ),
+        }
+    }
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        match self.kernel {
+            Some(kernel) => {
+                let (left, right) = kernel.divide_at(index);
+                (
+                    BlocksProducer { kernel: Some(left) },
+                    BlocksProducer {
+                        kernel: Some(right),
+                    },
+                )
+            }
+            None => (
+                BlocksProducer { kernel: None },
+                BlocksProducer { kernel: None },
+            ),
+        }
+    }
+}
+
+impl<K: Kernel> Producer for BlocksProducer<K> {
+    fn sizes(&self) -> (usize, Option<usize>) {
+        self.size_hint()
+    }
+    fn preview(&self, _index: usize) -> Self::Item {
+        panic!("you cannot preview matrix blocks")
+    }
+    fn partial_fold<B, F>(&mut self, init: B, fold_op: F, _limit: usize) -> B
+    where
+        B: Send,
+        F: Fn(B, Self::Item) -> B,
+    {
+        match self.kernel.take() {
+            Some(kernel) => fold_op(init, kernel),
+            None => init,
+        }
+    }
+}
+
+// entry points //
+
+/// Return the blocks transposing the row-major `rows` x `columns` matrix `input`
+/// into `output`.
+/// Scheduling policies can be applied before running each block.
+///
+/// # Example:
+///
+/// ```
+/// use kvik::prelude::*;
+/// use kvik::{transpose_blocks, Kernel};
+/// let input: Vec<u32> = (0..6).collect();
+/// let mut output = vec![0; 6];
+/// transpose_blocks(&input, &mut output, 2, 3)
+///     .bound_depth(2)
+///     .for_each(|block| block.run());
+/// assert_eq!(output, vec![0, 3, 1, 4, 2, 5]);
+/// ```
+pub fn transpose_blocks<'a, T: Copy + Send + Sync>(
+    input: &'a [T],
+    output: &'a mut [T],
+    rows: usize,
+    columns: usize,
+) -> MatrixBlocks<Transpose<'a, T>> {
+    MatrixBlocks {
+        kernel: Transpose {
+            views: (
+                MatrixRef::row_major(input, rows, columns),
+                MatrixMut::column_major(output, rows, columns),
+            ),
+        },
+    }
+}
+
+/// Transpose the row-major `rows` x `columns` matrix `input` into `output`.
+///
+/// # Example:
+///
+/// ```
+/// use kvik::par_transpose;
+/// let input: Vec<u32> = (0..10_000).collect();
+/// let mut output = vec![0; 10_000];
+/// par_transpose(&input, &mut output, 50, 200);
+/// assert_eq!(output[1], 200);
+/// assert_eq!(output[50], 1);
+/// ```
+pub fn par_transpose<T: Copy + Send + Sync>(
+    input: &[T],
+    output: &mut [T],
+    rows: usize,
+    columns: usize,
+) {
+    transpose_blocks(input, output, rows, columns).for_each(|block| block.run())
+}
+
+/// Return the blocks computing `c = a * b` for row-major matrices `a` (`n` x `k`),
+/// `b` (`k` x `m`) and `c` (`n` x `m`).
+/// Scheduling policies can be applied before running each block.
+///
+/// # Example:
+///
+/// ```
+/// use kvik::prelude::*;
+/// use kvik::{matmul_blocks, Kernel};
+/// let a = vec![1, 2, 3, 4];
+/// let b = vec![5, 6, 7, 8];
+/// let mut c = vec![0; 4];
+/// matmul_blocks(&a, &b, &mut c, 2, 2, 2)
+///     .size_limit(1)
+///     .for_each(|block| block.run());
+/// assert_eq!(c, vec![19, 22, 43, 50]);
+/// ```
+pub fn matmul_blocks<'a, T>(
+    a: &'a [T],
+    b: &'a [T],
+    c: &'a mut [T],
+    n: usize,
+    k: usize,
+    m: usize,
+) -> MatrixBlocks<Product<'a, T>>
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T> + Send + Sync,
+{
+    MatrixBlocks {
+        kernel: Product {
+            a: MatrixRef::row_major(a, n, k),
+            b: MatrixRef::row_major(b, k, m),
+            c: MatrixMut::row_major(c, n, m),
+        },
+    }
+}
+
+/// Compute `c = a * b` for row-major matrices `a` (`n` x `k`), `b` (`k` x `m`)
+/// and `c` (`n` x `m`).
+///
+/// # Example:
+///
+/// ```
+/// use kvik::par_matmul;
+/// let identity: Vec<f64> = (0..100 * 100)
+///     .map(|i| if i % 101 == 0 { 1.0 } else { 0.0 })
+///     .collect();
+/// let b: Vec<f64> = (0..100 * 30).map(|i| i as f64).collect();
+/// let mut c = vec![0.0; 100 * 30];
+/// par_matmul(&identity, &b, &mut c, 100, 100, 30);
+/// assert_eq!(b, c);
+/// ```
+pub fn par_matmul<T>(a: &[T], b: &[T], c: &mut [T], n: usize, k: usize, m: usize)
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T> + Send + Sync,
+{
+    matmul_blocks(a, b, c, n, k, m).for_each(|block| block.run())
+}
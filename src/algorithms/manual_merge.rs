@@ -258,7 +258,7 @@ pub fn adaptive_slice_merge<T: Copy + Ord + Send + Sync>(
     right: &mut [T],
     output: &mut [T],
 ) {
-    if rayon::current_num_threads() >= 60 {
+    if crate::executor::current_num_threads() >= 60 {
         let merger = Merger {
             a: left,
             b: right,
@@ -1,3 +1,12 @@
 pub mod iter_sort;
+pub mod kway_merge;
+pub mod linalg;
 pub mod manual_merge;
+pub mod par_sort;
+pub mod partition;
+pub mod pattern_search;
+pub mod prefix_sum;
+pub mod radix_sort;
+pub mod select;
+pub mod set_operations;
 pub mod slice_merge_sort;
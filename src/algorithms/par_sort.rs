@@ -0,0 +1,250 @@
+//! Parallel merge sorts for any `Send` type and any comparator.
+//! Elements are moved (never copied) between the slice and a buffer.
+//! The buffer never owns anything: if a comparator panics every element
+//! is still present exactly once in the slice.
+use crate::algorithms::slice_merge_sort::fuse_slices;
+use crate::prelude::*;
+use std::cmp::Ordering;
+use std::ptr;
+
+/// Stable adaptive merge of `a` and `b` into (uninitialized) `out`, moving elements.
+struct MoveMerger<'a, T, F> {
+    a: &'a mut [T],
+    b: &'a mut [T],
+    a_index: usize,
+    b_index: usize,
+    out: &'a mut [T],
+    out_index: usize,
+    compare: &'a F,
+}
+
+impl<'a, T, F> MoveMerger<'a, T, F>
+where
+    F: Fn(&T, &T) -> Ordering,
+{
+    fn new(a: &'a mut [T], b: &'a mut [T], out: &'a mut [T], compare: &'a F) -> Self {
+        MoveMerger {
+            a,
+            b,
+            a_index: 0,
+            b_index: 0,
+            out,
+            out_index: 0,
+            compare,
+        }
+    }
+    fn move_from_a(&mut self, amount: usize) {
+        unsafe {
+            ptr::copy_nonoverlapping(
+                self.a.as_ptr().add(self.a_index),
+                self.out.as_mut_ptr().add(self.out_index),
+                amount,
+            )
+        }
+        self.a_index += amount;
+        self.out_index += amount;
+    }
+    fn move_from_b(&mut self, amount: usize) {
+        unsafe {
+            ptr::copy_nonoverlapping(
+                self.b.as_ptr().add(self.b_index),
+                self.out.as_mut_ptr().add(self.out_index),
+                amount,
+            )
+        }
+        self.b_index += amount;
+        self.out_index += amount;
+    }
+    fn merge(&mut self, limit: usize) {
+        let end = self.out.len().min(self.out_index.saturating_add(limit));
+        while self.out_index < end {
+            let to_do = end - self.out_index;
+            if self.a_index == self.a.len() {
+                self.move_from_b(to_do);
+            } else if self.b_index == self.b.len() {
+                self.move_from_a(to_do);
+            } else if (self.compare)(&self.b[self.b_index], &self.a[self.a_index]) == Ordering::Less
+            {
+                self.move_from_b(1);
+            } else {
+                self.move_from_a(1);
+            }
+        }
+    }
+}
+
+impl<'a, T, F> Drop for MoveMerger<'a, T, F> {
+    /// Only does something when unwinding: move all that is left
+    /// so that `out` still contains all elements.
+    fn drop(&mut self) {
+        let a_remaining = self.a.len() - self.a_index;
+        let b_remaining = self.b.len() - self.b_index;
+        unsafe {
+            let out = self.out.as_mut_ptr().add(self.out_index);
+            ptr::copy_nonoverlapping(self.a.as_ptr().add(self.a_index), out, a_remaining);
+            ptr::copy_nonoverlapping(
+                self.b.as_ptr().add(self.b_index),
+                out.add(a_remaining),
+                b_remaining,
+            );
+        }
+    }
+}
+
+impl<'a, T, F> Divisible for MoveMerger<'a, T, F>
+where
+    F: Fn(&T, &T) -> Ordering,
+{
+    type Controlled = False;
+    fn should_be_divided(&self) -> bool {
+        let a_remaining = self.a.len() - self.a_index;
+        let b_remaining = self.b.len() - self.b_index;
+        a_remaining != 0 && b_remaining != 0 && a_remaining.max(b_remaining) >= 2
+    }
+    fn divide(mut self) -> (Self, Self) {
+        let compare = self.compare;
+        // split around the middle of the longest part.
+        // equal elements coming from `a` must stay on the left of those coming from `b`.
+        // we search before taking anything so that a panicking comparator leaves us intact.
+        let (a_mid, b_mid) = {
+            let a = &self.a[self.a_index..];
+            let b = &self.b[self.b_index..];
+            if a.len() >= b.len() {
+                let pivot = &a[a.len() / 2];
+                let b_mid = b.partition_point(|e| compare(e, pivot) == Ordering::Less);
+                (a.len() / 2, b_mid)
+            } else {
+                let pivot = &b[b.len() / 2];
+                let a_mid = a.partition_point(|e| compare(e, pivot) != Ordering::Greater);
+                (a_mid, b.len() / 2)
+            }
+        };
+        // we now leave nothing for our own drop
+        let a = std::mem::take(&mut self.a)
+            .split_at_mut(std::mem::take(&mut self.a_index))
+            .1;
+        let b = std::mem::take(&mut self.b)
+            .split_at_mut(std::mem::take(&mut self.b_index))
+            .1;
+        let out = std::mem::take(&mut self.out)
+            .split_at_mut(std::mem::take(&mut self.out_index))
+            .1;
+        let (left_a, right_a) = a.split_at_mut(a_mid);
+        let (left_b, right_b) = b.split_at_mut(b_mid);
+        let (left_out, right_out) = out.split_at_mut(a_mid + b_mid);
+        (
+            MoveMerger::new(left_a, left_b, left_out, compare),
+            MoveMerger::new(right_a, right_b, right_out, compare),
+        )
+    }
+    fn divide_at(self, _index: usize) -> (Self, Self) {
+        self.divide()
+    }
+}
+
+/// Merge sorted `a` and `b` into `out`, moving all elements.
+/// `out` content is overwritten without being dropped.
+fn par_move_merge<T, F>(a: &mut [T], b: &mut [T], out: &mut [T], compare: &F)
+where
+    T: Send,
+    F: Fn(&T, &T) -> Ordering + Sync,
+{
+    MoveMerger::new(a, b, out, compare)
+        .work(|m| m.out_index == m.out.len(), |m, limit| m.merge(limit))
+        .micro_block_sizes(1024, 10_000)
+        .for_each(|_| ())
+}
+
+fn par_merge_sort<T, F, S>(slice: &mut [T], compare: &F, sequential_sort: S)
+where
+    T: Send,
+    F: Fn(&T, &T) -> Ordering + Sync,
+    S: Fn(&mut [T], &F) + Sync + Send,
+{
+    let len = slice.len();
+    if len < 2 {
+        return;
+    }
+    // length stays at 0 so that the buffer never drops anything.
+    let mut memory: Vec<T> = Vec::with_capacity(len);
+    let buffer = unsafe { std::slice::from_raw_parts_mut(memory.as_mut_ptr(), len) };
+    let threads = crate::executor::current_num_threads() as f32;
+    (slice, buffer)
+        .wrap_iter()
+        .map(|(input, output)| {
+            sequential_sort(input, compare);
+            (input, output)
+        })
+        .join_context_policy((2.0 * threads.log2().ceil() - threads.log2().floor()) as u32)
+        .depjoin()
+        .even_levels()
+        .reduce_with(|(left_input, left_output), (right_input, right_output)| {
+            let new_output = fuse_slices(left_output, right_output);
+            par_move_merge(left_input, right_input, new_output, compare);
+            (new_output, fuse_slices(left_input, right_input))
+        });
+}
+
+/// Parallel sorts on slices of any `Send` type.
+pub trait ParallelSliceSort<T: Send> {
+    /// Stable parallel merge sort with a custom comparator.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// let mut v: Vec<String> = (0..1000).rev().map(|i| i.to_string()).collect();
+    /// v.par_sort_by(|a, b| a.len().cmp(&b.len()));
+    /// assert_eq!(v[0], "9");
+    /// assert_eq!(v[999], "100");
+    /// ```
+    fn par_sort_by<F>(&mut self, compare: F)
+    where
+        F: Fn(&T, &T) -> Ordering + Sync;
+    /// Stable parallel merge sort by key.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// let mut v: Vec<(u32, String)> = (0..1000).map(|i| (i % 7, i.to_string())).collect();
+    /// v.par_sort_by_key(|r| r.0);
+    /// assert!(v.windows(2).all(|w| w[0].0 < w[1].0 || (w[0].0 == w[1].0 && w[0].1.parse::<u32>().unwrap() < w[1].1.parse::<u32>().unwrap())));
+    /// ```
+    fn par_sort_by_key<K, F>(&mut self, key: F)
+    where
+        K: Ord,
+        F: Fn(&T) -> K + Sync,
+    {
+        self.par_sort_by(|a, b| key(a).cmp(&key(b)))
+    }
+    /// Parallel sort with a custom comparator, not preserving the order of equal elements.
+    /// Sequential parts use `sort_unstable_by` which is faster.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// let mut v: Vec<u32> = (0..10_000).collect();
+    /// v.par_sort_unstable_by(|a, b| b.cmp(a));
+    /// assert!(v.windows(2).all(|w| w[0] > w[1]));
+    /// ```
+    fn par_sort_unstable_by<F>(&mut self, compare: F)
+    where
+        F: Fn(&T, &T) -> Ordering + Sync;
+}
+
+impl<T: Send> ParallelSliceSort<T> for [T] {
+    fn par_sort_by<F>(&mut self, compare: F)
+    where
+        F: Fn(&T, &T) -> Ordering + Sync,
+    {
+        par_merge_sort(self, &compare, |s, compare| s.sort_by(compare))
+    }
+    fn par_sort_unstable_by<F>(&mut self, compare: F)
+    where
+        F: Fn(&T, &T) -> Ordering + Sync,
+    {
+        par_merge_sort(self, &compare, |s, compare| s.sort_unstable_by(compare))
+    }
+}
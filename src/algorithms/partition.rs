@@ -0,0 +1,175 @@
+//! Order preserving partitions and stream compaction.
+//! Blocks are first processed independently, then a prefix sum on their counts
+//! gives each block the final offset of its elements.
+use crate::par_prefix_sum_in_place;
+use crate::prelude::*;
+use crate::utils::slice_utils::SharedSlice;
+use std::ptr;
+
+/// Hand each block, in parallel, to `op` with its offset in the concatenation of all blocks.
+/// `len` tells how many elements each block contributes.
+/// Returns the total number of elements.
+pub(crate) fn with_block_offsets<B, L, OP>(mut blocks: Vec<B>, len: L, op: OP) -> usize
+where
+    B: Send,
+    L: Fn(&B) -> usize,
+    OP: Fn(B, usize) + Sync + Send,
+{
+    let mut offsets: Vec<usize> = blocks.iter().map(len).collect();
+    let lengths = offsets.clone();
+    par_prefix_sum_in_place(&mut offsets, |a, b| a + b);
+    let blocks_number = blocks.len();
+    let shared_blocks = SharedSlice(blocks.as_mut_ptr());
+    // blocks now belong to the tasks (and leak if one of them panics)
+    unsafe { blocks.set_len(0) }
+    let (shared_blocks, offsets_ref, lengths) = (&shared_blocks, &offsets, &lengths);
+    (0..blocks_number).into_par_iter().for_each(|index| {
+        // each task takes its own block
+        let block = unsafe { ptr::read(shared_blocks.0.add(index)) };
+        let start = offsets_ref[index] - lengths[index];
+        op(block, start)
+    });
+    offsets.last().copied().unwrap_or(0)
+}
+
+/// Concatenate all blocks into one vector, moving them in parallel.
+pub(crate) fn concatenate_blocks<T: Send>(blocks: Vec<Vec<T>>) -> Vec<T> {
+    let total = blocks.iter().map(|b| b.len()).sum();
+    let mut concatenation: Vec<T> = Vec::with_capacity(total);
+    let output = SharedSlice(concatenation.as_mut_ptr());
+    let output = &output;
+    with_block_offsets(blocks, Vec::len, |mut block, start| unsafe {
+        ptr::copy_nonoverlapping(block.as_ptr(), output.0.add(start), block.len());
+        // elements now belong to the concatenation
+        block.set_len(0);
+    });
+    unsafe { concatenation.set_len(total) }
+    concatenation
+}
+
+/// Contiguous block of the input and how many of its elements fall in each class.
+#[derive(Clone)]
+struct Block<const N: usize> {
+    start: usize,
+    end: usize,
+    counts: [usize; N],
+}
+
+/// Stable parallel partition: move all elements satisfying the predicate
+/// before all others, preserving their relative order.
+/// Returns the number of elements satisfying the predicate.
+/// If the predicate panics the slice is left untouched.
+///
+/// # Example:
+///
+/// ```
+/// use kvik::par_partition_in_place;
+/// let mut v: Vec<u32> = (0..10_000).collect();
+/// let evens = par_partition_in_place(&mut v, |e| e % 2 == 0);
+/// assert_eq!(evens, 5_000);
+/// assert!(v[..evens].iter().enumerate().all(|(i, &e)| e == 2 * i as u32));
+/// assert!(v[evens..].iter().enumerate().all(|(i, &e)| e == 2 * i as u32 + 1));
+/// ```
+pub fn par_partition_in_place<T, P>(slice: &mut [T], predicate: P) -> usize
+where
+    T: Send + Sync,
+    P: Fn(&T) -> bool + Sync,
+{
+    let [selected, _] = par_partition_by_class(slice, |e| if predicate(e) { 0 } else { 1 });
+    selected
+}
+
+/// Stable parallel partition into `N` classes: move all elements of class 0
+/// first, then all elements of class 1... preserving their relative order.
+/// Returns the number of elements in each class.
+/// If the classifier panics the slice is left untouched.
+///
+/// Passes over the elements run on the depjoin scheduler: when some blocks
+/// are slower to classify the pass ends with them, instead of waiting on
+/// them from each level of the join tree.
+pub(crate) fn par_partition_by_class<T, C, const N: usize>(
+    slice: &mut [T],
+    classifier: C,
+) -> [usize; N]
+where
+    T: Send + Sync,
+    C: Fn(&T) -> usize + Sync,
+{
+    assert!(N <= 256, "too many classes");
+    let len = slice.len();
+    // evaluate the classifier before moving anything
+    let mut classes = vec![0u8; len];
+    classes
+        .par_iter_mut()
+        .zip(&*slice)
+        .depjoin()
+        .for_each(|(c, e)| {
+            let class = classifier(e);
+            assert!(class < N, "invalid class");
+            *c = class as u8
+        });
+    let blocks: Vec<Block<N>> = classes
+        .par_iter()
+        .enumerate()
+        .rayon(2)
+        .fold(
+            || Block {
+                start: 0,
+                end: 0,
+                counts: [0; N],
+            },
+            |mut block, (index, c)| {
+                if block.start == block.end {
+                    block.start = index;
+                }
+                block.end = index + 1;
+                block.counts[*c as usize] += 1;
+                block
+            },
+        )
+        .filter(|block| block.start != block.end)
+        .collect();
+    let mut offsets: Vec<[usize; N]> = blocks.iter().map(|b| b.counts).collect();
+    par_prefix_sum_in_place(&mut offsets, |a, b| {
+        let mut sum = *a;
+        sum.iter_mut().zip(b).for_each(|(s, e)| *s += e);
+        sum
+    });
+    let totals = offsets.last().copied().unwrap_or([0; N]);
+    let mut class_starts = [0; N];
+    for class in 1..N {
+        class_starts[class] = class_starts[class - 1] + totals[class - 1];
+    }
+    // length stays at 0 so that the buffer never drops anything.
+    let mut memory: Vec<T> = Vec::with_capacity(len);
+    let input = SharedSlice(slice.as_mut_ptr());
+    let buffer = SharedSlice(memory.as_mut_ptr());
+    let (input, buffer, classes, blocks_ref, offsets) =
+        (&input, &buffer, &classes, &blocks, &offsets);
+    (0..blocks.len())
+        .into_par_iter()
+        .depjoin()
+        .for_each(|index| {
+            let block = &blocks_ref[index];
+            // all elements of a class before us are in blocks before us
+            let mut positions = [0; N];
+            for class in 0..N {
+                positions[class] =
+                    class_starts[class] + offsets[index][class] - block.counts[class];
+            }
+            for (i, &c) in classes[block.start..block.end].iter().enumerate() {
+                let i = block.start + i;
+                let position = &mut positions[c as usize];
+                unsafe { ptr::copy_nonoverlapping(input.0.add(i), buffer.0.add(*position), 1) }
+                *position += 1;
+            }
+        });
+    let buffer = unsafe { std::slice::from_raw_parts_mut(memory.as_mut_ptr(), len) };
+    (buffer, slice)
+        .wrap_iter()
+        .rayon(2)
+        .for_each(|(partitioned, slice)| unsafe {
+            ptr::copy_nonoverlapping(partitioned.as_ptr(), slice.as_mut_ptr(), slice.len())
+        });
+    totals
+}
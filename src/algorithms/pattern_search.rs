@@ -0,0 +1,108 @@
+//! Parallel search and replacement of patterns in slices and texts.
+//! Each task examines the matches starting in its own positions,
+//! reading into its neighbour's positions when a match straddles the boundary.
+use crate::algorithms::partition::concatenate_blocks;
+use crate::prelude::*;
+use crate::utils::slice_utils::SharedSlice;
+use crate::utils::windowed::windowed;
+
+/// Return the starting positions of all occurrences of `needle` in `haystack`,
+/// including overlapping ones, in increasing order.
+///
+/// # Example:
+///
+/// ```
+/// use kvik::par_find_all;
+/// let text = "abababa, ab";
+/// assert_eq!(par_find_all(text.as_bytes(), b"aba"), vec![0, 2, 4]);
+/// ```
+pub fn par_find_all<T: Eq + Sync>(haystack: &[T], needle: &[T]) -> Vec<usize> {
+    assert!(!needle.is_empty(), "cannot search for an empty needle");
+    let blocks = windowed(haystack, needle.len() - 1)
+        .wrap_iter()
+        .map(|piece| {
+            let start = piece.owned().start;
+            let found: Vec<usize> = piece
+                .window()
+                .windows(needle.len())
+                .take(piece.owned().len())
+                .enumerate()
+                .filter(|(_, w)| *w == needle)
+                .map(|(i, _)| start + i)
+                .collect();
+            vec![found]
+        })
+        .reduce(Vec::new, |mut left, mut right| {
+            left.append(&mut right);
+            left
+        });
+    concatenate_blocks(blocks)
+}
+
+/// Replace all leftmost non overlapping occurrences of `needle` in `haystack`
+/// (as `str::replace` does) by `replacement` of the same length.
+/// Return the number of replacements.
+/// Searching and writing are parallel but choosing which overlapping occurrences
+/// to keep is a sequential pass over all the matches.
+///
+/// # Example:
+///
+/// ```
+/// use kvik::par_replace_in_place;
+/// let mut v = vec![1, 1, 1, 2, 1, 1];
+/// assert_eq!(par_replace_in_place(&mut v, &[1, 1], &[0, 0]), 2);
+/// assert_eq!(v, vec![0, 0, 1, 2, 0, 0]);
+/// ```
+pub fn par_replace_in_place<T>(haystack: &mut [T], needle: &[T], replacement: &[T]) -> usize
+where
+    T: Eq + Clone + Send + Sync,
+{
+    assert_eq!(
+        needle.len(),
+        replacement.len(),
+        "replacement must have the needle's length"
+    );
+    let occurrences = par_find_all(haystack, needle);
+    // only overlapping occurrences of self overlapping needles are discarded here
+    let mut next_free = 0;
+    let selected: Vec<usize> = occurrences
+        .into_iter()
+        .filter(|&position| {
+            let keep = position >= next_free;
+            if keep {
+                next_free = position + needle.len();
+            }
+            keep
+        })
+        .collect();
+    let output = SharedSlice(haystack.as_mut_ptr());
+    let output = &output;
+    selected.par_iter().for_each(|&position| {
+        let target =
+            unsafe { std::slice::from_raw_parts_mut(output.0.add(position), replacement.len()) };
+        target.clone_from_slice(replacement)
+    });
+    selected.len()
+}
+
+/// Replace all occurrences of `needle` in `text` by `replacement` of the same byte length.
+/// Return the number of replacements.
+/// As for `par_replace_in_place`, overlapping occurrences are discarded sequentially.
+///
+/// # Example:
+///
+/// ```
+/// use kvik::par_replace_str_in_place;
+/// let mut text = String::from("Lorem ipsum, lorem Lorem.");
+/// assert_eq!(par_replace_str_in_place(&mut text, "Lorem", "*****"), 2);
+/// assert_eq!(text, "***** ipsum, lorem *****.");
+/// ```
+pub fn par_replace_str_in_place(text: &mut str, needle: &str, replacement: &str) -> usize {
+    // matches of a valid needle always start and end on char boundaries
+    // so replacing them by valid text keeps the text valid.
+    par_replace_in_place(
+        unsafe { text.as_bytes_mut() },
+        needle.as_bytes(),
+        replacement.as_bytes(),
+    )
+}
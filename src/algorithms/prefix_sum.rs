@@ -0,0 +1,148 @@
+//! Adaptive parallel prefix computations.
+//! The owner scans sequentially and only when a steal request arrives do we
+//! divide the remaining part. Each stolen part is scanned locally.
+//! Reductions only record where these locally scanned segments are, once all
+//! of them are known a second (parallel) pass fixes each stolen element once.
+use crate::algorithms::slice_merge_sort::fuse_slices;
+use crate::prelude::*;
+
+struct PrefixSum<'a, T, O> {
+    slice: &'a mut [T],
+    index: usize, // everything before index is already scanned
+    op: &'a O,
+}
+
+impl<'a, T, O> PrefixSum<'a, T, O>
+where
+    O: Fn(&T, &T) -> T,
+{
+    fn scan(&mut self, limit: usize) {
+        let end = self.slice.len().min(self.index.saturating_add(limit));
+        for i in self.index.max(1)..end {
+            let (done, remaining) = self.slice.split_at_mut(i);
+            remaining[0] = (self.op)(&done[i - 1], &remaining[0]);
+        }
+        self.index = end;
+    }
+}
+
+impl<'a, T, O> Divisible for PrefixSum<'a, T, O> {
+    type Controlled = True;
+    fn should_be_divided(&self) -> bool {
+        self.slice.len() - self.index >= 2
+    }
+    fn divide(self) -> (Self, Self) {
+        let remaining = self.slice.len() - self.index;
+        self.divide_at(remaining / 2)
+    }
+    /// Divide the remaining (not yet scanned) part at given index.
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        let mid = (self.index + index).min(self.slice.len());
+        let (left, right) = self.slice.split_at_mut(mid);
+        (
+            PrefixSum {
+                slice: left,
+                index: self.index,
+                op: self.op,
+            },
+            PrefixSum {
+                slice: right,
+                index: 0,
+                op: self.op,
+            },
+        )
+    }
+}
+
+/// Contiguous locally scanned segments, `ends` are their (exclusive) end positions.
+struct Segments<'a, T> {
+    slice: &'a mut [T],
+    ends: Vec<usize>,
+}
+
+impl<'a, T> Segments<'a, T>
+where
+    T: Send + Sync,
+{
+    fn fuse(mut self, right: Self) -> Self {
+        let offset = self.slice.len();
+        self.ends
+            .extend(right.ends.into_iter().map(|end| end + offset));
+        Segments {
+            slice: fuse_slices(self.slice, right.slice),
+            ends: self.ends,
+        }
+    }
+    /// Combine each segment with the last value of all previous ones.
+    /// The offsets are computed sequentially (there is one per steal),
+    /// then all segments are fixed in parallel.
+    fn fix<O>(self, op: &O)
+    where
+        O: Fn(&T, &T) -> T + Sync,
+    {
+        let mut segments = Vec::with_capacity(self.ends.len());
+        let mut remaining = self.slice;
+        let mut start = 0;
+        for end in self.ends {
+            let (segment, right) = remaining.split_at_mut(end - start);
+            if !segment.is_empty() {
+                segments.push(segment);
+            }
+            remaining = right;
+            start = end;
+        }
+        let (first, stolen) = match segments.split_first_mut() {
+            Some((first, stolen)) if !stolen.is_empty() => (&**first, stolen),
+            _ => return,
+        };
+        let first_last = first.last().unwrap();
+        // offsets[i] is for stolen[i + 1], stolen[0] directly uses first_last.
+        let mut offsets: Vec<T> = Vec::with_capacity(stolen.len() - 1);
+        for i in 1..stolen.len() {
+            let previous = if i == 1 { first_last } else { &offsets[i - 2] };
+            let offset = op(previous, stolen[i - 1].last().unwrap());
+            offsets.push(offset);
+        }
+        stolen.par_iter_mut().enumerate().for_each(|(i, segment)| {
+            let offset = if i == 0 { first_last } else { &offsets[i - 1] };
+            segment.par_iter_mut().for_each(|e| *e = op(offset, e))
+        });
+    }
+}
+
+/// Compute an inclusive scan in place: `slice[i]` becomes
+/// `op(slice[0], op(slice[1], ... slice[i]))`.
+/// `op` needs to be associative.
+///
+/// # Example:
+/// ```
+/// use kvik::par_prefix_sum_in_place;
+/// let mut v = vec![1u64; 10_000];
+/// par_prefix_sum_in_place(&mut v, |a, b| a + b);
+/// assert!(v.iter().enumerate().all(|(i, &e)| e == i as u64 + 1));
+/// ```
+pub fn par_prefix_sum_in_place<T, O>(slice: &mut [T], op: O)
+where
+    T: Send + Sync,
+    O: Fn(&T, &T) -> T + Sync,
+{
+    let state = PrefixSum {
+        slice,
+        index: 0,
+        op: &op,
+    };
+    let scanned = state
+        .work(|s| s.index == s.slice.len(), |s, limit| s.scan(limit))
+        .micro_block_sizes(1024, 10_000)
+        .map(|s| {
+            let len = s.slice.len();
+            Segments {
+                slice: s.slice,
+                ends: vec![len],
+            }
+        })
+        .reduce_with(Segments::fuse);
+    if let Some(segments) = scanned {
+        segments.fix(&op)
+    }
+}
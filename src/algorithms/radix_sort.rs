@@ -0,0 +1,193 @@
+//! Parallel LSD radix sort on integer and float keys.
+//! Digits histograms for all passes are computed upfront with a `fold`
+//! so that passes on constant digits can be skipped.
+//! Each pass then sorts on one byte:
+//! - input and buffer are divided together and each block is sorted locally
+//!   on the digit into its own part of the buffer
+//! - positions of all (block, digit) segments are computed with a parallel prefix sum
+//! - segments are moved back into the input, digit by digit.
+use crate::par_prefix_sum_in_place;
+use crate::prelude::*;
+
+const RADIX_BITS: usize = 8;
+const BUCKETS: usize = 1 << RADIX_BITS;
+
+/// Keys usable by the radix sort.
+/// `to_radix` must be an order preserving conversion to an unsigned integer.
+pub trait RadixKey: Copy + Send + Sync {
+    /// Number of meaningful bytes in the converted key.
+    const BYTES: usize;
+    fn to_radix(self) -> u64;
+}
+
+macro_rules! unsigned_radix_key {
+    ($($t:ty),*) => {$(
+        impl RadixKey for $t {
+            const BYTES: usize = std::mem::size_of::<$t>();
+            fn to_radix(self) -> u64 {
+                self as u64
+            }
+        }
+    )*};
+}
+
+// signed integers: flipping the sign bit puts negative numbers first.
+macro_rules! signed_radix_key {
+    ($($t:ty => $u:ty),*) => {$(
+        impl RadixKey for $t {
+            const BYTES: usize = std::mem::size_of::<$t>();
+            fn to_radix(self) -> u64 {
+                ((self as $u) ^ (1 << (8 * std::mem::size_of::<$t>() - 1))) as u64
+            }
+        }
+    )*};
+}
+
+// floats: negative numbers get all their bits flipped (reversing their order),
+// positive ones only their sign bit.
+// nans end up before negative infinity or after positive infinity, depending on their sign.
+macro_rules! float_radix_key {
+    ($($t:ty),*) => {$(
+        impl RadixKey for $t {
+            const BYTES: usize = std::mem::size_of::<$t>();
+            fn to_radix(self) -> u64 {
+                let bits = self.to_bits();
+                let sign = 1 << (8 * std::mem::size_of::<$t>() - 1);
+                (if bits & sign != 0 { !bits } else { bits ^ sign }) as u64
+            }
+        }
+    )*};
+}
+
+unsigned_radix_key!(u8, u16, u32, u64, usize);
+signed_radix_key!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, isize => usize);
+float_radix_key!(f32, f64);
+
+/// Stable counting sort of `input` on one digit into `output` (of same size).
+/// Returns the number of elements for each digit.
+fn local_pass<T, D>(input: &[T], output: &mut [T], digit: &D) -> Vec<usize>
+where
+    T: Copy,
+    D: Fn(&T) -> usize,
+{
+    let mut counts = vec![0; BUCKETS];
+    input.iter().for_each(|e| counts[digit(e)] += 1);
+    let mut positions: Vec<usize> = counts
+        .iter()
+        .scan(0, |position, count| {
+            let start = *position;
+            *position += count;
+            Some(start)
+        })
+        .collect();
+    for e in input {
+        let position = &mut positions[digit(e)];
+        output[*position] = *e;
+        *position += 1;
+    }
+    counts
+}
+
+/// Stable sort of `input` on one digit, using `buffer` as temporary space.
+fn radix_pass<T, D>(input: &mut [T], buffer: &mut [T], digit: D)
+where
+    T: Copy + Send + Sync,
+    D: Fn(&T) -> usize + Sync,
+{
+    let counts: Vec<Vec<usize>> = (&*input, &mut *buffer)
+        .wrap_iter()
+        .rayon(2)
+        .map(|(input, output)| local_pass(input, output, &digit))
+        .collect();
+    // blocks follow each other in the buffer, each one sorted by digit:
+    // all segments ends are a prefix sum over the block major counts.
+    let mut ends: Vec<usize> = counts.iter().flatten().cloned().collect();
+    par_prefix_sum_in_place(&mut ends, |a, b| a + b);
+    // in the input, segments are laid out digit major.
+    let mut segments = Vec::new();
+    let mut remaining_input = input;
+    for d in 0..BUCKETS {
+        for (block_index, block_counts) in counts.iter().enumerate() {
+            let count = block_counts[d];
+            if count != 0 {
+                let end = ends[block_index * BUCKETS + d];
+                let (destination, remaining) = remaining_input.split_at_mut(count);
+                remaining_input = remaining;
+                segments.push((&buffer[end - count..end], destination));
+            }
+        }
+    }
+    segments
+        .into_par_iter()
+        .for_each(|(source, destination)| destination.copy_from_slice(source));
+}
+
+/// Stable parallel radix sort on keys extracted from the elements.
+///
+/// # Example:
+///
+/// ```
+/// use kvik::par_radix_sort_by_key;
+/// let mut v: Vec<(i32, usize)> = (0..10_000).map(|i| ((i * 7919 % 1000) as i32 - 500, i)).collect();
+/// par_radix_sort_by_key(&mut v, |e| e.0);
+/// assert!(v.windows(2).all(|w| w[0].0 < w[1].0 || (w[0].0 == w[1].0 && w[0].1 < w[1].1)));
+/// ```
+pub fn par_radix_sort_by_key<T, K, F>(slice: &mut [T], key: F)
+where
+    T: Copy + Send + Sync,
+    K: RadixKey,
+    F: Fn(&T) -> K + Sync,
+{
+    let len = slice.len();
+    if len < 2 {
+        return;
+    }
+    let histograms = slice
+        .par_iter()
+        .rayon(2)
+        .fold(
+            || vec![0; K::BYTES * BUCKETS],
+            |mut histograms, e| {
+                let radix = key(e).to_radix();
+                for byte in 0..K::BYTES {
+                    let digit = ((radix >> (byte * RADIX_BITS)) as usize) & (BUCKETS - 1);
+                    histograms[byte * BUCKETS + digit] += 1;
+                }
+                histograms
+            },
+        )
+        .reduce(
+            || vec![0; K::BYTES * BUCKETS],
+            |mut a, b| {
+                a.iter_mut().zip(b).for_each(|(a, b)| *a += b);
+                a
+            },
+        );
+    // length stays at 0, elements are Copy so nothing ever needs dropping.
+    let mut memory: Vec<T> = Vec::with_capacity(len);
+    let buffer: &mut [T] = unsafe { std::slice::from_raw_parts_mut(memory.as_mut_ptr(), len) };
+    for (byte, histogram) in histograms.chunks(BUCKETS).enumerate() {
+        // all elements share this digit: nothing to do.
+        if histogram.contains(&len) {
+            continue;
+        }
+        let shift = byte * RADIX_BITS;
+        let digit = |e: &T| ((key(e).to_radix() >> shift) as usize) & (BUCKETS - 1);
+        radix_pass(slice, buffer, digit);
+    }
+}
+
+/// Stable parallel radix sort on integers or floats.
+/// Floats are sorted by their total order (-0.0 comes before 0.0).
+///
+/// # Example:
+///
+/// ```
+/// use kvik::par_radix_sort;
+/// let mut v: Vec<f64> = (0..10_000).map(|i| ((i * 7919) % 10_000) as f64 - 5_000.5).collect();
+/// par_radix_sort(&mut v);
+/// assert!(v.windows(2).all(|w| w[0] <= w[1]));
+/// ```
+pub fn par_radix_sort<K: RadixKey>(slice: &mut [K]) {
+    par_radix_sort_by_key(slice, |&k| k)
+}
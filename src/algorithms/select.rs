@@ -0,0 +1,150 @@
+//! Parallel selection.
+//! Quickselect levels split the slice into elements smaller than, equal to
+//! and greater than a pivot with the stable partition, then carry on in the
+//! part containing the wanted rank. Small parts are finished sequentially.
+use crate::algorithms::partition::par_partition_by_class;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+/// Below this size we switch to the sequential selection.
+const SEQUENTIAL_SELECTION: usize = 10_000;
+/// Number of elements the pivot is the median of.
+const PIVOT_SAMPLE: usize = 15;
+
+/// Reorder the slice such that the element at `index` is at its final sorted
+/// position, all elements before it being lower or equal and all elements after
+/// it greater or equal.
+/// Returns the elements before `index`, the element at `index` and the elements after it,
+/// just like `select_nth_unstable` from the standard library.
+///
+/// # Example:
+///
+/// ```
+/// use kvik::par_select_nth_unstable;
+/// let mut v: Vec<u32> = (0..100_000).rev().collect();
+/// let (smaller, median, greater) = par_select_nth_unstable(&mut v, 50_000);
+/// assert_eq!(*median, 50_000);
+/// assert!(smaller.iter().all(|&e| e < 50_000));
+/// assert!(greater.iter().all(|&e| e > 50_000));
+/// ```
+pub fn par_select_nth_unstable<T: Ord + Send + Sync>(
+    slice: &mut [T],
+    index: usize,
+) -> (&mut [T], &mut T, &mut [T]) {
+    assert!(index < slice.len(), "selection index out of bounds");
+    // all elements outside start..end are already at their final side of index.
+    let (mut start, mut end) = (0, slice.len());
+    while end - start > SEQUENTIAL_SELECTION {
+        let part = &mut slice[start..end];
+        move_pivot_first(part);
+        let (pivot, others) = part.split_first_mut().unwrap();
+        let pivot = &*pivot;
+        let [smaller, equal, _] = par_partition_by_class(others, |e| match e.cmp(pivot) {
+            Ordering::Less => 0,
+            Ordering::Equal => 1,
+            Ordering::Greater => 2,
+        });
+        // pivot goes back between the smaller and the greater elements
+        part.swap(0, smaller);
+        let (equal_start, equal_end) = (start + smaller, start + smaller + equal + 1);
+        if index < equal_start {
+            end = equal_start
+        } else if index >= equal_end {
+            start = equal_end
+        } else {
+            start = index;
+            end = index;
+        }
+    }
+    if start < end {
+        slice[start..end].select_nth_unstable(index - start);
+    }
+    let (smaller, remaining) = slice.split_at_mut(index);
+    let (nth, greater) = remaining.split_first_mut().unwrap();
+    (smaller, nth, greater)
+}
+
+/// Swap the median of evenly spaced elements into the first position.
+fn move_pivot_first<T: Ord>(slice: &mut [T]) {
+    let step = slice.len() / PIVOT_SAMPLE;
+    let mut positions: Vec<usize> = (0..PIVOT_SAMPLE).map(|i| i * step).collect();
+    positions.sort_unstable_by(|&a, &b| slice[a].cmp(&slice[b]));
+    slice.swap(0, positions[PIVOT_SAMPLE / 2]);
+}
+
+/// Element ordered by a comparison function.
+/// The order is reversed so that the top of the heap is the worst element kept.
+struct Ranked<'c, T, F> {
+    element: T,
+    compare: &'c F,
+}
+
+impl<'c, T, F: Fn(&T, &T) -> Ordering> PartialEq for Ranked<'c, T, F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<'c, T, F: Fn(&T, &T) -> Ordering> Eq for Ranked<'c, T, F> {}
+
+impl<'c, T, F: Fn(&T, &T) -> Ordering> PartialOrd for Ranked<'c, T, F> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'c, T, F: Fn(&T, &T) -> Ordering> Ord for Ranked<'c, T, F> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.compare)(&other.element, &self.element)
+    }
+}
+
+/// Keep the `capacity` greatest elements seen so far.
+pub(crate) struct BoundedHeap<'c, T, F> {
+    heap: BinaryHeap<Ranked<'c, T, F>>,
+    capacity: usize,
+    compare: &'c F,
+}
+
+impl<'c, T, F: Fn(&T, &T) -> Ordering> BoundedHeap<'c, T, F> {
+    pub(crate) fn new(capacity: usize, compare: &'c F) -> Self {
+        BoundedHeap {
+            heap: BinaryHeap::new(),
+            capacity,
+            compare,
+        }
+    }
+    pub(crate) fn push(mut self, element: T) -> Self {
+        if self.heap.len() < self.capacity {
+            self.heap.push(Ranked {
+                element,
+                compare: self.compare,
+            })
+        } else if let Some(mut worst) = self.heap.peek_mut() {
+            if (self.compare)(&element, &worst.element) == Ordering::Greater {
+                // the heap is fixed when worst goes out of scope
+                worst.element = element
+            }
+        }
+        self
+    }
+    pub(crate) fn merge(self, other: Self) -> Self {
+        let (larger, smaller) = if self.heap.len() >= other.heap.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        smaller
+            .heap
+            .into_iter()
+            .fold(larger, |heap, ranked| heap.push(ranked.element))
+    }
+    /// Return all kept elements, greatest first.
+    pub(crate) fn into_sorted_vec(self) -> Vec<T> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|ranked| ranked.element)
+            .collect()
+    }
+}
@@ -0,0 +1,382 @@
+//! Set operations on sorted slices, writing into an output buffer.
+//! Inputs are divided at value boundaries (all copies of a value end up on the same side)
+//! so that each part can be processed independently.
+//! Each part writes at the start of its own output region.
+//! Parts are then compacted: a prefix sum on their sizes gives their final offsets
+//! and all of them are moved in parallel.
+//! Like `std`'s `BTreeSet` operations these work on multisets:
+//! a value appearing m times in a and n times in b appears
+//! max(m, n) times in the union, min(m, n) times in the intersection,
+//! m - n times in the difference and |m - n| times in the symmetric difference.
+use crate::algorithms::partition::with_block_offsets;
+use crate::prelude::*;
+use crate::utils::slice_utils::SharedSlice;
+use std::cmp::Ordering;
+use std::ptr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Operation {
+    Union,
+    Intersection,
+    Difference,
+    SymmetricDifference,
+    Dedup,
+}
+
+impl Operation {
+    /// Do we keep elements of a which are smaller than b's head.
+    pub(crate) fn keeps_a(self) -> bool {
+        matches!(
+            self,
+            Operation::Union | Operation::Difference | Operation::SymmetricDifference
+        )
+    }
+    /// Do we keep elements of b which are smaller than a's head.
+    pub(crate) fn keeps_b(self) -> bool {
+        matches!(self, Operation::Union | Operation::SymmetricDifference)
+    }
+    /// Do we keep (one copy of) equal heads.
+    pub(crate) fn keeps_common(self) -> bool {
+        matches!(self, Operation::Union | Operation::Intersection)
+    }
+    /// Bounds on the size of the result for inputs of given sizes.
+    pub(crate) fn sizes(self, a: usize, b: usize) -> (usize, usize) {
+        match self {
+            Operation::Union => (a.max(b), a + b),
+            Operation::Intersection => (0, a.min(b)),
+            Operation::Difference => (a.saturating_sub(b), a),
+            Operation::SymmetricDifference => (0, a + b),
+            Operation::Dedup => (a.min(1), a),
+        }
+    }
+}
+
+/// Are all elements of both sorted sequences equal.
+pub(crate) fn all_equal<V, A, B>(a_len: usize, b_len: usize, a: A, b: B) -> bool
+where
+    V: Ord,
+    A: Fn(usize) -> V,
+    B: Fn(usize) -> V,
+{
+    let first = match (a_len, b_len) {
+        (0, 0) => return true,
+        (0, _) => b(0),
+        (_, 0) => a(0),
+        _ => a(0).min(b(0)),
+    };
+    let last = match (a_len, b_len) {
+        (0, _) => b(b_len - 1),
+        (_, 0) => a(a_len - 1),
+        _ => a(a_len - 1).max(b(b_len - 1)),
+    };
+    first == last
+}
+
+fn partition_point<V, A: Fn(usize) -> V, P: Fn(V) -> bool>(
+    len: usize,
+    value: A,
+    predicate: P,
+) -> usize {
+    let (mut start, mut end) = (0, len);
+    while start < end {
+        let mid = (start + end) / 2;
+        if predicate(value(mid)) {
+            start = mid + 1;
+        } else {
+            end = mid;
+        }
+    }
+    start
+}
+
+/// Split two sorted sequences at a value boundary, as close to the middle as possible.
+/// All elements on the left are strictly smaller than all elements on the right.
+/// PRECONDITION: not all elements are equal.
+pub(crate) fn value_boundary_split<V, A, B>(
+    a_len: usize,
+    b_len: usize,
+    a: A,
+    b: B,
+) -> (usize, usize)
+where
+    V: Ord,
+    A: Fn(usize) -> V,
+    B: Fn(usize) -> V,
+{
+    debug_assert!(!all_equal(a_len, b_len, &a, &b));
+    let split_before = |pivot: &V| {
+        (
+            partition_point(a_len, &a, |v| v < *pivot),
+            partition_point(b_len, &b, |v| v < *pivot),
+        )
+    };
+    let split_after = |pivot: &V| {
+        (
+            partition_point(a_len, &a, |v| v <= *pivot),
+            partition_point(b_len, &b, |v| v <= *pivot),
+        )
+    };
+    let balanced = |(left_a, left_b): (usize, usize)| {
+        let left = left_a + left_b;
+        left != 0 && left != a_len + b_len
+    };
+    let pivot = if a_len >= b_len {
+        a(a_len / 2)
+    } else {
+        b(b_len / 2)
+    };
+    let before = split_before(&pivot);
+    if balanced(before) {
+        return before;
+    }
+    let after = split_after(&pivot);
+    if balanced(after) {
+        return after;
+    }
+    // the pivot is the only value of one sequence, just cut before the largest value.
+    let largest = match (a_len, b_len) {
+        (0, _) => b(b_len - 1),
+        (_, 0) => a(a_len - 1),
+        _ => a(a_len - 1).max(b(b_len - 1)),
+    };
+    split_before(&largest)
+}
+
+/// Set operation between the remaining parts of a and b.
+/// Results are written at the start of `out`.
+struct SetOperator<'a, T> {
+    a: &'a [T],
+    b: &'a [T],
+    // last element consumed, only used by dedup
+    last: Option<&'a T>,
+    out: &'a mut [T],
+    written: usize,
+    operation: Operation,
+}
+
+impl<'a, T: Copy + Ord> SetOperator<'a, T> {
+    fn emit(&mut self, e: T) {
+        self.out[self.written] = e;
+        self.written += 1;
+    }
+    fn dedup(&mut self, limit: usize) {
+        let (todo, remaining) = self.a.split_at(limit.min(self.a.len()));
+        self.a = remaining;
+        for e in todo {
+            if self.last != Some(e) {
+                self.emit(*e);
+            }
+            self.last = Some(e);
+        }
+    }
+    fn operate(&mut self, limit: usize) {
+        if self.operation == Operation::Dedup {
+            return self.dedup(limit);
+        }
+        for _ in 0..limit {
+            let ordering = match (self.a.first(), self.b.first()) {
+                (None, None) => return,
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(a), Some(b)) => a.cmp(b),
+            };
+            match ordering {
+                Ordering::Less => {
+                    if self.operation.keeps_a() {
+                        self.emit(self.a[0]);
+                    }
+                    self.a = &self.a[1..];
+                }
+                Ordering::Greater => {
+                    if self.operation.keeps_b() {
+                        self.emit(self.b[0]);
+                    }
+                    self.b = &self.b[1..];
+                }
+                Ordering::Equal => {
+                    if self.operation.keeps_common() {
+                        self.emit(self.a[0]);
+                    }
+                    self.a = &self.a[1..];
+                    self.b = &self.b[1..];
+                }
+            }
+        }
+    }
+}
+
+impl<'a, T: Copy + Ord> Divisible for SetOperator<'a, T> {
+    type Controlled = False;
+    fn should_be_divided(&self) -> bool {
+        let (a, b) = (self.a, self.b);
+        self.a.len() + self.b.len() >= 2 && !all_equal(a.len(), b.len(), |i| &a[i], |i| &b[i])
+    }
+    fn divide(self) -> (Self, Self) {
+        let (a, b) = (self.a, self.b);
+        let (a_mid, b_mid) = value_boundary_split(a.len(), b.len(), |i| &a[i], |i| &b[i]);
+        let (left_a, right_a) = a.split_at(a_mid);
+        let (left_b, right_b) = b.split_at(b_mid);
+        let (left_out, right_out) = self
+            .out
+            .split_at_mut(self.written + self.operation.sizes(a_mid, b_mid).1);
+        (
+            SetOperator {
+                a: left_a,
+                b: left_b,
+                last: self.last,
+                out: left_out,
+                written: self.written,
+                operation: self.operation,
+            },
+            SetOperator {
+                a: right_a,
+                b: right_b,
+                last: None,
+                out: right_out,
+                written: 0,
+                operation: self.operation,
+            },
+        )
+    }
+    fn divide_at(self, _index: usize) -> (Self, Self) {
+        self.divide()
+    }
+}
+
+fn adaptive_set_operation<T>(a: &[T], b: &[T], output: &mut [T], operation: Operation) -> usize
+where
+    T: Copy + Ord + Send + Sync,
+{
+    assert!(
+        output.len() >= operation.sizes(a.len(), b.len()).1,
+        "output buffer is too small"
+    );
+    let destination = SharedSlice(output.as_mut_ptr());
+    let operator = SetOperator {
+        a,
+        b,
+        last: None,
+        out: output,
+        written: 0,
+        operation,
+    };
+    // parts' results are staged out of the output since their final places
+    // may overlap results of other parts which have not moved yet.
+    let parts: Vec<Vec<T>> = operator
+        .work(
+            |o| o.a.is_empty() && o.b.is_empty(),
+            |o, limit| o.operate(limit),
+        )
+        .micro_block_sizes(1024, 10_000)
+        .map(|o| o.out[..o.written].to_vec())
+        .collect();
+    let destination = &destination;
+    with_block_offsets(parts, Vec::len, |part, start| unsafe {
+        ptr::copy_nonoverlapping(part.as_ptr(), destination.0.add(start), part.len())
+    })
+}
+
+/// Parallel union of sorted slices `a` and `b`, written at the start of `output`.
+/// `output` must be at least as large as both inputs together.
+/// Returns the number of elements written.
+///
+/// # Example:
+///
+/// ```
+/// use kvik::adaptive_union;
+/// let a: Vec<u32> = (0..10_000).map(|i| 2 * i).collect();
+/// let b: Vec<u32> = (0..10_000).map(|i| 3 * i).collect();
+/// let mut output = vec![0; 20_000];
+/// let size = adaptive_union(&a, &b, &mut output);
+/// assert_eq!(size, 10_000 + 10_000 - 3_334);
+/// assert!(output[..size].windows(2).all(|w| w[0] < w[1]));
+/// ```
+pub fn adaptive_union<T: Copy + Ord + Send + Sync>(a: &[T], b: &[T], output: &mut [T]) -> usize {
+    adaptive_set_operation(a, b, output, Operation::Union)
+}
+
+/// Parallel intersection of sorted slices `a` and `b`, written at the start of `output`.
+/// `output` must be at least as large as `a`.
+/// Returns the number of elements written.
+///
+/// # Example:
+///
+/// ```
+/// use kvik::adaptive_intersection;
+/// let a: Vec<u32> = (0..10_000).map(|i| 2 * i).collect();
+/// let b: Vec<u32> = (0..10_000).map(|i| 3 * i).collect();
+/// let mut output = vec![0; 10_000];
+/// let size = adaptive_intersection(&a, &b, &mut output);
+/// assert!(output[..size].iter().enumerate().all(|(i, &e)| e == 6 * i as u32));
+/// ```
+pub fn adaptive_intersection<T: Copy + Ord + Send + Sync>(
+    a: &[T],
+    b: &[T],
+    output: &mut [T],
+) -> usize {
+    adaptive_set_operation(a, b, output, Operation::Intersection)
+}
+
+/// Parallel difference of sorted slices `a` and `b`, written at the start of `output`.
+/// `output` must be at least as large as `a`.
+/// Returns the number of elements written.
+///
+/// # Example:
+///
+/// ```
+/// use kvik::adaptive_difference;
+/// let a: Vec<u32> = (0..10_000).collect();
+/// let b: Vec<u32> = (0..5_000).map(|i| 2 * i).collect();
+/// let mut output = vec![0; 10_000];
+/// let size = adaptive_difference(&a, &b, &mut output);
+/// assert!(output[..size].iter().enumerate().all(|(i, &e)| e == 2 * i as u32 + 1));
+/// ```
+pub fn adaptive_difference<T: Copy + Ord + Send + Sync>(
+    a: &[T],
+    b: &[T],
+    output: &mut [T],
+) -> usize {
+    adaptive_set_operation(a, b, output, Operation::Difference)
+}
+
+/// Parallel symmetric difference of sorted slices `a` and `b`, written at the start of `output`.
+/// `output` must be at least as large as both inputs together.
+/// Returns the number of elements written.
+///
+/// # Example:
+///
+/// ```
+/// use kvik::adaptive_symmetric_difference;
+/// let a: Vec<u32> = (0..10_000).collect();
+/// let b: Vec<u32> = (5_000..15_000).collect();
+/// let mut output = vec![0; 20_000];
+/// let size = adaptive_symmetric_difference(&a, &b, &mut output);
+/// assert_eq!(size, 10_000);
+/// assert_eq!(output[4_999..5_001], [4_999, 10_000]);
+/// ```
+pub fn adaptive_symmetric_difference<T: Copy + Ord + Send + Sync>(
+    a: &[T],
+    b: &[T],
+    output: &mut [T],
+) -> usize {
+    adaptive_set_operation(a, b, output, Operation::SymmetricDifference)
+}
+
+/// Parallel removal of consecutive duplicates of sorted slice `input`,
+/// written at the start of `output`.
+/// `output` must be at least as large as `input`.
+/// Returns the number of elements written.
+///
+/// # Example:
+///
+/// ```
+/// use kvik::adaptive_dedup;
+/// let input: Vec<u32> = (0..100_000).map(|i| i / 10).collect();
+/// let mut output = vec![0; 100_000];
+/// let size = adaptive_dedup(&input, &mut output);
+/// assert_eq!(size, 10_000);
+/// assert!(output[..size].iter().enumerate().all(|(i, &e)| e == i as u32));
+/// ```
+pub fn adaptive_dedup<T: Copy + Ord + Send + Sync>(input: &[T], output: &mut [T]) -> usize {
+    adaptive_set_operation(input, &[], output, Operation::Dedup)
+}
@@ -2,7 +2,10 @@ use crate::adaptive_slice_merge;
 use crate::prelude::*;
 #[cfg(feature = "logs")]
 use rayon_logs::subgraph;
-fn fuse_slices<'a: 'c, 'b: 'c, 'c, T: 'a + 'b>(s1: &'a mut [T], s2: &'b mut [T]) -> &'c mut [T] {
+pub(crate) fn fuse_slices<'a: 'c, 'b: 'c, 'c, T: 'a + 'b>(
+    s1: &'a mut [T],
+    s2: &'b mut [T],
+) -> &'c mut [T] {
     let ptr1 = s1.as_mut_ptr();
     unsafe {
         assert_eq!(ptr1.add(s1.len()) as *const T, s2.as_ptr(),);
@@ -39,8 +42,12 @@ pub fn slice_par_sort<T: Copy + Ord + Send + Sync>(input: &mut [T]) {
             }
         })
         .join_context_policy(
-            (2.0 * (rayon::current_num_threads() as f32).log2().ceil()
-                - (rayon::current_num_threads() as f32).log2().floor()) as u32,
+            (2.0 * (crate::executor::current_num_threads() as f32)
+                .log2()
+                .ceil()
+                - (crate::executor::current_num_threads() as f32)
+                    .log2()
+                    .floor()) as u32,
         )
         .depjoin()
         .even_levels()
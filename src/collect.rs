@@ -0,0 +1,512 @@
+//! Collecting parallel iterators into vectors.
+//! Enumerable iterators know their final length: we allocate once
+//! and each leaf writes its elements directly into its own part of the buffer.
+use crate::prelude::*;
+use crate::traits::ReduceConsumer;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, LinkedList};
+use std::hash::{BuildHasher, Hash};
+use std::mem::MaybeUninit;
+
+/// Collect strategy, selected by the `Enumerable` marker of the iterator.
+/// The module is private so this trait cannot be named outside of the crate.
+pub trait VecCollector: Sized {
+    fn collect_vec<I>(iter: I) -> Vec<I::Item>
+    where
+        I: ParallelIterator<Enumerable = Self>;
+}
+
+impl VecCollector for False {
+    fn collect_vec<I>(iter: I) -> Vec<I::Item>
+    where
+        I: ParallelIterator<Enumerable = Self>,
+    {
+        let l = iter
+            .fold(Vec::new, |mut v, e| {
+                v.push(e);
+                v
+            })
+            .map(|v| std::iter::once(v).collect::<LinkedList<Vec<I::Item>>>())
+            .reduce(LinkedList::new, |mut l1, mut l2| {
+                l1.append(&mut l2);
+                l1
+            });
+
+        let mut iter_list = l.into_iter();
+        let first = iter_list.next();
+
+        if let Some(first) = first {
+            iter_list.fold(first, |mut v, mut v2| {
+                v.append(&mut v2);
+                v
+            })
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+impl VecCollector for True {
+    fn collect_vec<I>(iter: I) -> Vec<I::Item>
+    where
+        I: ParallelIterator<Enumerable = Self>,
+    {
+        return iter.with_producer(Callback);
+        struct Callback;
+        impl<T: Send> ProducerCallback<T> for Callback {
+            type Output = Vec<T>;
+            fn call<P>(self, producer: P) -> Vec<T>
+            where
+                P: Producer<Item = T>,
+            {
+                let len = producer.length();
+                let mut vector = Vec::with_capacity(len);
+                let producer = CollectProducer {
+                    base: producer,
+                    slots: &mut vector.spare_capacity_mut()[..len],
+                };
+                let written = ReduceConsumer {
+                    op: &Written::merge,
+                    identity: &Written::empty,
+                }
+                .consume_producer(producer);
+                assert_eq!(
+                    written.len, len,
+                    "iterator yielded an unexpected number of elements"
+                );
+                // the vector now owns the elements
+                std::mem::forget(written);
+                unsafe { vector.set_len(len) }
+                vector
+            }
+        }
+    }
+}
+
+/// Contiguous range of initialized slots in the output buffer.
+/// If anything panics before the end of the collect, dropping it drops its elements.
+struct Written<'v, T> {
+    start: *mut T,
+    len: usize,
+    marker: std::marker::PhantomData<&'v mut T>,
+}
+
+unsafe impl<'v, T: Send> Send for Written<'v, T> {}
+
+impl<'v, T> Written<'v, T> {
+    fn empty() -> Self {
+        Written {
+            start: std::ptr::NonNull::dangling().as_ptr(),
+            len: 0,
+            marker: std::marker::PhantomData,
+        }
+    }
+    fn merge(mut left: Self, right: Self) -> Self {
+        if left.len == 0 {
+            right
+        } else if unsafe { left.start.add(left.len) } == right.start {
+            left.len += right.len;
+            std::mem::forget(right);
+            left
+        } else {
+            // not adjacent: right gets dropped and the final length check fails
+            left
+        }
+    }
+}
+
+impl<'v, T> Drop for Written<'v, T> {
+    fn drop(&mut self) {
+        unsafe { std::ptr::drop_in_place(std::ptr::slice_from_raw_parts_mut(self.start, self.len)) }
+    }
+}
+
+/// Write each element of the base producer into its slot in the output buffer.
+/// Instead of elements we yield the ranges of slots written.
+struct CollectProducer<'v, P: Producer> {
+    base: P,
+    slots: &'v mut [MaybeUninit<P::Item>],
+}
+
+impl<'v, P: Producer> CollectProducer<'v, P> {
+    fn write(slot: &'v mut MaybeUninit<P::Item>, element: P::Item) -> Written<'v, P::Item> {
+        Written {
+            start: slot.write(element),
+            len: 1,
+            marker: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<'v, P: Producer> Iterator for CollectProducer<'v, P> {
+    type Item = Written<'v, P::Item>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let element = self.base.next()?;
+        let (slot, remaining) = std::mem::take(&mut self.slots)
+            .split_first_mut()
+            .expect("iterator yielded more elements than announced");
+        self.slots = remaining;
+        Some(Self::write(slot, element))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.base.size_hint()
+    }
+}
+
+impl<'v, P: Producer> DoubleEndedIterator for CollectProducer<'v, P> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let element = self.base.next_back()?;
+        let (slot, remaining) = std::mem::take(&mut self.slots)
+            .split_last_mut()
+            .expect("iterator yielded more elements than announced");
+        self.slots = remaining;
+        Some(Self::write(slot, element))
+    }
+}
+
+impl<'v, P: Producer> Divisible for CollectProducer<'v, P> {
+    type Controlled = P::Controlled;
+    fn should_be_divided(&self) -> bool {
+        self.base.should_be_divided()
+    }
+    fn divide(self) -> (Self, Self) {
+        let (left, right) = self.base.divide();
+        let (left_slots, right_slots) = self.slots.split_at_mut(left.length());
+        (
+            CollectProducer {
+                base: left,
+                slots: left_slots,
+            },
+            CollectProducer {
+                base: right,
+                slots: right_slots,
+            },
+        )
+    }
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        let (left, right) = self.base.divide_at(index);
+        let (left_slots, right_slots) = self.slots.split_at_mut(left.length());
+        (
+            CollectProducer {
+                base: left,
+                slots: left_slots,
+            },
+            CollectProducer {
+                base: right,
+                slots: right_slots,
+            },
+        )
+    }
+}
+
+impl<'v, P: Producer> Producer for CollectProducer<'v, P>
+where
+    P::Item: Send,
+{
+    fn sizes(&self) -> (usize, Option<usize>) {
+        self.base.sizes()
+    }
+    fn preview(&self, _index: usize) -> Self::Item {
+        panic!("you cannot preview a collect")
+    }
+    fn scheduler<'s, Q: 's, R: 's>(&self) -> Box<dyn Scheduler<Q, R> + 's>
+    where
+        Q: Producer,
+        Q::Item: Send,
+        R: Reducer<Q::Item>,
+    {
+        self.base.scheduler()
+    }
+    fn partial_fold<B, F>(&mut self, init: B, fold_op: F, limit: usize) -> B
+    where
+        B: Send,
+        F: Fn(B, Self::Item) -> B,
+    {
+        let slots = std::mem::take(&mut self.slots);
+        let available = slots.len();
+        let block = Written {
+            start: slots.as_mut_ptr() as *mut P::Item,
+            len: 0,
+            marker: std::marker::PhantomData,
+        };
+        // on panic the block is dropped with the fold's state, dropping its elements
+        let block = self.base.partial_fold(
+            block,
+            |mut block, element| {
+                assert!(
+                    block.len < available,
+                    "iterator yielded more elements than announced"
+                );
+                // slots are used in order, each exactly once
+                unsafe { block.start.add(block.len).write(element) };
+                block.len += 1;
+                block
+            },
+            limit,
+        );
+        self.slots = &mut slots[block.len..];
+        fold_op(init, block)
+    }
+    fn micro_block_sizes(&self) -> (usize, usize) {
+        self.base.micro_block_sizes()
+    }
+}
+
+/// Fold each block into its own collection, then merge them pairwise.
+fn fold_and_merge<I, C, M>(iter: I, merge: M) -> C
+where
+    I: ParallelIterator,
+    C: Default + Extend<I::Item> + Send,
+    M: Fn(C, C) -> C + Sync + Send,
+{
+    iter.fold(C::default, |mut collection, e| {
+        collection.extend(std::iter::once(e));
+        collection
+    })
+    .reduce(C::default, merge)
+}
+
+/// Fold each block into its own collection and gather them, in order, in a list.
+fn fold_into_list<I, C>(iter: I) -> LinkedList<C>
+where
+    I: ParallelIterator,
+    C: Default + Extend<I::Item> + Send,
+{
+    iter.fold(C::default, |mut collection, e| {
+        collection.extend(std::iter::once(e));
+        collection
+    })
+    .map(|collection| std::iter::once(collection).collect::<LinkedList<C>>())
+    .reduce(LinkedList::new, |mut left, mut right| {
+        left.append(&mut right);
+        left
+    })
+}
+
+impl<K, V, S> FromParallelIterator<(K, V)> for HashMap<K, V, S>
+where
+    K: Eq + Hash + Send + Sync,
+    V: Send + Sync,
+    S: BuildHasher + Default + Send,
+{
+    /// Each block fills its own map, the largest map of each pair absorbs the other.
+    /// As for sequential iterators, the last value of a key is the one kept.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// use std::collections::HashMap;
+    /// let squares: HashMap<u32, u32> = (0u32..1000).into_par_iter().map(|i| (i, i * i)).collect();
+    /// assert_eq!(squares[&30], 900);
+    /// ```
+    fn from_par_iter<I: ParallelIterator<Item = (K, V)>>(iter: I) -> Self {
+        fold_and_merge(iter, |mut left: HashMap<K, V, S>, mut right| {
+            if left.len() >= right.len() {
+                left.extend(right);
+                left
+            } else {
+                for (key, value) in left {
+                    right.entry(key).or_insert(value);
+                }
+                right
+            }
+        })
+    }
+}
+
+impl<T, S> FromParallelIterator<T> for HashSet<T, S>
+where
+    T: Eq + Hash + Send + Sync,
+    S: BuildHasher + Default + Send,
+{
+    /// Each block fills its own set, the largest set of each pair absorbs the other.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// use std::collections::HashSet;
+    /// let remainders: HashSet<u32> = (0u32..1000).into_par_iter().map(|i| i % 7).collect();
+    /// assert_eq!(remainders.len(), 7);
+    /// ```
+    fn from_par_iter<I: ParallelIterator<Item = T>>(iter: I) -> Self {
+        fold_and_merge(iter, |left: HashSet<T, S>, right| {
+            let (mut larger, smaller) = if left.len() >= right.len() {
+                (left, right)
+            } else {
+                (right, left)
+            };
+            larger.extend(smaller);
+            larger
+        })
+    }
+}
+
+impl<K, V> FromParallelIterator<(K, V)> for BTreeMap<K, V>
+where
+    K: Ord + Send + Sync,
+    V: Send + Sync,
+{
+    /// Each block fills its own tree, trees are then merged pairwise.
+    /// As for sequential iterators, the last value of a key is the one kept.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// use std::collections::BTreeMap;
+    /// let last: BTreeMap<u32, u32> = (0u32..1000).into_par_iter().map(|i| (i % 10, i)).collect();
+    /// assert_eq!(last.values().copied().collect::<Vec<_>>(), (990..1000).collect::<Vec<_>>());
+    /// ```
+    fn from_par_iter<I: ParallelIterator<Item = (K, V)>>(iter: I) -> Self {
+        fold_and_merge(iter, |mut left: BTreeMap<K, V>, mut right| {
+            left.append(&mut right);
+            left
+        })
+    }
+}
+
+impl<T> FromParallelIterator<T> for BTreeSet<T>
+where
+    T: Ord + Send + Sync,
+{
+    /// Each block fills its own tree, trees are then merged pairwise.
+    fn from_par_iter<I: ParallelIterator<Item = T>>(iter: I) -> Self {
+        fold_and_merge(iter, |mut left: BTreeSet<T>, mut right| {
+            left.append(&mut right);
+            left
+        })
+    }
+}
+
+impl<T> FromParallelIterator<T> for LinkedList<T>
+where
+    T: Send + Sync,
+{
+    /// Each block fills its own list, lists are then appended in constant time.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// use std::collections::LinkedList;
+    /// let l: LinkedList<u32> = (0u32..100).into_par_iter().filter(|i| i % 2 == 0).collect();
+    /// assert!(l.iter().copied().eq((0..100).step_by(2)));
+    /// ```
+    fn from_par_iter<I: ParallelIterator<Item = T>>(iter: I) -> Self {
+        fold_and_merge(iter, |mut left: LinkedList<T>, mut right| {
+            left.append(&mut right);
+            left
+        })
+    }
+}
+
+/// Append the strings built by each block, allocating only once.
+fn append_strings(string: &mut String, blocks: LinkedList<String>) {
+    string.reserve(blocks.iter().map(|s| s.len()).sum());
+    blocks.iter().for_each(|block| string.push_str(block));
+}
+
+fn concatenate_strings(blocks: LinkedList<String>) -> String {
+    let mut string = String::new();
+    append_strings(&mut string, blocks);
+    string
+}
+
+impl FromParallelIterator<char> for String {
+    /// # Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// let s: String = (0u32..26)
+    ///     .into_par_iter()
+    ///     .map(|i| std::char::from_u32('a' as u32 + i).unwrap())
+    ///     .collect();
+    /// assert_eq!(s, "abcdefghijklmnopqrstuvwxyz");
+    /// ```
+    fn from_par_iter<I: ParallelIterator<Item = char>>(iter: I) -> Self {
+        concatenate_strings(fold_into_list(iter))
+    }
+}
+
+impl<'a> FromParallelIterator<&'a str> for String {
+    /// # Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// let words = vec!["a", "b", "c"];
+    /// let s: String = words.par_iter().map(|w| *w).collect();
+    /// assert_eq!(s, "abc");
+    /// ```
+    fn from_par_iter<I: ParallelIterator<Item = &'a str>>(iter: I) -> Self {
+        concatenate_strings(fold_into_list(iter))
+    }
+}
+
+impl FromParallelIterator<String> for String {
+    fn from_par_iter<I: ParallelIterator<Item = String>>(iter: I) -> Self {
+        concatenate_strings(fold_into_list(iter))
+    }
+}
+
+/// Most collections are extended with a collection built in parallel.
+macro_rules! extend_by_collecting {
+    ($collection:ty, $item:ty, [$($generics:tt)*], [$($bounds:tt)*]) => {
+        impl<$($generics)*> ParallelExtend<$item> for $collection
+        where
+            $($bounds)*
+        {
+            fn par_extend<I: ParallelIterator<Item = $item>>(&mut self, iter: I) {
+                let collected: $collection = iter.collect();
+                self.extend(collected)
+            }
+        }
+    };
+}
+
+extend_by_collecting!(
+    HashMap<K, V, S>,
+    (K, V),
+    [K, V, S],
+    [K: Eq + Hash + Send + Sync, V: Send + Sync, S: BuildHasher + Default + Send]
+);
+extend_by_collecting!(
+    HashSet<T, S>,
+    T,
+    [T, S],
+    [T: Eq + Hash + Send + Sync, S: BuildHasher + Default + Send]
+);
+extend_by_collecting!(BTreeSet<T>, T, [T], [T: Ord + Send + Sync]);
+
+impl<A: Send + Sync> ParallelExtend<A> for Vec<A> {
+    fn par_extend<I: ParallelIterator<Item = A>>(&mut self, iter: I) {
+        let mut collected: Vec<A> = iter.collect();
+        self.append(&mut collected)
+    }
+}
+
+impl<K: Ord + Send + Sync, V: Send + Sync> ParallelExtend<(K, V)> for BTreeMap<K, V> {
+    fn par_extend<I: ParallelIterator<Item = (K, V)>>(&mut self, iter: I) {
+        let mut collected: BTreeMap<K, V> = iter.collect();
+        self.append(&mut collected)
+    }
+}
+
+impl<T: Send + Sync> ParallelExtend<T> for LinkedList<T> {
+    fn par_extend<I: ParallelIterator<Item = T>>(&mut self, iter: I) {
+        let mut collected: LinkedList<T> = iter.collect();
+        self.append(&mut collected)
+    }
+}
+
+impl ParallelExtend<char> for String {
+    fn par_extend<I: ParallelIterator<Item = char>>(&mut self, iter: I) {
+        append_strings(self, fold_into_list(iter))
+    }
+}
+
+impl<'a> ParallelExtend<&'a str> for String {
+    fn par_extend<I: ParallelIterator<Item = &'a str>>(&mut self, iter: I) {
+        append_strings(self, fold_into_list(iter))
+    }
+}
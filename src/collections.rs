@@ -0,0 +1,824 @@
+//! Parallel iterators over the standard hash and B-tree collections and over
+//! hashbrown's hash tables.
+//!
+//! hashbrown tables divide over the buckets of their raw table.
+//! std does not give access to the buckets of its hash tables so they first
+//! gather their elements in a vector, which is linear and sequential.
+//! Shared iterations on B-trees divide with range queries on the key space and
+//! owned B-trees are split by rebuilding both halves. Since we cannot take two
+//! mutable ranges of the same tree at once, mutable iterations on B-trees
+//! gather references in a vector.
+use crate::prelude::*;
+use hashbrown::raw::{Bucket, RawTable};
+use std::collections::{btree_map, btree_set, BTreeMap, BTreeSet, HashMap, HashSet};
+use std::hash::{BuildHasher, Hash};
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+use std::ops::{Bound, Range};
+
+// hashbrown tables //
+
+/// How items are obtained from the full buckets of a raw table.
+pub trait Access<'a, T> {
+    type Item;
+    /// Set when items are moved out of the table.
+    /// Producers then drop the elements they did not yield.
+    const MOVES: bool;
+    /// # Safety
+    /// The bucket must be full, belong to a table living for `'a` and be
+    /// accessed only once.
+    unsafe fn access(bucket: Bucket<T>) -> Self::Item;
+}
+
+/// Shared references to the keys and values of a map.
+pub struct MapRefs;
+/// Shared references to the keys and mutable references to the values of a map.
+pub struct MapMuts;
+/// Shared references to the elements of a set.
+pub struct SetRefs;
+/// Key-value pairs moved out of a map.
+pub struct MapMoves;
+/// Elements moved out of a set.
+pub struct SetMoves;
+
+impl<'a, K: 'a, V: 'a> Access<'a, (K, V)> for MapRefs {
+    type Item = (&'a K, &'a V);
+    const MOVES: bool = false;
+    unsafe fn access(bucket: Bucket<(K, V)>) -> Self::Item {
+        let (key, value) = bucket.as_ref();
+        (key, value)
+    }
+}
+
+impl<'a, K: 'a, V: 'a> Access<'a, (K, V)> for MapMuts {
+    type Item = (&'a K, &'a mut V);
+    const MOVES: bool = false;
+    unsafe fn access(bucket: Bucket<(K, V)>) -> Self::Item {
+        let (key, value) = bucket.as_mut();
+        (&*key, value)
+    }
+}
+
+impl<'a, T: 'a> Access<'a, (T, ())> for SetRefs {
+    type Item = &'a T;
+    const MOVES: bool = false;
+    unsafe fn access(bucket: Bucket<(T, ())>) -> Self::Item {
+        &bucket.as_ref().0
+    }
+}
+
+impl<'a, K, V> Access<'a, (K, V)> for MapMoves {
+    type Item = (K, V);
+    const MOVES: bool = true;
+    unsafe fn access(bucket: Bucket<(K, V)>) -> Self::Item {
+        bucket.as_ptr().read()
+    }
+}
+
+impl<'a, T> Access<'a, (T, ())> for SetMoves {
+    type Item = T;
+    const MOVES: bool = true;
+    unsafe fn access(bucket: Bucket<(T, ())>) -> Self::Item {
+        bucket.as_ptr().read().0
+    }
+}
+
+/// Parallel iterator over a borrowed hashbrown table, dividing over its buckets.
+///
+/// # Example:
+///
+/// ```
+/// use kvik::prelude::*;
+/// let squares: hashbrown::HashMap<u32, u32> = (0..1000).map(|i| (i, i * i)).collect();
+/// let sum: u32 = squares.par_iter().map(|(k, _)| *k).reduce(|| 0, |a, b| a + b);
+/// assert_eq!(sum, 499_500);
+/// ```
+pub struct HashTableIter<'a, T, A> {
+    table: &'a RawTable<T>,
+    access: PhantomData<A>,
+}
+
+impl<'a, T, A> ParallelIterator for HashTableIter<'a, T, A>
+where
+    A: Access<'a, T>,
+    A::Item: Send,
+{
+    type Item = A::Item;
+    type Controlled = False;
+    type Enumerable = False;
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        callback.call(BucketsProducer::<T, A>::new(self.table))
+    }
+}
+
+/// Owning parallel iterator over a hashbrown table, dividing over its buckets.
+pub struct HashTableIntoIter<T, A> {
+    table: RawTable<T>,
+    access: PhantomData<A>,
+}
+
+/// A table whose elements are all moved out or dropped by the producers.
+struct Emptied<T>(RawTable<T>);
+
+impl<T> Drop for Emptied<T> {
+    fn drop(&mut self) {
+        // only the buckets are freed when the table is dropped
+        self.0.clear_no_drop()
+    }
+}
+
+macro_rules! moving_par_iter {
+    ($element:ty, $item:ty, $access:ty, [$($generics:tt)*]) => {
+        impl<$($generics)*> ParallelIterator for HashTableIntoIter<$element, $access> {
+            type Item = $item;
+            type Controlled = False;
+            type Enumerable = False;
+            fn with_producer<CB>(self, callback: CB) -> CB::Output
+            where
+                CB: ProducerCallback<Self::Item>,
+            {
+                let table = Emptied(self.table);
+                callback.call(BucketsProducer::<_, $access>::new(&table.0))
+            }
+        }
+    };
+}
+
+moving_par_iter!((K, V), (K, V), MapMoves, [K: Send, V: Send]);
+moving_par_iter!((T, ()), T, SetMoves, [T: Send]);
+
+/// Yield the items of all full buckets in the range.
+/// Moved elements which are not yielded are dropped with us.
+struct BucketsProducer<'a, T, A: Access<'a, T>> {
+    table: &'a RawTable<T>,
+    buckets: Range<usize>,
+    access: PhantomData<A>,
+}
+
+// buckets are accessed by a single producer, which only needs to send its items
+unsafe impl<'a, T, A: Access<'a, T>> Send for BucketsProducer<'a, T, A> where A::Item: Send {}
+
+impl<'a, T, A: Access<'a, T>> BucketsProducer<'a, T, A> {
+    fn new(table: &'a RawTable<T>) -> Self {
+        BucketsProducer {
+            table,
+            buckets: 0..table.buckets(),
+            access: PhantomData,
+        }
+    }
+    unsafe fn access(&self, index: usize) -> Option<A::Item> {
+        if self.table.is_bucket_full(index) {
+            Some(A::access(self.table.bucket(index)))
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, T, A: Access<'a, T>> Drop for BucketsProducer<'a, T, A> {
+    fn drop(&mut self) {
+        if A::MOVES {
+            self.for_each(std::mem::drop)
+        }
+    }
+}
+
+impl<'a, T, A: Access<'a, T>> Iterator for BucketsProducer<'a, T, A> {
+    type Item = A::Item;
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(index) = self.buckets.next() {
+            if let Some(item) = unsafe { self.access(index) } {
+                return Some(item);
+            }
+        }
+        None
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.buckets.len()))
+    }
+}
+
+impl<'a, T, A: Access<'a, T>> DoubleEndedIterator for BucketsProducer<'a, T, A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while let Some(index) = self.buckets.next_back() {
+            if let Some(item) = unsafe { self.access(index) } {
+                return Some(item);
+            }
+        }
+        None
+    }
+}
+
+impl<'a, T, A: Access<'a, T>> Divisible for BucketsProducer<'a, T, A>
+where
+    A::Item: Send,
+{
+    type Controlled = False;
+    fn should_be_divided(&self) -> bool {
+        self.buckets.len() >= 2
+    }
+    fn divide(self) -> (Self, Self) {
+        let mid = self.buckets.len() / 2;
+        self.divide_at(mid)
+    }
+    /// Divide at the given bucket.
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        // we give our buckets to the two halves
+        let me = ManuallyDrop::new(self);
+        let mid = me.buckets.start + index.min(me.buckets.len());
+        (
+            BucketsProducer {
+                table: me.table,
+                buckets: me.buckets.start..mid,
+                access: PhantomData,
+            },
+            BucketsProducer {
+                table: me.table,
+                buckets: mid..me.buckets.end,
+                access: PhantomData,
+            },
+        )
+    }
+}
+
+impl<'a, T, A: Access<'a, T>> Producer for BucketsProducer<'a, T, A>
+where
+    A::Item: Send,
+{
+    fn sizes(&self) -> (usize, Option<usize>) {
+        self.size_hint()
+    }
+    fn preview(&self, _index: usize) -> Self::Item {
+        panic!("hash tables are not previewable")
+    }
+    fn partial_fold<B, F>(&mut self, init: B, fold_op: F, limit: usize) -> B
+    where
+        B: Send,
+        F: Fn(B, Self::Item) -> B,
+    {
+        self.take(limit).fold(init, fold_op)
+    }
+}
+
+impl<'a, K: Sync, V: Sync, S> IntoParallelIterator for &'a hashbrown::HashMap<K, V, S> {
+    type Item = (&'a K, &'a V);
+    type Iter = HashTableIter<'a, (K, V), MapRefs>;
+    fn into_par_iter(self) -> Self::Iter {
+        HashTableIter {
+            table: self.raw_table(),
+            access: PhantomData,
+        }
+    }
+}
+
+impl<'a, K: Sync, V: Send, S> IntoParallelIterator for &'a mut hashbrown::HashMap<K, V, S> {
+    type Item = (&'a K, &'a mut V);
+    type Iter = HashTableIter<'a, (K, V), MapMuts>;
+    fn into_par_iter(self) -> Self::Iter {
+        HashTableIter {
+            table: self.raw_table(),
+            access: PhantomData,
+        }
+    }
+}
+
+impl<K: Send, V: Send, S> IntoParallelIterator for hashbrown::HashMap<K, V, S> {
+    type Item = (K, V);
+    type Iter = HashTableIntoIter<(K, V), MapMoves>;
+    fn into_par_iter(mut self) -> Self::Iter {
+        HashTableIntoIter {
+            table: std::mem::replace(self.raw_table_mut(), RawTable::new()),
+            access: PhantomData,
+        }
+    }
+}
+
+impl<'a, T: Sync, S> IntoParallelIterator for &'a hashbrown::HashSet<T, S> {
+    type Item = &'a T;
+    type Iter = HashTableIter<'a, (T, ()), SetRefs>;
+    fn into_par_iter(self) -> Self::Iter {
+        HashTableIter {
+            table: self.raw_table(),
+            access: PhantomData,
+        }
+    }
+}
+
+impl<T: Send, S> IntoParallelIterator for hashbrown::HashSet<T, S> {
+    type Item = T;
+    type Iter = HashTableIntoIter<(T, ()), SetMoves>;
+    fn into_par_iter(mut self) -> Self::Iter {
+        HashTableIntoIter {
+            table: std::mem::replace(self.raw_table_mut(), RawTable::new()),
+            access: PhantomData,
+        }
+    }
+}
+
+// gathered elements //
+
+macro_rules! gathered_into_par_iter {
+    ($collection:ty, $item:ty, [$($generics:tt)*], [$($bounds:tt)*]) => {
+        impl<$($generics)*> IntoParallelIterator for $collection
+        where
+            $($bounds)*
+        {
+            type Item = $item;
+            type Iter = crate::vec::IntoIter<$item>;
+            fn into_par_iter(self) -> Self::Iter {
+                self.into_iter().collect::<Vec<_>>().into_par_iter()
+            }
+        }
+    };
+}
+
+gathered_into_par_iter!(
+    HashMap<K, V, S>,
+    (K, V),
+    [K, V, S],
+    [K: Eq + Hash + Send, V: Send, S: BuildHasher]
+);
+gathered_into_par_iter!(
+    &'a HashMap<K, V, S>,
+    (&'a K, &'a V),
+    ['a, K, V, S],
+    [K: Eq + Hash + Sync, V: Sync, S: BuildHasher]
+);
+gathered_into_par_iter!(
+    &'a mut HashMap<K, V, S>,
+    (&'a K, &'a mut V),
+    ['a, K, V, S],
+    [K: Eq + Hash + Sync, V: Send, S: BuildHasher]
+);
+gathered_into_par_iter!(
+    HashSet<T, S>,
+    T,
+    [T, S],
+    [T: Eq + Hash + Send, S: BuildHasher]
+);
+gathered_into_par_iter!(
+    &'a HashSet<T, S>,
+    &'a T,
+    ['a, T, S],
+    [T: Eq + Hash + Sync, S: BuildHasher]
+);
+gathered_into_par_iter!(
+    &'a mut BTreeMap<K, V>,
+    (&'a K, &'a mut V),
+    ['a, K, V],
+    [K: Ord + Sync, V: Send]
+);
+
+// B-trees //
+
+/// Parallel iterator over a `BTreeMap`, dividing with range queries.
+///
+/// std gives no access to the ranks of keys so finding the key at which we
+/// divide walks the range: divisions and previews are linear in the index.
+///
+/// # Example:
+///
+/// ```
+/// use kvik::prelude::*;
+/// use std::collections::BTreeMap;
+/// let squares: BTreeMap<u32, u32> = (0..1000).map(|i| (i, i * i)).collect();
+/// let keys: Vec<u32> = squares.par_iter().filter(|(_, s)| *s % 2 == 1).map(|(k, _)| *k).collect();
+/// assert_eq!(keys, (0..1000).filter(|k| k % 2 == 1).collect::<Vec<_>>());
+/// ```
+pub struct BTreeMapIter<'a, K, V> {
+    map: &'a BTreeMap<K, V>,
+}
+
+impl<'a, K: Ord + Sync, V: Sync> IntoParallelIterator for &'a BTreeMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type Iter = BTreeMapIter<'a, K, V>;
+    fn into_par_iter(self) -> Self::Iter {
+        BTreeMapIter { map: self }
+    }
+}
+
+impl<'a, K: Ord + Sync, V: Sync> ParallelIterator for BTreeMapIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+    type Controlled = True;
+    type Enumerable = True;
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        callback.call(BTreeMapProducer {
+            map: self.map,
+            range: self.map.range::<K, _>(..),
+            len: self.map.len(),
+        })
+    }
+}
+
+/// Remaining elements are the `len` first ones of `range`.
+struct BTreeMapProducer<'a, K, V> {
+    map: &'a BTreeMap<K, V>,
+    range: btree_map::Range<'a, K, V>,
+    len: usize,
+}
+
+impl<'a, K, V> Iterator for BTreeMapProducer<'a, K, V> {
+    type Item = (&'a K, &'a V);
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            None
+        } else {
+            self.len -= 1;
+            self.range.next()
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for BTreeMapProducer<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            None
+        } else {
+            self.len -= 1;
+            self.range.next_back()
+        }
+    }
+}
+
+impl<'a, K: Ord + Sync, V: Sync> Divisible for BTreeMapProducer<'a, K, V> {
+    type Controlled = True;
+    fn should_be_divided(&self) -> bool {
+        self.len >= 2
+    }
+    fn divide(self) -> (Self, Self) {
+        let mid = self.len / 2;
+        self.divide_at(mid)
+    }
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        let index = index.min(self.len);
+        if index == 0 || index == self.len {
+            let empty = BTreeMapProducer {
+                map: self.map,
+                range: self.range.clone(),
+                len: 0,
+            };
+            return if index == 0 {
+                (empty, self)
+            } else {
+                (self, empty)
+            };
+        }
+        let first = self.range.clone().next().unwrap().0;
+        let last = self.range.clone().next_back().unwrap().0;
+        let mid = self.range.clone().nth(index).unwrap().0;
+        (
+            BTreeMapProducer {
+                map: self.map,
+                range: self
+                    .map
+                    .range::<K, _>((Bound::Included(first), Bound::Excluded(mid))),
+                len: index,
+            },
+            BTreeMapProducer {
+                map: self.map,
+                range: self
+                    .map
+                    .range::<K, _>((Bound::Included(mid), Bound::Included(last))),
+                len: self.len - index,
+            },
+        )
+    }
+}
+
+impl<'a, K: Ord + Sync, V: Sync> Producer for BTreeMapProducer<'a, K, V> {
+    fn sizes(&self) -> (usize, Option<usize>) {
+        self.size_hint()
+    }
+    fn preview(&self, index: usize) -> Self::Item {
+        self.range.clone().nth(index).unwrap()
+    }
+    fn partial_fold<B, F>(&mut self, init: B, fold_op: F, limit: usize) -> B
+    where
+        B: Send,
+        F: Fn(B, Self::Item) -> B,
+    {
+        self.take(limit).fold(init, fold_op)
+    }
+}
+
+/// Parallel iterator over a `BTreeSet`, dividing with range queries.
+///
+/// Like for maps, divisions and previews are linear in the index.
+///
+/// # Example:
+///
+/// ```
+/// use kvik::prelude::*;
+/// use std::collections::BTreeSet;
+/// let set: BTreeSet<u32> = (0..1000).collect();
+/// assert_eq!(set.par_iter().map(|e| *e).reduce(|| 0, |a, b| a + b), 499_500);
+/// ```
+pub struct BTreeSetIter<'a, T> {
+    set: &'a BTreeSet<T>,
+}
+
+impl<'a, T: Ord + Sync> IntoParallelIterator for &'a BTreeSet<T> {
+    type Item = &'a T;
+    type Iter = BTreeSetIter<'a, T>;
+    fn into_par_iter(self) -> Self::Iter {
+        BTreeSetIter { set: self }
+    }
+}
+
+impl<'a, T: Ord + Sync> ParallelIterator for BTreeSetIter<'a, T> {
+    type Item = &'a T;
+    type Controlled = True;
+    type Enumerable = True;
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        callback.call(BTreeSetProducer {
+            set: self.set,
+            range: self.set.range::<T, _>(..),
+            len: self.set.len(),
+        })
+    }
+}
+
+/// Remaining elements are the `len` first ones of `range`.
+struct BTreeSetProducer<'a, T> {
+    set: &'a BTreeSet<T>,
+    range: btree_set::Range<'a, T>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for BTreeSetProducer<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            None
+        } else {
+            self.len -= 1;
+            self.range.next()
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for BTreeSetProducer<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            None
+        } else {
+            self.len -= 1;
+            self.range.next_back()
+        }
+    }
+}
+
+impl<'a, T: Ord + Sync> Divisible for BTreeSetProducer<'a, T> {
+    type Controlled = True;
+    fn should_be_divided(&self) -> bool {
+        self.len >= 2
+    }
+    fn divide(self) -> (Self, Self) {
+        let mid = self.len / 2;
+        self.divide_at(mid)
+    }
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        let index = index.min(self.len);
+        if index == 0 || index == self.len {
+            let empty = BTreeSetProducer {
+                set: self.set,
+                range: self.range.clone(),
+                len: 0,
+            };
+            return if index == 0 {
+                (empty, self)
+            } else {
+                (self, empty)
+            };
+        }
+        let first = self.range.clone().next().unwrap();
+        let last = self.range.clone().next_back().unwrap();
+        let mid = self.range.clone().nth(index).unwrap();
+        (
+            BTreeSetProducer {
+                set: self.set,
+                range: self
+                    .set
+                    .range::<T, _>((Bound::Included(first), Bound::Excluded(mid))),
+                len: index,
+            },
+            BTreeSetProducer {
+                set: self.set,
+                range: self
+                    .set
+                    .range::<T, _>((Bound::Included(mid), Bound::Included(last))),
+                len: self.len - index,
+            },
+        )
+    }
+}
+
+impl<'a, T: Ord + Sync> Producer for BTreeSetProducer<'a, T> {
+    fn sizes(&self) -> (usize, Option<usize>) {
+        self.size_hint()
+    }
+    fn preview(&self, index: usize) -> Self::Item {
+        self.range.clone().nth(index).unwrap()
+    }
+    fn partial_fold<B, F>(&mut self, init: B, fold_op: F, limit: usize) -> B
+    where
+        B: Send,
+        F: Fn(B, Self::Item) -> B,
+    {
+        self.take(limit).fold(init, fold_op)
+    }
+}
+
+impl<'a, K: Ord + Sync, V: Sync> PreviewableParallelIterator for BTreeMapIter<'a, K, V> {}
+impl<'a, T: Ord + Sync> PreviewableParallelIterator for BTreeSetIter<'a, T> {}
+
+// owned B-trees //
+
+/// Split the tree at the given index, returning the right part.
+/// Both parts are rebuilt from the ordered elements: this is linear in the size of the tree,
+/// as finding the element at the index would be anyway.
+fn split_tree<C>(tree: &mut C, index: usize) -> C
+where
+    C: Default + IntoIterator + std::iter::FromIterator<C::Item>,
+{
+    let mut elements = std::mem::take(tree).into_iter();
+    *tree = elements.by_ref().take(index).collect();
+    elements.collect()
+}
+
+/// Owning parallel iterator over a `BTreeMap`, dividing by rebuilding both halves.
+///
+/// # Example:
+///
+/// ```
+/// use kvik::prelude::*;
+/// use std::collections::BTreeMap;
+/// let names: BTreeMap<u32, String> = (0..1000).map(|i| (i, i.to_string())).collect();
+/// let names: Vec<String> = names.into_par_iter().map(|(_, n)| n).collect();
+/// assert_eq!(names[10], "10");
+/// ```
+pub struct BTreeMapIntoIter<K, V> {
+    map: BTreeMap<K, V>,
+}
+
+impl<K: Ord + Send, V: Send> IntoParallelIterator for BTreeMap<K, V> {
+    type Item = (K, V);
+    type Iter = BTreeMapIntoIter<K, V>;
+    fn into_par_iter(self) -> Self::Iter {
+        BTreeMapIntoIter { map: self }
+    }
+}
+
+impl<K: Ord + Send, V: Send> ParallelIterator for BTreeMapIntoIter<K, V> {
+    type Item = (K, V);
+    type Controlled = True;
+    type Enumerable = True;
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        callback.call(BTreeMapIntoProducer { map: self.map })
+    }
+}
+
+struct BTreeMapIntoProducer<K, V> {
+    map: BTreeMap<K, V>,
+}
+
+impl<K: Ord, V> Iterator for BTreeMapIntoProducer<K, V> {
+    type Item = (K, V);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.map.pop_first()
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.map.len(), Some(self.map.len()))
+    }
+}
+
+impl<K: Ord, V> DoubleEndedIterator for BTreeMapIntoProducer<K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.map.pop_last()
+    }
+}
+
+impl<K: Ord + Send, V: Send> Divisible for BTreeMapIntoProducer<K, V> {
+    type Controlled = True;
+    fn should_be_divided(&self) -> bool {
+        self.map.len() >= 2
+    }
+    fn divide(self) -> (Self, Self) {
+        let mid = self.map.len() / 2;
+        self.divide_at(mid)
+    }
+    fn divide_at(mut self, index: usize) -> (Self, Self) {
+        let right = split_tree(&mut self.map, index);
+        (self, BTreeMapIntoProducer { map: right })
+    }
+}
+
+impl<K: Ord + Send, V: Send> Producer for BTreeMapIntoProducer<K, V> {
+    fn sizes(&self) -> (usize, Option<usize>) {
+        self.size_hint()
+    }
+    fn preview(&self, _index: usize) -> Self::Item {
+        panic!("owned B-trees are not previewable")
+    }
+    fn partial_fold<B, F>(&mut self, init: B, fold_op: F, limit: usize) -> B
+    where
+        B: Send,
+        F: Fn(B, Self::Item) -> B,
+    {
+        self.take(limit).fold(init, fold_op)
+    }
+}
+
+/// Owning parallel iterator over a `BTreeSet`, dividing by rebuilding both halves.
+pub struct BTreeSetIntoIter<T> {
+    set: BTreeSet<T>,
+}
+
+impl<T: Ord + Send> IntoParallelIterator for BTreeSet<T> {
+    type Item = T;
+    type Iter = BTreeSetIntoIter<T>;
+    fn into_par_iter(self) -> Self::Iter {
+        BTreeSetIntoIter { set: self }
+    }
+}
+
+impl<T: Ord + Send> ParallelIterator for BTreeSetIntoIter<T> {
+    type Item = T;
+    type Controlled = True;
+    type Enumerable = True;
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        callback.call(BTreeSetIntoProducer { set: self.set })
+    }
+}
+
+struct BTreeSetIntoProducer<T> {
+    set: BTreeSet<T>,
+}
+
+impl<T: Ord> Iterator for BTreeSetIntoProducer<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.set.pop_first()
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.set.len(), Some(self.set.len()))
+    }
+}
+
+impl<T: Ord> DoubleEndedIterator for BTreeSetIntoProducer<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.set.pop_last()
+    }
+}
+
+impl<T: Ord + Send> Divisible for BTreeSetIntoProducer<T> {
+    type Controlled = True;
+    fn should_be_divided(&self) -> bool {
+        self.set.len() >= 2
+    }
+    fn divide(self) -> (Self, Self) {
+        let mid = self.set.len() / 2;
+        self.divide_at(mid)
+    }
+    fn divide_at(mut self, index: usize) -> (Self, Self) {
+        let right = split_tree(&mut self.set, index);
+        (self, BTreeSetIntoProducer { set: right })
+    }
+}
+
+impl<T: Ord + Send> Producer for BTreeSetIntoProducer<T> {
+    fn sizes(&self) -> (usize, Option<usize>) {
+        self.size_hint()
+    }
+    fn preview(&self, _index: usize) -> Self::Item {
+        panic!("owned B-trees are not previewable")
+    }
+    fn partial_fold<B, F>(&mut self, init: B, fold_op: F, limit: usize) -> B
+    where
+        B: Send,
+        F: Fn(B, Self::Item) -> B,
+    {
+        self.take(limit).fold(init, fold_op)
+    }
+}
@@ -0,0 +1,195 @@
+//! Executors decide where and when tasks run.
+//! Schedulers never call rayon directly but go through the current executor,
+//! which is the global rayon pool unless changed with the `on` adaptor.
+use crate::trace::EventKind;
+use std::cell::Cell;
+use std::time::Instant;
+
+/// Something able to run two tasks, possibly in parallel.
+/// Closures are passed as trait objects to keep executors usable as `&dyn Executor`.
+pub trait Executor: Sync {
+    /// Run both operations, possibly in parallel.
+    /// Each operation is told whether it migrated to another thread (was stolen).
+    fn join_context(
+        &self,
+        oper_a: &mut (dyn FnMut(bool) + Send),
+        oper_b: &mut (dyn FnMut(bool) + Send),
+    );
+    /// Run the given operation inside the executor's threads.
+    fn install(&self, op: &mut (dyn FnMut() + Send));
+    fn current_num_threads(&self) -> usize;
+    fn current_thread_index(&self) -> Option<usize>;
+    /// Is a thief asking the currently running left task for work.
+    /// Real executors rely on channels only, simulated ones decide here.
+    fn steal_requested(&self) -> bool {
+        false
+    }
+    /// Do we record events (see `Tracer`).
+    fn is_tracing(&self) -> bool {
+        false
+    }
+    /// Record an event happening on the current thread.
+    fn record(&self, _kind: EventKind, _start: Instant, _end: Instant) {}
+    /// Run given operation (and all kvik computations inside it) on this executor.
+    fn run<R, OP>(&self, op: OP) -> R
+    where
+        Self: Sized,
+        R: Send,
+        OP: FnOnce() -> R + Send,
+    {
+        run_on(self, op)
+    }
+}
+
+/// Run tasks on a rayon thread pool (the global one by default).
+#[derive(Default)]
+pub struct RayonExecutor {
+    pool: Option<rayon::ThreadPool>,
+}
+
+impl RayonExecutor {
+    /// Use rayon's global pool.
+    pub const fn global() -> Self {
+        RayonExecutor { pool: None }
+    }
+    /// Use our own pool.
+    pub fn new(pool: rayon::ThreadPool) -> Self {
+        RayonExecutor { pool: Some(pool) }
+    }
+}
+
+impl Executor for RayonExecutor {
+    fn join_context(
+        &self,
+        oper_a: &mut (dyn FnMut(bool) + Send),
+        oper_b: &mut (dyn FnMut(bool) + Send),
+    ) {
+        rayon::join_context(|c| oper_a(c.migrated()), |c| oper_b(c.migrated()));
+    }
+    fn install(&self, op: &mut (dyn FnMut() + Send)) {
+        match &self.pool {
+            Some(pool) => pool.install(op),
+            None => op(),
+        }
+    }
+    fn current_num_threads(&self) -> usize {
+        self.pool
+            .as_ref()
+            .map(|pool| pool.current_num_threads())
+            .unwrap_or_else(rayon::current_num_threads)
+    }
+    fn current_thread_index(&self) -> Option<usize> {
+        rayon::current_thread_index()
+    }
+}
+
+/// Run everything on the calling thread, left task first.
+/// Nothing is ever stolen so executions are fully deterministic.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct SequentialExecutor;
+
+impl Executor for SequentialExecutor {
+    fn join_context(
+        &self,
+        oper_a: &mut (dyn FnMut(bool) + Send),
+        oper_b: &mut (dyn FnMut(bool) + Send),
+    ) {
+        oper_a(false);
+        oper_b(false);
+    }
+    fn install(&self, op: &mut (dyn FnMut() + Send)) {
+        op()
+    }
+    fn current_num_threads(&self) -> usize {
+        1
+    }
+    fn current_thread_index(&self) -> Option<usize> {
+        Some(0)
+    }
+}
+
+static GLOBAL_EXECUTOR: RayonExecutor = RayonExecutor::global();
+
+thread_local! {
+    // the lifetime is erased but the pointer is only set for the duration of a `scoped` call.
+    static CURRENT_EXECUTOR: Cell<Option<*const (dyn Executor + 'static)>> = Cell::new(None);
+}
+
+/// Call `op` with the executor currently in use on this thread.
+pub(crate) fn with_current<R>(op: impl FnOnce(&dyn Executor) -> R) -> R {
+    match CURRENT_EXECUTOR.with(|current| current.get()) {
+        // safe since the executor outlives the `scoped` call which registered it.
+        Some(executor) => op(unsafe { &*executor }),
+        None => op(&GLOBAL_EXECUTOR),
+    }
+}
+
+/// Run `op` with `executor` registered as current one on this thread.
+fn scoped<R>(executor: &dyn Executor, op: impl FnOnce() -> R) -> R {
+    struct Restore(Option<*const (dyn Executor + 'static)>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CURRENT_EXECUTOR.with(|current| current.set(self.0))
+        }
+    }
+    let executor: *const (dyn Executor + '_) = executor;
+    let executor: *const (dyn Executor + 'static) = unsafe { std::mem::transmute(executor) };
+    let _restore = Restore(CURRENT_EXECUTOR.with(|current| current.replace(Some(executor))));
+    op()
+}
+
+/// Run `op` inside given executor.
+pub(crate) fn run_on<R, OP>(executor: &dyn Executor, op: OP) -> R
+where
+    R: Send,
+    OP: FnOnce() -> R + Send,
+{
+    let mut op = Some(op);
+    let mut result = None;
+    executor.install(&mut || result = Some(scoped(executor, op.take().unwrap())));
+    result.unwrap()
+}
+
+/// Like `rayon::join_context` but on the current executor.
+/// We only tell each closure whether it migrated.
+pub(crate) fn join_context<A, B, RA, RB>(oper_a: A, oper_b: B) -> (RA, RB)
+where
+    A: FnOnce(bool) -> RA + Send,
+    B: FnOnce(bool) -> RB + Send,
+    RA: Send,
+    RB: Send,
+{
+    with_current(|executor| {
+        let (mut oper_a, mut oper_b) = (Some(oper_a), Some(oper_b));
+        let (mut result_a, mut result_b) = (None, None);
+        // tasks can move to other threads so they need to register the executor there.
+        executor.join_context(
+            &mut |migrated| result_a = Some(scoped(executor, || oper_a.take().unwrap()(migrated))),
+            &mut |migrated| result_b = Some(scoped(executor, || oper_b.take().unwrap()(migrated))),
+        );
+        (result_a.unwrap(), result_b.unwrap())
+    })
+}
+
+/// Like `rayon::join` but on the current executor.
+pub(crate) fn join<A, B, RA, RB>(oper_a: A, oper_b: B) -> (RA, RB)
+where
+    A: FnOnce() -> RA + Send,
+    B: FnOnce() -> RB + Send,
+    RA: Send,
+    RB: Send,
+{
+    join_context(|_| oper_a(), |_| oper_b())
+}
+
+pub(crate) fn current_num_threads() -> usize {
+    with_current(|executor| executor.current_num_threads())
+}
+
+pub(crate) fn current_thread_index() -> Option<usize> {
+    with_current(|executor| executor.current_thread_index())
+}
+
+pub(crate) fn steal_requested() -> bool {
+    with_current(|executor| executor.steal_requested())
+}
@@ -0,0 +1,385 @@
+//! Multi-dimensional index spaces.
+//!
+//! A grid is a rectangular block of indices, with a row-major (last axis
+//! fastest) order.
+//! Grids divide along their longest axis to keep pieces close to squares and
+//! preserve locality. Dividing at a given index cuts in linearized order
+//! instead, leaving pieces which are not rectangles but only row-major ranges
+//! inside their bounding rectangle.
+//!
+//! Since pieces of a rectangle cut along a column are not consecutive in
+//! row-major order, grids are not enumerable: each element comes with its
+//! coordinates and the order in which pieces get processed is not specified.
+use crate::prelude::*;
+use std::marker::PhantomData;
+use std::ops::Range;
+
+/// Block of indices in `D` dimensions, iterated as `[usize; D]` coordinates.
+///
+/// # Example:
+///
+/// ```
+/// use kvik::prelude::*;
+/// use kvik::Grid2;
+/// let sum = Grid2::new([0..100, 0..50])
+///     .into_par_iter()
+///     .map(|[i, j]| i * j)
+///     .reduce(|| 0, |a, b| a + b);
+/// assert_eq!(sum, 4950 * 1225);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Grid<const D: usize> {
+    bounds: [Range<usize>; D],
+    // remaining elements lie between these linear positions inside bounds.
+    start: usize,
+    end: usize,
+}
+
+/// Two dimensional grid, iterated as `[row, column]`.
+pub type Grid2 = Grid<2>;
+/// Three dimensional grid, iterated as `[plane, row, column]`.
+pub type Grid3 = Grid<3>;
+
+impl<const D: usize> Grid<D> {
+    /// Create the grid containing all coordinates in the given ranges.
+    pub fn new(bounds: [Range<usize>; D]) -> Self {
+        let end = bounds.iter().map(|range| range.len()).product();
+        Grid {
+            bounds,
+            start: 0,
+            end,
+        }
+    }
+    fn extent(&self, axis: usize) -> usize {
+        self.bounds[axis].len()
+    }
+    fn remaining(&self) -> usize {
+        self.end - self.start
+    }
+    fn is_rectangle(&self) -> bool {
+        self.start == 0 && self.end == (0..D).map(|axis| self.extent(axis)).product()
+    }
+    /// Coordinates of the element at given linear position inside the bounds.
+    fn coordinates(&self, mut position: usize) -> [usize; D] {
+        let mut coordinates = [0; D];
+        for axis in (0..D).rev() {
+            let extent = self.extent(axis);
+            coordinates[axis] = self.bounds[axis].start + position % extent;
+            position /= extent;
+        }
+        coordinates
+    }
+    /// Shrink the bounds to the outermost layers still containing elements.
+    fn normalized(mut self) -> Self {
+        if D == 0 || self.start >= self.end {
+            return self;
+        }
+        let layer: usize = (1..D).map(|axis| self.extent(axis)).product();
+        let (first, last) = (self.start / layer, self.end.div_ceil(layer));
+        let outer_start = self.bounds[0].start;
+        self.bounds[0] = outer_start + first..outer_start + last;
+        self.start -= first * layer;
+        self.end -= first * layer;
+        self
+    }
+}
+
+impl<const D: usize> Iterator for Grid<D> {
+    type Item = [usize; D];
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start >= self.end {
+            None
+        } else {
+            self.start += 1;
+            Some(self.coordinates(self.start - 1))
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining(), Some(self.remaining()))
+    }
+}
+
+impl<const D: usize> DoubleEndedIterator for Grid<D> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.start >= self.end {
+            None
+        } else {
+            self.end -= 1;
+            Some(self.coordinates(self.end))
+        }
+    }
+}
+
+impl<const D: usize> Divisible for Grid<D> {
+    type Controlled = True;
+    fn should_be_divided(&self) -> bool {
+        self.remaining() >= 2
+    }
+    fn divide(self) -> (Self, Self) {
+        if !self.is_rectangle() {
+            let index = self.remaining() - self.remaining() / 2;
+            return self.divide_at(index);
+        }
+        // on ties we cut the outermost axis, keeping rows contiguous
+        let axis = (0..D).fold(0, |longest, axis| {
+            if self.extent(axis) > self.extent(longest) {
+                axis
+            } else {
+                longest
+            }
+        });
+        let range = self.bounds[axis].clone();
+        let mid = range.start + range.len() - range.len() / 2;
+        let mut left_bounds = self.bounds.clone();
+        let mut right_bounds = self.bounds;
+        left_bounds[axis] = range.start..mid;
+        right_bounds[axis] = mid..range.end;
+        (Grid::new(left_bounds), Grid::new(right_bounds))
+    }
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        let mid = self.start + index.min(self.remaining());
+        let left = Grid {
+            bounds: self.bounds.clone(),
+            start: self.start,
+            end: mid,
+        };
+        let right = Grid {
+            bounds: self.bounds,
+            start: mid,
+            end: self.end,
+        };
+        (left.normalized(), right.normalized())
+    }
+}
+
+impl<const D: usize> Producer for Grid<D> {
+    fn sizes(&self) -> (usize, Option<usize>) {
+        self.size_hint()
+    }
+    fn preview(&self, index: usize) -> Self::Item {
+        self.coordinates(self.start + index)
+    }
+    fn partial_fold<B, F>(&mut self, init: B, fold_op: F, limit: usize) -> B
+    where
+        B: Send,
+        F: Fn(B, Self::Item) -> B,
+    {
+        let count = limit.min(self.remaining());
+        if count == 0 {
+            return init;
+        }
+        // avoid divisions by incrementing coordinates like an odometer
+        let mut coordinates = self.coordinates(self.start);
+        self.start += count;
+        let mut output = init;
+        for _ in 0..count {
+            output = fold_op(output, coordinates);
+            for axis in (0..D).rev() {
+                coordinates[axis] += 1;
+                if axis == 0 || coordinates[axis] < self.bounds[axis].end {
+                    break;
+                }
+                coordinates[axis] = self.bounds[axis].start;
+            }
+        }
+        output
+    }
+}
+
+pub struct GridIter<const D: usize> {
+    grid: Grid<D>,
+}
+
+impl<const D: usize> IntoParallelIterator for Grid<D> {
+    type Item = [usize; D];
+    type Iter = GridIter<D>;
+    fn into_par_iter(self) -> Self::Iter {
+        GridIter { grid: self }
+    }
+}
+
+impl<const D: usize> ParallelIterator for GridIter<D> {
+    type Item = [usize; D];
+    type Controlled = True;
+    type Enumerable = False;
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        callback.call(self.grid)
+    }
+}
+
+// tiles //
+
+/// Mutable rectangular block of a row-major matrix.
+pub struct Tile<'a, T> {
+    data: *mut T,
+    stride: usize,
+    origin: [usize; 2],
+    width: usize,
+    height: usize,
+    phantom: PhantomData<&'a mut T>,
+}
+
+unsafe impl<'a, T: Send> Send for Tile<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for Tile<'a, T> {}
+
+impl<'a, T> Tile<'a, T> {
+    /// `[row, column]` of the top left element inside the whole matrix.
+    pub fn origin(&self) -> [usize; 2] {
+        self.origin
+    }
+    /// Number of columns.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    /// Number of rows.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    /// Row at given index, relative to the tile.
+    pub fn row(&self, row: usize) -> &[T] {
+        assert!(row < self.height, "row out of tile");
+        unsafe {
+            std::slice::from_raw_parts(
+                self.data
+                    .add((self.origin[0] + row) * self.stride + self.origin[1]),
+                self.width,
+            )
+        }
+    }
+    /// Mutable row at given index, relative to the tile.
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        assert!(row < self.height, "row out of tile");
+        unsafe {
+            std::slice::from_raw_parts_mut(
+                self.data
+                    .add((self.origin[0] + row) * self.stride + self.origin[1]),
+                self.width,
+            )
+        }
+    }
+    /// Iterate on all rows of the tile.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(move |row| self.row(row))
+    }
+    /// Iterate mutably on all rows of the tile.
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
+        let (data, stride, origin, width) = (self.data, self.stride, self.origin, self.width);
+        // rows are disjoint and all borrowed from self
+        (0..self.height).map(move |row| unsafe {
+            std::slice::from_raw_parts_mut(data.add((origin[0] + row) * stride + origin[1]), width)
+        })
+    }
+}
+
+/// Parallel iterator on the tiles of a row-major matrix.
+pub struct Tiles<'a, T> {
+    pub(crate) data: &'a mut [T],
+    pub(crate) stride: usize,
+    pub(crate) tile_width: usize,
+    pub(crate) tile_height: usize,
+}
+
+impl<'a, T: Send> ParallelIterator for Tiles<'a, T> {
+    type Item = Tile<'a, T>;
+    type Controlled = True;
+    type Enumerable = False;
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        let height = self.data.len() / self.stride;
+        callback.call(TilesProducer {
+            tiles: Grid::new([
+                0..height.div_ceil(self.tile_height),
+                0..self.stride.div_ceil(self.tile_width),
+            ]),
+            data: self.data.as_mut_ptr(),
+            stride: self.stride,
+            height,
+            tile_width: self.tile_width,
+            tile_height: self.tile_height,
+            phantom: PhantomData,
+        })
+    }
+}
+
+/// Tiles producer, dividing the grid of tile indices.
+struct TilesProducer<'a, T> {
+    tiles: Grid2,
+    data: *mut T,
+    stride: usize,
+    height: usize,
+    tile_width: usize,
+    tile_height: usize,
+    phantom: PhantomData<&'a mut [T]>,
+}
+
+// each tile index is yielded once so tiles never alias.
+unsafe impl<'a, T: Send> Send for TilesProducer<'a, T> {}
+
+impl<'a, T> TilesProducer<'a, T> {
+    fn tile(&self, [row, column]: [usize; 2]) -> Tile<'a, T> {
+        let origin = [row * self.tile_height, column * self.tile_width];
+        Tile {
+            data: self.data,
+            stride: self.stride,
+            origin,
+            width: self.tile_width.min(self.stride - origin[1]),
+            height: self.tile_height.min(self.height - origin[0]),
+            phantom: PhantomData,
+        }
+    }
+    fn with_tiles(&self, tiles: Grid2) -> Self {
+        TilesProducer { tiles, ..*self }
+    }
+}
+
+impl<'a, T> Iterator for TilesProducer<'a, T> {
+    type Item = Tile<'a, T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.tiles.next().map(|index| self.tile(index))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.tiles.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for TilesProducer<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.tiles.next_back().map(|index| self.tile(index))
+    }
+}
+
+impl<'a, T: Send> Divisible for TilesProducer<'a, T> {
+    type Controlled = True;
+    fn should_be_divided(&self) -> bool {
+        self.tiles.should_be_divided()
+    }
+    fn divide(self) -> (Self, Self) {
+        let (left, right) = self.tiles.clone().divide();
+        (self.with_tiles(left), self.with_tiles(right))
+    }
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        let (left, right) = self.tiles.clone().divide_at(index);
+        (self.with_tiles(left), self.with_tiles(right))
+    }
+}
+
+impl<'a, T: Send> Producer for TilesProducer<'a, T> {
+    fn sizes(&self) -> (usize, Option<usize>) {
+        self.tiles.sizes()
+    }
+    fn preview(&self, _index: usize) -> Self::Item {
+        panic!("you cannot preview mutable tiles")
+    }
+    fn partial_fold<B, F>(&mut self, init: B, fold_op: F, limit: usize) -> B
+    where
+        B: Send,
+        F: Fn(B, Self::Item) -> B,
+    {
+        self.take(limit).fold(init, fold_op)
+    }
+}
@@ -4,19 +4,53 @@ extern crate rayon_logs as rayon;
 
 mod adaptors;
 mod algorithms;
+mod collect;
+mod collections;
+mod executor;
+mod grid;
 mod schedulers;
+#[cfg(any(test, feature = "steal-simulator"))]
+mod steal_simulator;
 mod str;
+mod trace;
 mod try_fold;
+mod unwind;
 mod worker;
+pub use adaptors::cancellable::{CancellationToken, Cancelled};
+pub use adaptors::merge_all::{merge_all, MergeAll};
 pub use algorithms::iter_sort::iter_par_sort;
+pub use algorithms::kway_merge::{adaptive_kway_merge, KWayMerger};
+pub use algorithms::linalg::{
+    matmul_blocks, par_matmul, par_transpose, transpose_blocks, Kernel, MatrixBlocks, Product,
+    Transpose,
+};
 pub use algorithms::manual_merge::{adaptive_slice_merge, Merger};
+pub use algorithms::partition::par_partition_in_place;
+pub use algorithms::pattern_search::{
+    par_find_all, par_replace_in_place, par_replace_str_in_place,
+};
+pub use algorithms::prefix_sum::par_prefix_sum_in_place;
+pub use algorithms::radix_sort::{par_radix_sort, par_radix_sort_by_key, RadixKey};
+pub use algorithms::select::par_select_nth_unstable;
+pub use algorithms::set_operations::{
+    adaptive_dedup, adaptive_difference, adaptive_intersection, adaptive_symmetric_difference,
+    adaptive_union,
+};
 pub use algorithms::slice_merge_sort::slice_par_sort;
+pub use executor::{Executor, RayonExecutor, SequentialExecutor};
+pub use grid::{Grid, Grid2, Grid3, Tile};
+pub use range::{par_range_step, RangeInteger};
+#[cfg(any(test, feature = "steal-simulator"))]
+pub use steal_simulator::{Decision, StealSimulator};
+pub use trace::{Event, EventKind, Tracer};
+pub use unwind::BlockPanic;
 pub mod prelude;
 mod range;
 mod slice;
 pub(crate) mod small_channel;
 pub(crate) mod traits;
 pub mod utils;
+mod vec;
 mod wrap;
 // TODO: change crate name
 #[macro_use]
@@ -174,10 +208,324 @@ mod tests {
         let inp: Vec<u64> = (1u64..PROBLEM_SIZE).collect();
         assert_eq!(
             inp.into_par_iter()
-                .map(|&elem| elem)
                 .filter(|elem| elem % 2 == 0)
                 .reduce(|| 0, |l, r| l + r),
             500 * 501
         );
     }
+    #[test]
+    fn vec_into_par_iter_test() {
+        let v: Vec<String> = (0..1000u32).map(|i| i.to_string()).collect();
+        let total = v
+            .into_par_iter()
+            .map(|s| s.parse::<u32>().unwrap())
+            .reduce(|| 0, |a, b| a + b);
+        assert_eq!(total, 999 * 500);
+        let d: std::collections::VecDeque<u32> = (0..100).collect();
+        assert_eq!(d.into_par_iter().reduce(|| 0, |a, b| a + b), 99 * 50);
+        let b: Box<[u32]> = (0..100).collect();
+        assert_eq!(
+            b.into_par_iter().adaptive().reduce(|| 0, |a, b| a + b),
+            99 * 50
+        );
+    }
+    #[test]
+    fn vec_into_par_iter_drops_test() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+        struct Counted(u32, Arc<AtomicUsize>);
+        impl Drop for Counted {
+            fn drop(&mut self) {
+                self.1.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+        let drops = Arc::new(AtomicUsize::new(0));
+        let v: Vec<_> = (0..10_000).map(|i| Counted(i, drops.clone())).collect();
+        let found = v.into_par_iter().find_first(|c| c.0 == 10).map(|c| c.0);
+        assert_eq!(found, Some(10));
+        assert_eq!(drops.load(Ordering::SeqCst), 10_000);
+    }
+    #[test]
+    fn numeric_reductions_test() {
+        let s: u64 = (0u64..1000).into_par_iter().adaptive().sum();
+        assert_eq!(s, 999 * 500);
+        let s: u64 = (0u64..1000).into_par_iter().depjoin().sum();
+        assert_eq!(s, 999 * 500);
+        let s: u64 = (0u64..1000).into_par_iter().rayon(2).sum();
+        assert_eq!(s, 999 * 500);
+        let p: u64 = (1u64..11).into_par_iter().adaptive().product();
+        assert_eq!(p, 3_628_800);
+        assert_eq!(
+            (0u32..1000).into_par_iter().filter(|e| e % 3 == 0).count(),
+            334
+        );
+        let v = vec![3, 7, 1, 7, 1];
+        assert_eq!(v.par_iter().min(), Some(&1));
+        assert_eq!(v.par_iter().max(), Some(&7));
+        let words = ["bb", "a", "ccc", "dd", "eee"];
+        assert_eq!(words.par_iter().min_by_key(|w| w.len()), Some(&"a"));
+        assert_eq!(words.par_iter().max_by_key(|w| w.len()), Some(&"eee"));
+        assert_eq!(
+            words
+                .par_iter()
+                .adaptive()
+                .max_by(|a, b| a.len().cmp(&b.len())),
+            Some(&"eee")
+        );
+    }
+    #[test]
+    fn short_circuit_test() {
+        assert!((0u64..10_000).into_par_iter().any(|e| e == 5_000));
+        assert!(!(0u64..10_000)
+            .into_par_iter()
+            .adaptive()
+            .any(|e| e == 10_000));
+        let found = (0u64..10_000)
+            .into_par_iter()
+            .find_any(|e| e % 1000 == 999)
+            .unwrap();
+        assert_eq!(found % 1000, 999);
+        assert_eq!((0u64..10).into_par_iter().find_any(|&e| e > 10), None);
+        // any does not need a controlled iterator
+        let evens: Vec<u32> = (0..5_000).map(|e| 2 * e).collect();
+        let odds: Vec<u32> = (0..5_000).map(|e| 2 * e + 1).collect();
+        assert!(evens.par_iter().merge(odds.as_slice()).any(|&e| e == 4_321));
+        assert!(!evens
+            .par_iter()
+            .merge(odds.as_slice())
+            .adaptive()
+            .any(|&e| e >= 10_000));
+        assert_eq!(
+            (0u64..10_000)
+                .into_par_iter()
+                .find_last(|e| e % 1000 == 999),
+            Some(9_999)
+        );
+        assert_eq!(
+            (0u64..10_000)
+                .into_par_iter()
+                .adaptive()
+                .find_last(|e| e % 3 == 1),
+            Some(9_997)
+        );
+        let v: Vec<u32> = (0..10_000).collect();
+        assert_eq!(v.par_iter().position_first(|&e| e >= 1234), Some(1234));
+        assert_eq!(v.par_iter().map(|e| e * 2).position_first(|e| e == 3), None);
+        assert_eq!(v.par_iter().position_any(|&e| e == 42), Some(42));
+    }
+    #[test]
+    fn executors_test() {
+        use crate::{RayonExecutor, SequentialExecutor};
+        let s: u64 = (0u64..10_000)
+            .into_par_iter()
+            .adaptive()
+            .on(&SequentialExecutor)
+            .sum();
+        assert_eq!(s, 9_999 * 5_000);
+        let s: u64 = (0u64..10_000)
+            .into_par_iter()
+            .depjoin()
+            .on(&SequentialExecutor)
+            .sum();
+        assert_eq!(s, 9_999 * 5_000);
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(3)
+            .build()
+            .unwrap();
+        let executor = RayonExecutor::new(pool);
+        let threads = (0u64..10_000)
+            .into_par_iter()
+            .map(|_| crate::executor::current_num_threads())
+            .on(&executor)
+            .max();
+        assert_eq!(threads, Some(3));
+        let s: u64 = (0u64..10_000)
+            .into_par_iter()
+            .rayon(2)
+            .adaptive()
+            .on(&executor)
+            .sum();
+        assert_eq!(s, 9_999 * 5_000);
+    }
+    #[test]
+    fn tracing_test() {
+        use crate::{Decision, EventKind, Executor, StealSimulator, Tracer};
+        let simulator = StealSimulator::new(3);
+        let tracer = Tracer::new(&simulator);
+        let s: u64 = tracer.run(|| (0u64..100_000).into_par_iter().adaptive().sum());
+        assert_eq!(s, 99_999 * 50_000);
+        let events = tracer.events();
+        let count = |kind: EventKind| events.iter().filter(|e| e.kind == kind).count();
+        let joins = simulator
+            .schedule()
+            .iter()
+            .filter(|d| matches!(d, Decision::Join { .. }))
+            .count();
+        let steals = simulator
+            .schedule()
+            .iter()
+            .filter(|d| matches!(d, Decision::Join { stolen: true, .. }))
+            .count();
+        assert_eq!(count(EventKind::Spawn), joins);
+        assert_eq!(count(EventKind::Task), 2 * joins);
+        assert_eq!(count(EventKind::Steal), steals);
+        assert!(events
+            .iter()
+            .any(|e| matches!(e.kind, EventKind::Block(1024))));
+        assert!(events.iter().all(|e| e.start <= e.end && e.thread < 4));
+        let json = tracer.to_chrome_json();
+        assert_eq!(json.matches("\"name\"").count(), events.len());
+    }
+    #[test]
+    fn adaptors_tracing_test() {
+        use crate::{EventKind, Executor, RayonExecutor, Tracer};
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(4)
+            .build()
+            .expect("Thread pool build failed");
+        let executor = RayonExecutor::new(pool);
+        let tracer = Tracer::new(&executor);
+        let s: u64 = tracer.run(|| {
+            (0u64..100_000)
+                .into_par_iter()
+                .log("squares")
+                .map(|e| e * e)
+                .by_blocks(std::iter::successors(Some(1_000), |s| Some(s * 2)))
+                .sum()
+        });
+        assert_eq!(s, (0u64..100_000).map(|e| e * e).sum());
+        let events = tracer.events();
+        let logged: usize = events
+            .iter()
+            .filter_map(|e| match e.kind {
+                EventKind::Subgraph("squares", size) => Some(size),
+                _ => None,
+            })
+            .sum();
+        assert!(logged >= 100_000);
+        // by_blocks divides before each block, outside of any scheduler
+        assert!(
+            events
+                .iter()
+                .filter(|e| e.kind == EventKind::Division)
+                .count()
+                >= 6
+        );
+        assert!(events.windows(2).all(|w| w[0].start <= w[1].start));
+        assert!(tracer.to_chrome_json().contains("\"name\":\"squares\""));
+        tracer.clear();
+        assert!(tracer.events().is_empty());
+    }
+    #[test]
+    fn escaped_names_tracing_test() {
+        use crate::{Executor, SequentialExecutor, Tracer};
+        let executor = SequentialExecutor;
+        let tracer = Tracer::new(&executor);
+        let name = "<\"quoted\" & \\escaped\n>";
+        tracer.run(|| (0u32..100).into_par_iter().log(name).for_each(|_| ()));
+        let json = tracer.to_chrome_json();
+        assert!(json.contains("\"name\":\"<\\\"quoted\\\" & \\\\escaped\\u000a>\""));
+        let svg = tracer.to_svg();
+        assert!(svg.contains("<title>&lt;&quot;quoted&quot; &amp; \\escaped\n&gt; "));
+    }
+    #[test]
+    fn panic_propagation_test() {
+        use std::panic::catch_unwind;
+        let payload = catch_unwind(|| {
+            (0u64..100_000).into_par_iter().adaptive().for_each(|e| {
+                if e == 90_000 {
+                    panic!("adaptive boom")
+                }
+            })
+        })
+        .unwrap_err();
+        assert_eq!(payload.downcast_ref::<&str>(), Some(&"adaptive boom"));
+        let payload = catch_unwind(|| {
+            (0u64..100_000).into_par_iter().depjoin().for_each(|e| {
+                if e == 3 {
+                    panic!("depjoin boom")
+                }
+            })
+        })
+        .unwrap_err();
+        assert_eq!(payload.downcast_ref::<&str>(), Some(&"depjoin boom"));
+        let r = (0u64..100_000).into_par_iter().try_for_each_unwind(|e| {
+            if e % 50_000 == 49_999 {
+                panic!("unwind boom")
+            }
+        });
+        let block_panic = r.unwrap_err();
+        assert_eq!(block_panic.index, 49_999);
+        assert!(block_panic.block_start <= 49_999);
+        assert_eq!(
+            block_panic.payload.downcast_ref::<&str>(),
+            Some(&"unwind boom")
+        );
+        assert!((0u64..1000)
+            .into_par_iter()
+            .try_for_each_unwind(|_| ())
+            .is_ok());
+    }
+    #[test]
+    fn block_panic_error_test() {
+        fn run(limit: u64) -> Result<u64, Box<dyn std::error::Error>> {
+            (0u64..1000).into_par_iter().try_for_each_unwind(|e| {
+                if e >= limit {
+                    panic!("{} is too large", e)
+                }
+            })?;
+            Ok(limit)
+        }
+        assert_eq!(run(1000).unwrap(), 1000);
+        let error = run(0).unwrap_err();
+        let block_panic = error.downcast_ref::<crate::BlockPanic>().unwrap();
+        assert_eq!(block_panic.message(), Some("0 is too large"));
+        assert_eq!(
+            error.to_string(),
+            "panicked on element 0 (block starting at 0): 0 is too large"
+        );
+    }
+    #[test]
+    fn index_adaptors_test() {
+        let v: Vec<u32> = (0..100).collect();
+        let e: Vec<(usize, u32)> = v.par_iter().map(|&e| e * 2).enumerate().collect();
+        assert!(e.iter().all(|&(i, x)| x == 2 * i as u32));
+        let s = v
+            .par_iter()
+            .enumerate()
+            .adaptive()
+            .map(|(i, &e)| i as u32 - e)
+            .reduce(|| 0, |a, b| a + b);
+        assert_eq!(s, 0);
+        let t: Vec<u32> = (0u32..100).into_par_iter().skip(10).take(5).collect();
+        assert_eq!(t, vec![10, 11, 12, 13, 14]);
+        let t: Vec<u32> = (0u32..10).into_par_iter().take(100).collect();
+        assert_eq!(t.len(), 10);
+        let s: Vec<u32> = (0u32..100).into_par_iter().step_by(7).collect();
+        assert_eq!(s, (0u32..100).step_by(7).collect::<Vec<_>>());
+        let r: Vec<u32> = (0u32..100).into_par_iter().step_by(7).rev().collect();
+        assert_eq!(r, (0u32..100).step_by(7).rev().collect::<Vec<_>>());
+        let c: Vec<u32> = (0u32..50)
+            .into_par_iter()
+            .chain(80u32..100)
+            .step_by(3)
+            .collect();
+        let expected: Vec<u32> = (0u32..50).chain(80u32..100).step_by(3).collect();
+        assert_eq!(c, expected);
+        let zipped = (0u32..20)
+            .into_par_iter()
+            .enumerate()
+            .zip(0u32..20)
+            .all(|((i, a), b)| a == b && i as u32 == b);
+        assert!(zipped);
+        let r: Vec<(usize, u32)> = (0u32..5).into_par_iter().rev().enumerate().collect();
+        assert_eq!(r, vec![(0, 4), (1, 3), (2, 2), (3, 1), (4, 0)]);
+        let l: u32 = (0u32..1000)
+            .into_par_iter()
+            .chain(0u32..1000)
+            .step_by(2)
+            .adaptive()
+            .sum();
+        assert_eq!(l, 2 * 499 * 500);
+    }
 }
@@ -1,4 +1,7 @@
+pub use crate::algorithms::par_sort::ParallelSliceSort;
 pub use crate::schedulers::Scheduler;
+pub use crate::slice::{ParallelSlice, ParallelSliceMut};
+pub use crate::str::ParallelString;
 pub use crate::traits::Consumer;
 pub use crate::traits::Divisible;
 pub use crate::traits::EnumerableParallelIterator;
@@ -6,6 +9,7 @@ pub use crate::traits::FromParallelIterator;
 pub use crate::traits::IntoParallelIterator;
 pub use crate::traits::IntoParallelRefIterator;
 pub use crate::traits::IntoParallelRefMutIterator;
+pub use crate::traits::ParallelExtend;
 pub use crate::traits::PreviewableParallelIterator;
 pub use crate::traits::Producer;
 pub use crate::traits::ProducerCallback;
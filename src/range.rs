@@ -1,14 +1,86 @@
+use crate::collect::VecCollector;
 use crate::prelude::*;
 use crate::try_fold::try_fold;
 use crate::Try;
+use std::ops::{Range, RangeInclusive};
+
+/// Integers usable as bounds of parallel ranges.
+/// All positions are computed modulo the width of the integer so that ranges
+/// spanning the whole type never overflow.
+pub trait RangeInteger: Copy + Ord + Send + Sync {
+    /// Marks if exclusive ranges of this type hold at most `usize::MAX`
+    /// integers, in which case they can be enumerated.
+    /// This excludes 128 bits integers: their ranges are not enumerable
+    /// (no `zip`, `enumerate`, `skip`...) even when short.
+    type Enumerable: VecCollector;
+    /// Number of integers in `start..end`, zero if the range is empty.
+    fn distance(start: Self, end: Self) -> u128;
+    /// Integer `count` positions after `self`, wrapping around on overflow.
+    fn offset(self, count: u128) -> Self;
+}
+
+/// Integer `count` positions after `start` but not after `end`.
+fn capped_offset<T: RangeInteger>(start: T, end: T, count: usize) -> T {
+    if count as u128 >= T::distance(start, end) {
+        end.max(start)
+    } else {
+        start.offset(count as u128)
+    }
+}
+
+/// Convert a number of elements into sizes, the upper bound being unknown
+/// if it does not fit in a `usize`.
+/// This only happens for integer types which are not enumerable.
+fn sizes_from(len: u128) -> (usize, Option<usize>) {
+    if len > usize::MAX as u128 {
+        (usize::MAX, None)
+    } else {
+        (len as usize, Some(len as usize))
+    }
+}
 
 pub struct Iter<T> {
-    range: std::ops::Range<T>,
+    range: Range<T>,
 }
 
+/// Parallel iterator on an inclusive range.
+/// `0..=u64::MAX` holds one more integer than a `usize` can count so inclusive ranges
+/// of 64 bits (and wider) integers are not enumerable, whatever their actual length.
+pub struct InclusiveIter<T> {
+    range: RangeInclusive<T>,
+}
+
+/// Return an empty inclusive range.
+/// std does not allow building an exhausted range directly.
+fn empty_inclusive<T>(bound: T) -> RangeInclusive<T>
+where
+    T: Copy,
+    RangeInclusive<T>: Iterator,
+{
+    let mut range = bound..=bound;
+    range.next();
+    range
+}
+
+// Ranges are enumerable only if they cannot hold more than `usize::MAX` integers:
+// we would not be able to give their exact length otherwise.
 macro_rules! implement_traits {
-    ($x: ty) => {
-        impl IntoParallelIterator for std::ops::Range<$x> {
+    ($($x: ty => $u: ty, $enumerable: ty, $inclusive_enumerable: ty),*) => {$(
+        impl RangeInteger for $x {
+            type Enumerable = $enumerable;
+            fn distance(start: Self, end: Self) -> u128 {
+                if start < end {
+                    (end as $u).wrapping_sub(start as $u) as u128
+                } else {
+                    0
+                }
+            }
+            fn offset(self, count: u128) -> Self {
+                (self as $u).wrapping_add(count as $u) as $x
+            }
+        }
+
+        impl IntoParallelIterator for Range<$x> {
             type Item = $x;
             type Iter = Iter<$x>;
             fn into_par_iter(self) -> Self::Iter {
@@ -19,7 +91,7 @@ macro_rules! implement_traits {
         impl ParallelIterator for Iter<$x> {
             type Item = $x;
             type Controlled = True;
-            type Enumerable = True;
+            type Enumerable = $enumerable;
             fn with_producer<CB>(self, callback: CB) -> CB::Output
             where
                 CB: ProducerCallback<Self::Item>,
@@ -28,41 +100,39 @@ macro_rules! implement_traits {
             }
         }
 
-        impl Divisible for std::ops::Range<$x> {
+        impl Divisible for Range<$x> {
             type Controlled = True;
             fn should_be_divided(&self) -> bool {
-                (self.end - self.start) >= 2
+                <$x>::distance(self.start, self.end) >= 2
             }
             fn divide(self) -> (Self, Self) {
                 // We need to divide with the biggest half on the left, so instead of just doing
-                // (end - start) / 2, which in cases of an odd length will put the biggest
+                // len / 2, which in cases of an odd length will put the biggest
                 // half on the right, we need to make sure we take the ceiling of the value:
-                // this is done using (end - start) - ((end - start) / 2).
-                let index = (self.end - self.start) - ((self.end - self.start) / 2);
-                self.divide_at(index as usize)
+                // this is done using len - (len / 2).
+                let len = <$x>::distance(self.start, self.end);
+                let mid = self.start.offset(len - len / 2);
+                (self.start..mid, mid..self.end)
             }
             fn divide_at(self, index: usize) -> (Self, Self) {
-                let mut mid = self.start + (index as $x);
-                if mid > self.end {
-                    mid = self.end
-                }
+                let mid = capped_offset(self.start, self.end, index);
                 (self.start..mid, mid..self.end)
             }
         }
 
-        impl Producer for std::ops::Range<$x> {
+        impl Producer for Range<$x> {
             fn sizes(&self) -> (usize, Option<usize>) {
-                self.size_hint()
+                sizes_from(<$x>::distance(self.start, self.end))
             }
             fn preview(&self, index: usize) -> Self::Item {
-                self.start + index as $x
+                self.start.offset(index as u128)
             }
             fn partial_fold<B, F>(&mut self, init: B, fold_op: F, limit: usize) -> B
             where
                 B: Send,
                 F: Fn(B, Self::Item) -> B,
             {
-                let next_limit = (self.start + limit as $x).min(self.end);
+                let next_limit = capped_offset(self.start, self.end, limit);
                 let output = (self.start..next_limit).fold(init, fold_op);
                 self.start = next_limit;
                 output
@@ -72,7 +142,7 @@ macro_rules! implement_traits {
                 F: FnMut(B, Self::Item) -> R,
                 R: Try<Ok = B>,
             {
-                let next_limit = (self.start + limit as $x).min(self.end);
+                let next_limit = capped_offset(self.start, self.end, limit);
                 let output = try_fold(&mut (self.start..next_limit), init, f);
                 self.start = next_limit;
                 output
@@ -80,15 +150,246 @@ macro_rules! implement_traits {
         }
 
         impl PreviewableParallelIterator for Iter<$x> {}
-    };
-}
-
-implement_traits!(i16);
-implement_traits!(u16);
-implement_traits!(i32);
-implement_traits!(isize);
-implement_traits!(u8);
-implement_traits!(usize);
-implement_traits!(i8);
-implement_traits!(u32);
-implement_traits!(u64);
+
+        impl IntoParallelIterator for RangeInclusive<$x> {
+            type Item = $x;
+            type Iter = InclusiveIter<$x>;
+            fn into_par_iter(self) -> Self::Iter {
+                InclusiveIter { range: self }
+            }
+        }
+
+        impl ParallelIterator for InclusiveIter<$x> {
+            type Item = $x;
+            type Controlled = True;
+            type Enumerable = $inclusive_enumerable;
+            fn with_producer<CB>(self, callback: CB) -> CB::Output
+            where
+                CB: ProducerCallback<Self::Item>,
+            {
+                callback.call(self.range)
+            }
+        }
+
+        // inclusive ranges are manipulated through the distance between their bounds
+        // which, unlike their length, always fits in the integer type.
+        impl Divisible for RangeInclusive<$x> {
+            type Controlled = True;
+            fn should_be_divided(&self) -> bool {
+                !self.is_empty() && self.start() < self.end()
+            }
+            fn divide(self) -> (Self, Self) {
+                let (start, end) = (*self.start(), *self.end());
+                let half = <$x>::distance(start, end) / 2;
+                (start..=start.offset(half), start.offset(half + 1)..=end)
+            }
+            fn divide_at(self, index: usize) -> (Self, Self) {
+                if self.is_empty() || index == 0 {
+                    return (empty_inclusive(*self.start()), self);
+                }
+                let (start, end) = (*self.start(), *self.end());
+                if index as u128 > <$x>::distance(start, end) {
+                    (self, empty_inclusive(end))
+                } else {
+                    (
+                        start..=start.offset(index as u128 - 1),
+                        start.offset(index as u128)..=end,
+                    )
+                }
+            }
+        }
+
+        impl Producer for RangeInclusive<$x> {
+            fn sizes(&self) -> (usize, Option<usize>) {
+                if self.is_empty() {
+                    (0, Some(0))
+                } else {
+                    let distance = <$x>::distance(*self.start(), *self.end());
+                    distance
+                        .checked_add(1)
+                        .map(sizes_from)
+                        .unwrap_or((usize::MAX, None))
+                }
+            }
+            fn preview(&self, index: usize) -> Self::Item {
+                self.start().offset(index as u128)
+            }
+            fn partial_fold<B, F>(&mut self, init: B, fold_op: F, limit: usize) -> B
+            where
+                B: Send,
+                F: Fn(B, Self::Item) -> B,
+            {
+                if limit == 0 {
+                    return init;
+                }
+                let (left, right) = std::mem::replace(self, empty_inclusive(*self.end()))
+                    .divide_at(limit);
+                *self = right;
+                left.fold(init, fold_op)
+            }
+            fn partial_try_fold<B, F, R>(&mut self, init: B, f: F, limit: usize) -> R
+            where
+                F: FnMut(B, Self::Item) -> R,
+                R: Try<Ok = B>,
+            {
+                if limit == 0 {
+                    return R::from_ok(init);
+                }
+                let (mut left, right) = std::mem::replace(self, empty_inclusive(*self.end()))
+                    .divide_at(limit);
+                let output = try_fold(&mut left, init, f);
+                // elements left over by an early exit are lost, just like for ranges.
+                *self = right;
+                output
+            }
+        }
+
+        impl PreviewableParallelIterator for InclusiveIter<$x> {}
+    )*};
+}
+
+// we assume 64 bits targets
+implement_traits!(
+    i8 => u8, True, True,
+    u8 => u8, True, True,
+    i16 => u16, True, True,
+    u16 => u16, True, True,
+    i32 => u32, True, True,
+    u32 => u32, True, True,
+    i64 => u64, True, False,
+    u64 => u64, True, False,
+    i128 => u128, False, False,
+    u128 => u128, False, False,
+    isize => usize, True, False,
+    usize => usize, True, False
+);
+
+/// Parallel iterator on `start`, `start + step`, ... up to `end` excluded.
+pub struct StepRange<T> {
+    producer: StepProducer<T>,
+}
+
+struct StepProducer<T> {
+    start: T,
+    step: u128,
+    len: u128,
+}
+
+/// Iterate in parallel on all integers from `start` to `end` (excluded) with
+/// a stride of `step`.
+/// Unlike `(start..end).into_par_iter().step_by(step)` the produced iterator
+/// is a base iterator, dividing directly at the right positions.
+///
+/// # Example:
+///
+/// ```
+/// use kvik::prelude::*;
+/// use kvik::par_range_step;
+/// let v: Vec<i8> = par_range_step(-128i8, 127, 50).collect();
+/// assert_eq!(v, vec![-128, -78, -28, 22, 72, 122]);
+/// ```
+pub fn par_range_step<T: RangeInteger>(start: T, end: T, step: usize) -> StepRange<T> {
+    assert!(step != 0, "par_range_step needs a non zero step");
+    let step = step as u128;
+    let distance = T::distance(start, end);
+    StepRange {
+        producer: StepProducer {
+            start,
+            step,
+            len: distance.div_ceil(step),
+        },
+    }
+}
+
+impl<T: RangeInteger> ParallelIterator for StepRange<T> {
+    type Item = T;
+    type Controlled = True;
+    // we cannot be longer than the range without steps
+    type Enumerable = T::Enumerable;
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        callback.call(self.producer)
+    }
+}
+
+impl<T: RangeInteger> Iterator for StepProducer<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            None
+        } else {
+            let current = self.start;
+            self.len -= 1;
+            // wraps harmlessly after the last element
+            self.start = self.start.offset(self.step);
+            Some(current)
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        sizes_from(self.len)
+    }
+}
+
+impl<T: RangeInteger> DoubleEndedIterator for StepProducer<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            None
+        } else {
+            self.len -= 1;
+            Some(self.start.offset(self.len * self.step))
+        }
+    }
+}
+
+impl<T: RangeInteger> Divisible for StepProducer<T> {
+    type Controlled = True;
+    fn should_be_divided(&self) -> bool {
+        self.len >= 2
+    }
+    fn divide(self) -> (Self, Self) {
+        let half = self.len - self.len / 2;
+        self.divide_at_u128(half)
+    }
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        self.divide_at_u128(index as u128)
+    }
+}
+
+impl<T: RangeInteger> StepProducer<T> {
+    fn divide_at_u128(self, index: u128) -> (Self, Self) {
+        let index = index.min(self.len);
+        (
+            StepProducer {
+                start: self.start,
+                step: self.step,
+                len: index,
+            },
+            StepProducer {
+                // index * step is at most the distance between the original bounds
+                start: self.start.offset(index * self.step),
+                step: self.step,
+                len: self.len - index,
+            },
+        )
+    }
+}
+
+impl<T: RangeInteger> Producer for StepProducer<T> {
+    fn sizes(&self) -> (usize, Option<usize>) {
+        self.size_hint()
+    }
+    fn preview(&self, index: usize) -> Self::Item {
+        self.start.offset(index as u128 * self.step)
+    }
+    fn partial_fold<B, F>(&mut self, init: B, fold_op: F, limit: usize) -> B
+    where
+        B: Send,
+        F: Fn(B, Self::Item) -> B,
+    {
+        self.take(limit).fold(init, fold_op)
+    }
+}
+
+impl<T: RangeInteger> PreviewableParallelIterator for StepRange<T> {}
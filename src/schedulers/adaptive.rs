@@ -1,5 +1,9 @@
+use crate::executor::join_context;
 use crate::prelude::*;
+use crate::schedulers::resume_panics;
 use crate::small_channel::small_channel;
+use crate::trace::{traced, EventKind};
+use std::panic::{catch_unwind, AssertUnwindSafe};
 
 pub(crate) struct AdaptiveScheduler;
 
@@ -34,62 +38,80 @@ where
 {
     let (lower, upper) = producer.micro_block_sizes();
     let (sender, receiver) = small_channel();
-    let (left_result, maybe_right_result): (T, Option<T>) = rayon::join_context(
-        |_| match block_sizes(lower, upper)
-            .take_while(|_| !sender.receiver_is_waiting())
-            .try_fold((producer, output), |(mut producer, output), s| {
-                //TODO: is this the right way to test for the end ?
-                if producer.sizes().1 == Some(0) {
-                    Err(producer.fold(output, |a, b| reducer.reduce(a, b)))
-                } else {
-                    // TODO: remove closure ?
-                    let new_output = producer.partial_fold(output, |a, b| reducer.reduce(a, b), s);
-                    Ok((producer, new_output))
+    // both sides catch panics so that the stealer is woken up by the sender's drop
+    // and that we re-raise the original payload once both sides are done.
+    let (left_result, maybe_right_result) = join_context(
+        |_| {
+            catch_unwind(AssertUnwindSafe(|| {
+                match block_sizes(lower, upper)
+                    .take_while(|_| !sender.receiver_is_waiting())
+                    .try_fold((producer, output), |(mut producer, output), s| {
+                        //TODO: is this the right way to test for the end ?
+                        if producer.sizes().1 == Some(0) {
+                            Err(traced(EventKind::Fold, || {
+                                producer.fold(output, |a, b| reducer.reduce(a, b))
+                            }))
+                        } else {
+                            // TODO: remove closure ?
+                            let new_output = traced(EventKind::Block(s), || {
+                                producer.partial_fold(output, |a, b| reducer.reduce(a, b), s)
+                            });
+                            Ok((producer, new_output))
+                        }
+                    }) {
+                    Ok((remaining_producer, output)) => {
+                        // we are being stolen. Let's give something if what is left is big enough.
+                        if remaining_producer.should_be_divided() {
+                            let (my_half, his_half) =
+                                traced(EventKind::Division, || remaining_producer.divide());
+                            sender.send(Some(his_half));
+                            adaptive_scheduler(reducer, my_half, output)
+                        } else {
+                            sender.send(None);
+                            //TODO: remove closure ?
+                            traced(EventKind::Fold, || {
+                                remaining_producer.fold(output, |a, b| reducer.reduce(a, b))
+                            })
+                        }
+                    }
+                    Err(output) => {
+                        // all is completed, cancel stealer's task.
+                        sender.send(None);
+                        output
+                    }
                 }
-            }) {
-            Ok((remaining_producer, output)) => {
-                // we are being stolen. Let's give something if what is left is big enough.
-                if remaining_producer.should_be_divided() {
-                    let (my_half, his_half) = remaining_producer.divide();
-                    sender.send(Some(his_half));
-                    adaptive_scheduler(reducer, my_half, output)
+            }))
+        },
+        |migrated| {
+            catch_unwind(AssertUnwindSafe(|| {
+                if migrated {
+                    // nothing is received if the victim panicked
+                    let stolen_task = {
+                        #[cfg(feature = "logs")]
+                        {
+                            use rayon_logs::subgraph;
+                            subgraph("En attendant", 0, || receiver.recv().flatten())
+                        }
+                        #[cfg(not(feature = "logs"))]
+                        {
+                            receiver.recv().flatten()
+                        }
+                    };
+                    stolen_task
+                        .map(|producer| adaptive_scheduler(reducer, producer, reducer.identity()))
                 } else {
-                    sender.send(None);
-                    //TODO: remove closure ?
-                    remaining_producer.fold(output, |a, b| reducer.reduce(a, b))
+                    None
                 }
-            }
-            Err(output) => {
-                // all is completed, cancel stealer's task.
-                sender.send(None);
-                output
-            }
-        },
-        |c| {
-            if c.migrated() {
-                let stolen_task = {
-                    #[cfg(feature = "logs")]
-                    {
-                        use rayon_logs::subgraph;
-                        subgraph("En attendant", 0, || {
-                            receiver.recv().expect("receiving adaptive producer failed")
-                        })
-                    }
-                    #[cfg(not(feature = "logs"))]
-                    {
-                        receiver.recv().expect("receiving adaptive producer failed")
-                    }
-                };
-                stolen_task
-                    .map(|producer| adaptive_scheduler(reducer, producer, reducer.identity()))
-            } else {
-                None
-            }
+            }))
         },
     );
+    let (left_result, maybe_right_result): (T, Option<T>) =
+        resume_panics(left_result, maybe_right_result);
 
     if let Some(right_result) = maybe_right_result {
-        reducer.reduce(left_result, right_result)
+        traced(EventKind::Reduction, || {
+            reducer.reduce(left_result, right_result)
+        })
     } else {
         left_result
     }
@@ -1,7 +1,11 @@
 //! like join scheduler except that continuation is not always for left task
 //! but instead for latest completing task.
+use crate::executor::join;
 use crate::prelude::*;
+use crate::schedulers::resume_panics;
 use crate::small_channel::small_channel;
+use crate::trace::{traced, EventKind};
+use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::sync::atomic::{AtomicBool, Ordering};
 
 pub(crate) struct DepJoinScheduler;
@@ -17,34 +21,49 @@ where
             let cleanup = AtomicBool::new(false);
             let (sender, receiver) = small_channel();
             let (sender1, receiver1) = small_channel();
-            let (left, right) = producer.divide();
-            let (left_r, right_r) = rayon::join(
+            let (left, right) = traced(EventKind::Division, || producer.divide());
+            // a panicking task never marks itself as completed so nobody waits for it.
+            // the panic is re-raised once both sides are done.
+            let (left_r, right_r) = join(
                 || {
-                    let my_result = self.schedule(left, reducer);
-                    let last = cleanup.swap(true, Ordering::SeqCst);
-                    if last {
-                        let his_result = receiver.recv().expect("receiving depjoin failed");
-                        Some(reducer.reduce(my_result, his_result))
-                    } else {
-                        sender1.send(my_result);
-                        None
-                    }
+                    catch_unwind(AssertUnwindSafe(|| {
+                        let my_result = self.schedule(left, reducer);
+                        let last = cleanup.swap(true, Ordering::SeqCst);
+                        if last {
+                            receiver.recv().map(|his_result| {
+                                traced(EventKind::Reduction, || {
+                                    reducer.reduce(my_result, his_result)
+                                })
+                            })
+                        } else {
+                            sender1.send(my_result);
+                            None
+                        }
+                    }))
                 },
                 || {
-                    let my_result = self.schedule(right, reducer);
-                    let last = cleanup.swap(true, Ordering::SeqCst);
-                    if last {
-                        let his_result = receiver1.recv().expect("receiving1 depjoin failed");
-                        Some(reducer.reduce(his_result, my_result))
-                    } else {
-                        sender.send(my_result);
-                        None
-                    }
+                    catch_unwind(AssertUnwindSafe(|| {
+                        let my_result = self.schedule(right, reducer);
+                        let last = cleanup.swap(true, Ordering::SeqCst);
+                        if last {
+                            receiver1.recv().map(|his_result| {
+                                traced(EventKind::Reduction, || {
+                                    reducer.reduce(his_result, my_result)
+                                })
+                            })
+                        } else {
+                            sender.send(my_result);
+                            None
+                        }
+                    }))
                 },
             );
-            left_r.or(right_r).unwrap()
+            let (left_r, right_r) = resume_panics(left_r, right_r);
+            left_r
+                .or(right_r)
+                .expect("depjoin: no side reduced both results")
         } else {
-            reducer.fold(producer)
+            traced(EventKind::Fold, || reducer.fold(producer))
         }
     }
 }
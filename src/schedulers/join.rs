@@ -1,5 +1,7 @@
 //! Easiest parallel scheduler.
+use crate::executor::join;
 use crate::prelude::*;
+use crate::trace::{traced, EventKind};
 
 pub(crate) struct JoinScheduler;
 
@@ -11,14 +13,14 @@ where
 {
     fn schedule(&self, producer: P, reducer: &R) -> P::Item {
         if producer.should_be_divided() {
-            let (left, right) = producer.divide();
-            let (left_r, right_r) = rayon::join(
+            let (left, right) = traced(EventKind::Division, || producer.divide());
+            let (left_r, right_r) = join(
                 || self.schedule(left, reducer),
                 || self.schedule(right, reducer),
             );
-            reducer.reduce(left_r, right_r)
+            traced(EventKind::Reduction, || reducer.reduce(left_r, right_r))
         } else {
-            reducer.fold(producer)
+            traced(EventKind::Fold, || reducer.fold(producer))
         }
     }
 }
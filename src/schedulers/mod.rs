@@ -17,3 +17,15 @@ pub(crate) use adaptive::AdaptiveScheduler;
 pub(crate) use depjoin::DepJoinScheduler;
 pub(crate) use join::JoinScheduler;
 pub(crate) use sequential::SequentialScheduler;
+
+/// Combine the outcomes of two `catch_unwind` protected tasks.
+/// Both tasks are completed so we can now re-raise the first panic.
+pub(crate) fn resume_panics<A, B>(
+    left: std::thread::Result<A>,
+    right: std::thread::Result<B>,
+) -> (A, B) {
+    match (left, right) {
+        (Ok(left), Ok(right)) => (left, right),
+        (Err(payload), _) | (_, Err(payload)) => std::panic::resume_unwind(payload),
+    }
+}
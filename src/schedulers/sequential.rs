@@ -1,5 +1,6 @@
 //! sequential scheduler
 use crate::prelude::*;
+use crate::trace::{traced, EventKind};
 
 pub(crate) struct SequentialScheduler;
 
@@ -10,6 +11,6 @@ where
     R: Reducer<P::Item>,
 {
     fn schedule(&self, producer: P, reducer: &R) -> P::Item {
-        reducer.fold(producer)
+        traced(EventKind::Fold, || reducer.fold(producer))
     }
 }
@@ -1,3 +1,4 @@
+use crate::grid::Tiles;
 use crate::prelude::*;
 use crate::try_fold::try_fold;
 use crate::Try;
@@ -171,3 +172,589 @@ impl<'a, T: 'a> Divisible for &'a mut [T] {
         self.split_at_mut(index)
     }
 }
+
+// chunks and windows //
+
+/// Parallel iterators on blocks of slices.
+pub trait ParallelSlice<T: Sync> {
+    /// Parallel iterator over consecutive chunks of `size` elements.
+    /// The last chunk might be shorter.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// let v: Vec<u32> = (0..10).collect();
+    /// let sums: Vec<u32> = v.par_chunks(4).map(|c| c.iter().sum()).collect();
+    /// assert_eq!(sums, vec![6, 22, 17]);
+    /// ```
+    fn par_chunks(&self, size: usize) -> Chunks<'_, T>;
+    /// Parallel iterator over consecutive chunks of exactly `size` elements.
+    /// Remaining elements are available with `remainder`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// let v: Vec<u32> = (0..10).collect();
+    /// let chunks = v.par_chunks_exact(4);
+    /// assert_eq!(chunks.remainder(), &[8, 9]);
+    /// assert_eq!(chunks.map(|c| c.len()).reduce(|| 0, |a, b| a + b), 8);
+    /// ```
+    fn par_chunks_exact(&self, size: usize) -> ChunksExact<'_, T>;
+    /// Parallel iterator over all overlapping windows of `size` elements.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// let v: Vec<u32> = (0..10).collect();
+    /// assert!(v.par_windows(2).all(|w| w[0] + 1 == w[1]));
+    /// ```
+    fn par_windows(&self, size: usize) -> Windows<'_, T>;
+    /// Parallel iterator over chunks of `size` elements starting from the end.
+    /// The last chunk (at the start of the slice) might be shorter.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// let v: Vec<u32> = (0..10).collect();
+    /// let firsts: Vec<u32> = v.par_rchunks(4).map(|c| c[0]).collect();
+    /// assert_eq!(firsts, vec![6, 2, 0]);
+    /// ```
+    fn par_rchunks(&self, size: usize) -> RChunks<'_, T>;
+}
+
+/// Parallel iterators on mutable blocks of slices.
+pub trait ParallelSliceMut<T: Send> {
+    /// Parallel iterator over consecutive mutable chunks of `size` elements.
+    /// The last chunk might be shorter.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// let mut v = vec![0u32; 10];
+    /// v.par_chunks_mut(4)
+    ///     .enumerate()
+    ///     .for_each(|(i, c)| c.iter_mut().for_each(|e| *e = i as u32));
+    /// assert_eq!(v, vec![0, 0, 0, 0, 1, 1, 1, 1, 2, 2]);
+    /// ```
+    fn par_chunks_mut(&mut self, size: usize) -> ChunksMut<'_, T>;
+    /// Parallel iterator over mutable `tile_width` x `tile_height` tiles of
+    /// a row-major matrix with rows of `stride` elements.
+    /// Tiles on the right and bottom borders might be smaller.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// let mut image = vec![0usize; 6 * 5];
+    /// image.par_tiles(6, 4, 2).for_each(|mut tile| {
+    ///     let [row, column] = tile.origin();
+    ///     tile.rows_mut()
+    ///         .enumerate()
+    ///         .for_each(|(i, r)| r.iter_mut().for_each(|e| *e = row + i + column));
+    /// });
+    /// assert_eq!(image[6 * 3..6 * 4], [3, 3, 3, 3, 7, 7]);
+    /// ```
+    fn par_tiles(&mut self, stride: usize, tile_width: usize, tile_height: usize) -> Tiles<'_, T>;
+}
+
+impl<T: Sync> ParallelSlice<T> for [T] {
+    fn par_chunks(&self, size: usize) -> Chunks<'_, T> {
+        assert!(size != 0, "chunk size must be non-zero");
+        Chunks { slice: self, size }
+    }
+    fn par_chunks_exact(&self, size: usize) -> ChunksExact<'_, T> {
+        assert!(size != 0, "chunk size must be non-zero");
+        let (exact, remainder) = self.split_at(self.len() - self.len() % size);
+        ChunksExact {
+            chunks: Chunks { slice: exact, size },
+            remainder,
+        }
+    }
+    fn par_windows(&self, size: usize) -> Windows<'_, T> {
+        assert!(size != 0, "window size must be non-zero");
+        Windows { slice: self, size }
+    }
+    fn par_rchunks(&self, size: usize) -> RChunks<'_, T> {
+        assert!(size != 0, "chunk size must be non-zero");
+        RChunks { slice: self, size }
+    }
+}
+
+impl<T: Send> ParallelSliceMut<T> for [T] {
+    fn par_chunks_mut(&mut self, size: usize) -> ChunksMut<'_, T> {
+        assert!(size != 0, "chunk size must be non-zero");
+        ChunksMut { slice: self, size }
+    }
+    fn par_tiles(&mut self, stride: usize, tile_width: usize, tile_height: usize) -> Tiles<'_, T> {
+        assert!(
+            tile_width != 0 && tile_height != 0,
+            "tile sizes must be non-zero"
+        );
+        assert!(
+            stride != 0 && self.len().is_multiple_of(stride),
+            "slice length must be a multiple of the stride"
+        );
+        Tiles {
+            data: self,
+            stride,
+            tile_width,
+            tile_height,
+        }
+    }
+}
+
+/// Number of chunks of given size needed to cover given length.
+fn chunks_number(len: usize, size: usize) -> usize {
+    len.div_ceil(size)
+}
+
+pub struct Chunks<'a, T> {
+    slice: &'a [T],
+    size: usize,
+}
+
+impl<'a, T: Sync> ParallelIterator for Chunks<'a, T> {
+    type Item = &'a [T];
+    type Controlled = True;
+    type Enumerable = True;
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        callback.call(ChunksProducer {
+            slice: self.slice,
+            size: self.size,
+        })
+    }
+}
+
+impl<'a, T: Sync> PreviewableParallelIterator for Chunks<'a, T> {}
+
+struct ChunksProducer<'a, T> {
+    slice: &'a [T],
+    size: usize,
+}
+
+impl<'a, T> Iterator for ChunksProducer<'a, T> {
+    type Item = &'a [T];
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.slice.is_empty() {
+            None
+        } else {
+            let (chunk, remaining) = self.slice.split_at(self.size.min(self.slice.len()));
+            self.slice = remaining;
+            Some(chunk)
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = chunks_number(self.slice.len(), self.size);
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for ChunksProducer<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.slice.is_empty() {
+            None
+        } else {
+            let last_size = (self.slice.len() - 1) % self.size + 1;
+            let (remaining, chunk) = self.slice.split_at(self.slice.len() - last_size);
+            self.slice = remaining;
+            Some(chunk)
+        }
+    }
+}
+
+impl<'a, T: Sync> Divisible for ChunksProducer<'a, T> {
+    type Controlled = True;
+    fn should_be_divided(&self) -> bool {
+        self.slice.len() > self.size
+    }
+    fn divide(self) -> (Self, Self) {
+        let mid = chunks_number(self.slice.len(), self.size) / 2;
+        self.divide_at(mid)
+    }
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        let (left, right) = self
+            .slice
+            .split_at(index.saturating_mul(self.size).min(self.slice.len()));
+        (
+            ChunksProducer {
+                slice: left,
+                size: self.size,
+            },
+            ChunksProducer {
+                slice: right,
+                size: self.size,
+            },
+        )
+    }
+}
+
+impl<'a, T: Sync> Producer for ChunksProducer<'a, T> {
+    fn sizes(&self) -> (usize, Option<usize>) {
+        self.size_hint()
+    }
+    fn preview(&self, index: usize) -> Self::Item {
+        let start = index * self.size;
+        &self.slice[start..(start + self.size).min(self.slice.len())]
+    }
+    fn partial_fold<B, F>(&mut self, init: B, fold_op: F, limit: usize) -> B
+    where
+        B: Send,
+        F: Fn(B, Self::Item) -> B,
+    {
+        self.take(limit).fold(init, fold_op)
+    }
+}
+
+pub struct ChunksExact<'a, T> {
+    chunks: Chunks<'a, T>,
+    remainder: &'a [T],
+}
+
+impl<'a, T> ChunksExact<'a, T> {
+    /// Elements left out of all chunks.
+    pub fn remainder(&self) -> &'a [T] {
+        self.remainder
+    }
+}
+
+impl<'a, T: Sync> ParallelIterator for ChunksExact<'a, T> {
+    type Item = &'a [T];
+    type Controlled = True;
+    type Enumerable = True;
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        self.chunks.with_producer(callback)
+    }
+}
+
+impl<'a, T: Sync> PreviewableParallelIterator for ChunksExact<'a, T> {}
+
+pub struct RChunks<'a, T> {
+    slice: &'a [T],
+    size: usize,
+}
+
+impl<'a, T: Sync> ParallelIterator for RChunks<'a, T> {
+    type Item = &'a [T];
+    type Controlled = True;
+    type Enumerable = True;
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        callback.call(RChunksProducer {
+            slice: self.slice,
+            size: self.size,
+        })
+    }
+}
+
+impl<'a, T: Sync> PreviewableParallelIterator for RChunks<'a, T> {}
+
+struct RChunksProducer<'a, T> {
+    slice: &'a [T],
+    size: usize,
+}
+
+impl<'a, T> Iterator for RChunksProducer<'a, T> {
+    type Item = &'a [T];
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.slice.is_empty() {
+            None
+        } else {
+            let (remaining, chunk) = self
+                .slice
+                .split_at(self.slice.len().saturating_sub(self.size));
+            self.slice = remaining;
+            Some(chunk)
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = chunks_number(self.slice.len(), self.size);
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for RChunksProducer<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.slice.is_empty() {
+            None
+        } else {
+            let last_size = (self.slice.len() - 1) % self.size + 1;
+            let (chunk, remaining) = self.slice.split_at(last_size);
+            self.slice = remaining;
+            Some(chunk)
+        }
+    }
+}
+
+impl<'a, T: Sync> Divisible for RChunksProducer<'a, T> {
+    type Controlled = True;
+    fn should_be_divided(&self) -> bool {
+        self.slice.len() > self.size
+    }
+    fn divide(self) -> (Self, Self) {
+        let mid = chunks_number(self.slice.len(), self.size) / 2;
+        self.divide_at(mid)
+    }
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        // first chunks are at the end of the slice
+        let tail_size = index.saturating_mul(self.size).min(self.slice.len());
+        let (right, left) = self.slice.split_at(self.slice.len() - tail_size);
+        (
+            RChunksProducer {
+                slice: left,
+                size: self.size,
+            },
+            RChunksProducer {
+                slice: right,
+                size: self.size,
+            },
+        )
+    }
+}
+
+impl<'a, T: Sync> Producer for RChunksProducer<'a, T> {
+    fn sizes(&self) -> (usize, Option<usize>) {
+        self.size_hint()
+    }
+    fn preview(&self, index: usize) -> Self::Item {
+        let end = self.slice.len() - index * self.size;
+        &self.slice[end.saturating_sub(self.size)..end]
+    }
+    fn partial_fold<B, F>(&mut self, init: B, fold_op: F, limit: usize) -> B
+    where
+        B: Send,
+        F: Fn(B, Self::Item) -> B,
+    {
+        self.take(limit).fold(init, fold_op)
+    }
+}
+
+pub struct Windows<'a, T> {
+    slice: &'a [T],
+    size: usize,
+}
+
+impl<'a, T: Sync> ParallelIterator for Windows<'a, T> {
+    type Item = &'a [T];
+    type Controlled = True;
+    type Enumerable = True;
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        callback.call(WindowsProducer {
+            slice: self.slice,
+            size: self.size,
+        })
+    }
+}
+
+impl<'a, T: Sync> PreviewableParallelIterator for Windows<'a, T> {}
+
+/// Windows of consecutive producers overlap on `size - 1` elements.
+struct WindowsProducer<'a, T> {
+    slice: &'a [T],
+    size: usize,
+}
+
+impl<'a, T> Iterator for WindowsProducer<'a, T> {
+    type Item = &'a [T];
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.slice.len() < self.size {
+            None
+        } else {
+            let window = &self.slice[..self.size];
+            self.slice = &self.slice[1..];
+            Some(window)
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.slice.len() + 1).saturating_sub(self.size);
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for WindowsProducer<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.slice.len() < self.size {
+            None
+        } else {
+            let len = self.slice.len();
+            let window = &self.slice[len - self.size..];
+            self.slice = &self.slice[..len - 1];
+            Some(window)
+        }
+    }
+}
+
+impl<'a, T: Sync> Divisible for WindowsProducer<'a, T> {
+    type Controlled = True;
+    fn should_be_divided(&self) -> bool {
+        self.slice.len() > self.size
+    }
+    fn divide(self) -> (Self, Self) {
+        let mid = self.size_hint().0 / 2;
+        self.divide_at(mid)
+    }
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        let index = index.min(self.size_hint().0);
+        let left = &self.slice[..(index + self.size - 1).min(self.slice.len())];
+        (
+            WindowsProducer {
+                slice: left,
+                size: self.size,
+            },
+            WindowsProducer {
+                slice: &self.slice[index..],
+                size: self.size,
+            },
+        )
+    }
+}
+
+impl<'a, T: Sync> Producer for WindowsProducer<'a, T> {
+    fn sizes(&self) -> (usize, Option<usize>) {
+        self.size_hint()
+    }
+    fn preview(&self, index: usize) -> Self::Item {
+        &self.slice[index..index + self.size]
+    }
+    fn partial_fold<B, F>(&mut self, init: B, fold_op: F, limit: usize) -> B
+    where
+        B: Send,
+        F: Fn(B, Self::Item) -> B,
+    {
+        self.take(limit).fold(init, fold_op)
+    }
+}
+
+pub struct ChunksMut<'a, T> {
+    slice: &'a mut [T],
+    size: usize,
+}
+
+impl<'a, T: Send> ParallelIterator for ChunksMut<'a, T> {
+    type Item = &'a mut [T];
+    type Controlled = True;
+    type Enumerable = True;
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        callback.call(ChunksMutProducer {
+            slice: self.slice,
+            size: self.size,
+        })
+    }
+}
+
+struct ChunksMutProducer<'a, T> {
+    slice: &'a mut [T],
+    size: usize,
+}
+
+impl<'a, T> Iterator for ChunksMutProducer<'a, T> {
+    type Item = &'a mut [T];
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.slice.is_empty() {
+            None
+        } else {
+            let slice = std::mem::take(&mut self.slice);
+            let (chunk, remaining) = slice.split_at_mut(self.size.min(slice.len()));
+            self.slice = remaining;
+            Some(chunk)
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = chunks_number(self.slice.len(), self.size);
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for ChunksMutProducer<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.slice.is_empty() {
+            None
+        } else {
+            let slice = std::mem::take(&mut self.slice);
+            let last_size = (slice.len() - 1) % self.size + 1;
+            let (remaining, chunk) = slice.split_at_mut(slice.len() - last_size);
+            self.slice = remaining;
+            Some(chunk)
+        }
+    }
+}
+
+impl<'a, T: Send> Divisible for ChunksMutProducer<'a, T> {
+    type Controlled = True;
+    fn should_be_divided(&self) -> bool {
+        self.slice.len() > self.size
+    }
+    fn divide(self) -> (Self, Self) {
+        let mid = chunks_number(self.slice.len(), self.size) / 2;
+        self.divide_at(mid)
+    }
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        let len = self.slice.len();
+        let (left, right) = self
+            .slice
+            .split_at_mut(index.saturating_mul(self.size).min(len));
+        (
+            ChunksMutProducer {
+                slice: left,
+                size: self.size,
+            },
+            ChunksMutProducer {
+                slice: right,
+                size: self.size,
+            },
+        )
+    }
+}
+
+impl<'a, T: Send> Producer for ChunksMutProducer<'a, T> {
+    fn sizes(&self) -> (usize, Option<usize>) {
+        self.size_hint()
+    }
+    fn preview(&self, _index: usize) -> Self::Item {
+        panic!("mutable chunks are not peekable");
+    }
+    fn partial_fold<B, F>(&mut self, init: B, fold_op: F, limit: usize) -> B
+    where
+        B: Send,
+        F: Fn(B, Self::Item) -> B,
+    {
+        self.take(limit).fold(init, fold_op)
+    }
+}
+
+/// Blocks iterators can be given directly to `zip`, `merge` and the like.
+macro_rules! into_par_iter_identity {
+    ($iterator:ident, $bound:ident) => {
+        impl<'a, T: $bound> IntoParallelIterator for $iterator<'a, T> {
+            type Item = <Self as ParallelIterator>::Item;
+            type Iter = Self;
+            fn into_par_iter(self) -> Self {
+                self
+            }
+        }
+    };
+}
+
+into_par_iter_identity!(Chunks, Sync);
+into_par_iter_identity!(ChunksExact, Sync);
+into_par_iter_identity!(RChunks, Sync);
+into_par_iter_identity!(Windows, Sync);
+into_par_iter_identity!(ChunksMut, Send);
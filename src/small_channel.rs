@@ -36,6 +36,8 @@ pub fn small_channel<T: Send>() -> (SmallSender<T>, SmallReceiver<T>) {
 }
 
 impl<T> SmallReceiver<T> {
+    /// Wait until the sender is gone.
+    /// Returns `None` if it was dropped without sending (for example when panicking).
     pub fn recv(self) -> Option<T> {
         self.channel.request.store(true, Ordering::Relaxed);
         let mut channel = self.channel;
@@ -52,7 +54,7 @@ impl<T> SmallReceiver<T> {
 impl<T> SmallSender<T> {
     /// Return whether receiver is blocking, waiting for something.
     pub fn receiver_is_waiting(&self) -> bool {
-        self.channel.request.load(Ordering::Relaxed)
+        self.channel.request.load(Ordering::Relaxed) || crate::executor::steal_requested()
     }
     pub fn send(self, t: T) {
         self.channel.data.store(Some(t));
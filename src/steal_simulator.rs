@@ -0,0 +1,179 @@
+//! Deterministic executor simulating steals, meant for testing.
+//! Everything runs on the calling thread but a seeded random number generator decides
+//! which tasks get stolen, by which (fake) thread and when thieves ask for work.
+//! All decisions are recorded so that a failing schedule can be replayed.
+use crate::executor::Executor;
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// One scheduling decision taken by the `StealSimulator`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    /// On each join: is the right task stolen and by which thread.
+    Join { stolen: bool, thief: usize },
+    /// On each poll of a stolen join's left task: did the thief ask for work.
+    Request(bool),
+}
+
+struct Frame {
+    stolen: bool,
+    requested: bool,
+}
+
+struct SimulationState {
+    rng: StdRng,
+    replay: Option<VecDeque<Decision>>,
+    recorded: Vec<Decision>,
+    // one frame for each join whose left task is running
+    frames: Vec<Frame>,
+    thread: usize,
+}
+
+/// Single threaded executor taking all stealing decisions from a seeded RNG.
+///
+/// # Example:
+///
+/// ```
+/// use kvik::prelude::*;
+/// use kvik::{Executor, StealSimulator};
+/// for seed in 0..10 {
+///     let simulator = StealSimulator::new(seed);
+///     let s: u64 = simulator.run(|| (0u64..10_000).into_par_iter().adaptive().sum());
+///     assert_eq!(s, 9_999 * 5_000);
+///     // re-run the exact same schedule
+///     let schedule = simulator.schedule();
+///     let replayed = StealSimulator::replay(4, schedule.clone());
+///     replayed.run(|| (0u64..10_000).into_par_iter().adaptive().sum::<u64>());
+///     assert_eq!(replayed.schedule(), schedule);
+/// }
+/// ```
+pub struct StealSimulator {
+    threads: usize,
+    state: Mutex<SimulationState>,
+}
+
+impl StealSimulator {
+    /// Simulate 4 threads with given seed.
+    pub fn new(seed: u64) -> Self {
+        StealSimulator::with_threads(seed, 4)
+    }
+    /// Simulate given number of threads with given seed.
+    pub fn with_threads(seed: u64, threads: usize) -> Self {
+        assert!(threads != 0);
+        StealSimulator {
+            threads,
+            state: Mutex::new(SimulationState {
+                rng: StdRng::seed_from_u64(seed),
+                replay: None,
+                recorded: Vec::new(),
+                frames: Vec::new(),
+                thread: 0,
+            }),
+        }
+    }
+    /// Follow a schedule recorded by a previous simulation.
+    pub fn replay(threads: usize, schedule: Vec<Decision>) -> Self {
+        let simulator = StealSimulator::with_threads(0, threads);
+        simulator.state.lock().unwrap().replay = Some(schedule.into());
+        simulator
+    }
+    /// All decisions taken so far.
+    pub fn schedule(&self) -> Vec<Decision> {
+        self.state.lock().unwrap().recorded.clone()
+    }
+    fn decide<F>(&self, state: &mut SimulationState, random_decision: F) -> Decision
+    where
+        F: FnOnce(&mut StdRng) -> Decision,
+    {
+        let decision = match state.replay.as_mut() {
+            Some(replay) => replay
+                .pop_front()
+                .expect("replayed schedule is too short for this computation"),
+            None => random_decision(&mut state.rng),
+        };
+        state.recorded.push(decision);
+        decision
+    }
+}
+
+impl Executor for StealSimulator {
+    fn join_context(
+        &self,
+        oper_a: &mut (dyn FnMut(bool) + Send),
+        oper_b: &mut (dyn FnMut(bool) + Send),
+    ) {
+        let threads = self.threads;
+        let (stolen, thief, victim) = {
+            let mut state = self.state.lock().unwrap();
+            let victim = state.thread;
+            let decision = self.decide(&mut state, |rng| {
+                let stolen = threads > 1 && rng.next_u32() % 2 == 0;
+                let thief = if stolen {
+                    (victim + 1 + (rng.next_u64() % (threads as u64 - 1)) as usize) % threads
+                } else {
+                    victim
+                };
+                Decision::Join { stolen, thief }
+            });
+            match decision {
+                Decision::Join { stolen, thief } => {
+                    state.frames.push(Frame {
+                        stolen,
+                        requested: false,
+                    });
+                    (stolen, thief, victim)
+                }
+                Decision::Request(_) => panic!("replayed schedule diverged: expected a join"),
+            }
+        };
+        // restore the simulation state even if a task panics
+        struct Restore<'s>(&'s StealSimulator, usize, bool);
+        impl<'s> Drop for Restore<'s> {
+            fn drop(&mut self) {
+                let mut state = self.0.state.lock().unwrap_or_else(|e| e.into_inner());
+                if self.2 {
+                    state.frames.pop();
+                }
+                state.thread = self.1;
+            }
+        }
+        {
+            let _restore = Restore(self, victim, true);
+            oper_a(false);
+        }
+        self.state.lock().unwrap().thread = thief;
+        let _restore = Restore(self, victim, false);
+        oper_b(stolen);
+    }
+    fn install(&self, op: &mut (dyn FnMut() + Send)) {
+        op()
+    }
+    fn current_num_threads(&self) -> usize {
+        self.threads
+    }
+    fn current_thread_index(&self) -> Option<usize> {
+        Some(self.state.lock().unwrap().thread)
+    }
+    fn steal_requested(&self) -> bool {
+        let mut guard = self.state.lock().unwrap();
+        let state = &mut *guard;
+        match state.frames.last() {
+            Some(frame) if frame.stolen && !frame.requested => {
+                let decision = self.decide(state, |rng| Decision::Request(rng.next_u32() % 4 == 0));
+                match decision {
+                    Decision::Request(requested) => {
+                        state.frames.last_mut().unwrap().requested = requested;
+                        requested
+                    }
+                    Decision::Join { .. } => {
+                        panic!("replayed schedule diverged: expected a steal request")
+                    }
+                }
+            }
+            Some(frame) => frame.requested,
+            None => false,
+        }
+    }
+}
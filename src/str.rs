@@ -40,3 +40,591 @@ impl<'a> Divisible for &'a mut str {
         self.split_at_mut(real_index)
     }
 }
+
+/// Char boundary around given index, strictly inside the text if it has at least two chars.
+fn inner_char_boundary(text: &str, index: usize) -> usize {
+    let index = index.clamp(1, text.len().max(1));
+    (index..text.len())
+        .chain((1..index).rev())
+        .find(|&i| text.is_char_boundary(i))
+        .unwrap_or(text.len())
+}
+
+/// Do we contain at least two chars ?
+fn has_two_chars(text: &str) -> bool {
+    text.chars().nth(1).is_some()
+}
+
+/// Char patterns used to split or search texts.
+pub trait Pattern: Sync + Send {
+    fn matches_char(&self, c: char) -> bool;
+    /// Byte range of the first matching char.
+    fn find_in(&self, text: &str) -> Option<(usize, usize)> {
+        text.char_indices()
+            .find(|(_, c)| self.matches_char(*c))
+            .map(|(i, c)| (i, i + c.len_utf8()))
+    }
+    /// Byte range of the last matching char.
+    fn rfind_in(&self, text: &str) -> Option<(usize, usize)> {
+        text.char_indices()
+            .rev()
+            .find(|(_, c)| self.matches_char(*c))
+            .map(|(i, c)| (i, i + c.len_utf8()))
+    }
+}
+
+impl Pattern for char {
+    fn matches_char(&self, c: char) -> bool {
+        *self == c
+    }
+    fn find_in(&self, text: &str) -> Option<(usize, usize)> {
+        text.find(*self).map(|i| (i, i + self.len_utf8()))
+    }
+    fn rfind_in(&self, text: &str) -> Option<(usize, usize)> {
+        text.rfind(*self).map(|i| (i, i + self.len_utf8()))
+    }
+}
+
+impl Pattern for &[char] {
+    fn matches_char(&self, c: char) -> bool {
+        self.contains(&c)
+    }
+}
+
+impl<const N: usize> Pattern for [char; N] {
+    fn matches_char(&self, c: char) -> bool {
+        self.contains(&c)
+    }
+}
+
+impl<F: Fn(char) -> bool + Sync + Send> Pattern for F {
+    fn matches_char(&self, c: char) -> bool {
+        self(c)
+    }
+}
+
+/// Parallel iterators over texts.
+pub trait ParallelString {
+    /// Parallel iterator over all chars.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// let vowels = "parallel iterators".par_chars().filter(|c| "aeiou".contains(*c)).count();
+    /// assert_eq!(vowels, 7);
+    /// ```
+    fn par_chars(&self) -> Chars<'_>;
+    /// Parallel iterator over all chars and their byte positions.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// let position = "hé ho".par_char_indices().filter(|(_, c)| *c == 'o').map(|(i, _)| i).max();
+    /// assert_eq!(position, Some(5));
+    /// ```
+    fn par_char_indices(&self) -> CharIndices<'_>;
+    /// Parallel iterator over all lines, as `str::lines`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// let lines: Vec<&str> = "one\r\ntwo\n\nthree\n".par_lines().collect();
+    /// assert_eq!(lines, vec!["one", "two", "", "three"]);
+    /// ```
+    fn par_lines(&self) -> Lines<'_>;
+    /// Parallel iterator over all substrings separated by chars matching the pattern.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// let fields: Vec<&str> = "a,b;;c".par_split([',', ';']).collect();
+    /// assert_eq!(fields, vec!["a", "b", "", "c"]);
+    /// ```
+    fn par_split<P: Pattern>(&self, pattern: P) -> Split<'_, P>;
+    /// Parallel iterator over all substrings separated by whitespace.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// let words = " the quick\tbrown  fox ".par_split_whitespace().count();
+    /// assert_eq!(words, 4);
+    /// ```
+    fn par_split_whitespace(&self) -> SplitWhitespace<'_>;
+    /// Parallel iterator over all chars matching the pattern.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// let digits: String = "a1b22c333".par_matches(|c: char| c.is_ascii_digit()).collect();
+    /// assert_eq!(digits, "122333");
+    /// ```
+    fn par_matches<P: Pattern>(&self, pattern: P) -> Matches<'_, P>;
+}
+
+impl ParallelString for str {
+    fn par_chars(&self) -> Chars<'_> {
+        Chars { text: self }
+    }
+    fn par_char_indices(&self) -> CharIndices<'_> {
+        CharIndices { text: self }
+    }
+    fn par_lines(&self) -> Lines<'_> {
+        // as for sequential lines, a final line ending does not start a new line
+        let text = self.strip_suffix('\n').unwrap_or(self);
+        Lines {
+            split: Split {
+                text,
+                pattern: '\n',
+                empty: self.is_empty(),
+            },
+            terminated: text.len() < self.len(),
+        }
+    }
+    fn par_split<P: Pattern>(&self, pattern: P) -> Split<'_, P> {
+        Split {
+            text: self,
+            pattern,
+            empty: false,
+        }
+    }
+    fn par_split_whitespace(&self) -> SplitWhitespace<'_> {
+        SplitWhitespace {
+            split: self.par_split(char::is_whitespace),
+        }
+    }
+    fn par_matches<P: Pattern>(&self, pattern: P) -> Matches<'_, P> {
+        Matches {
+            text: self,
+            pattern,
+        }
+    }
+}
+
+// chars //
+
+pub struct Chars<'a> {
+    text: &'a str,
+}
+
+impl<'a> ParallelIterator for Chars<'a> {
+    type Item = char;
+    type Controlled = True;
+    type Enumerable = False;
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        callback.call(self.text.chars())
+    }
+}
+
+impl<'a> Divisible for std::str::Chars<'a> {
+    type Controlled = True;
+    fn should_be_divided(&self) -> bool {
+        has_two_chars(self.as_str())
+    }
+    fn divide(self) -> (Self, Self) {
+        let mid = self.as_str().len() / 2;
+        self.divide_at(mid)
+    }
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        let text = self.as_str();
+        let (left, right) = text.split_at(inner_char_boundary(text, index));
+        (left.chars(), right.chars())
+    }
+}
+
+impl<'a> Producer for std::str::Chars<'a> {
+    fn sizes(&self) -> (usize, Option<usize>) {
+        self.size_hint()
+    }
+    fn preview(&self, _index: usize) -> Self::Item {
+        panic!("chars are not previewable")
+    }
+    fn partial_fold<B, F>(&mut self, init: B, fold_op: F, limit: usize) -> B
+    where
+        B: Send,
+        F: Fn(B, Self::Item) -> B,
+    {
+        self.take(limit).fold(init, fold_op)
+    }
+}
+
+// char indices //
+
+pub struct CharIndices<'a> {
+    text: &'a str,
+}
+
+impl<'a> ParallelIterator for CharIndices<'a> {
+    type Item = (usize, char);
+    type Controlled = True;
+    type Enumerable = False;
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        callback.call(CharIndicesProducer {
+            offset: 0,
+            text: self.text,
+        })
+    }
+}
+
+/// Remaining text, together with its position in the whole text.
+struct CharIndicesProducer<'a> {
+    offset: usize,
+    text: &'a str,
+}
+
+impl<'a> Iterator for CharIndicesProducer<'a> {
+    type Item = (usize, char);
+    fn next(&mut self) -> Option<Self::Item> {
+        let c = self.text.chars().next()?;
+        let index = self.offset;
+        self.offset += c.len_utf8();
+        self.text = &self.text[c.len_utf8()..];
+        Some((index, c))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.text.chars().size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for CharIndicesProducer<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let c = self.text.chars().next_back()?;
+        let index = self.text.len() - c.len_utf8();
+        self.text = &self.text[..index];
+        Some((self.offset + index, c))
+    }
+}
+
+impl<'a> Divisible for CharIndicesProducer<'a> {
+    type Controlled = True;
+    fn should_be_divided(&self) -> bool {
+        has_two_chars(self.text)
+    }
+    fn divide(self) -> (Self, Self) {
+        let mid = self.text.len() / 2;
+        self.divide_at(mid)
+    }
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        let boundary = inner_char_boundary(self.text, index);
+        let (left, right) = self.text.split_at(boundary);
+        (
+            CharIndicesProducer {
+                offset: self.offset,
+                text: left,
+            },
+            CharIndicesProducer {
+                offset: self.offset + boundary,
+                text: right,
+            },
+        )
+    }
+}
+
+impl<'a> Producer for CharIndicesProducer<'a> {
+    fn sizes(&self) -> (usize, Option<usize>) {
+        self.size_hint()
+    }
+    fn preview(&self, _index: usize) -> Self::Item {
+        panic!("char indices are not previewable")
+    }
+    fn partial_fold<B, F>(&mut self, init: B, fold_op: F, limit: usize) -> B
+    where
+        B: Send,
+        F: Fn(B, Self::Item) -> B,
+    {
+        self.take(limit).fold(init, fold_op)
+    }
+}
+
+// split //
+
+pub struct Split<'a, P> {
+    text: &'a str,
+    pattern: P,
+    empty: bool,
+}
+
+impl<'a, P: Pattern> ParallelIterator for Split<'a, P> {
+    type Item = &'a str;
+    type Controlled = False;
+    type Enumerable = False;
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        callback.call(SplitProducer {
+            text: self.text,
+            pattern: &self.pattern,
+            done: self.empty,
+            divisible: true,
+        })
+    }
+}
+
+/// Remaining substrings are the ones of `text`, unless we are done.
+struct SplitProducer<'a, 'p, P> {
+    text: &'a str,
+    pattern: &'p P,
+    done: bool,
+    /// Cleared once we failed to find a separator to divide at.
+    divisible: bool,
+}
+
+impl<'a, 'p, P: Pattern> Iterator for SplitProducer<'a, 'p, P> {
+    type Item = &'a str;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        if let Some((start, end)) = self.pattern.find_in(self.text) {
+            let token = &self.text[..start];
+            self.text = &self.text[end..];
+            Some(token)
+        } else {
+            self.done = true;
+            Some(self.text)
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            (0, Some(0))
+        } else {
+            (1, Some(self.text.len() + 1))
+        }
+    }
+}
+
+impl<'a, 'p, P: Pattern> DoubleEndedIterator for SplitProducer<'a, 'p, P> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        if let Some((start, end)) = self.pattern.rfind_in(self.text) {
+            let token = &self.text[end..];
+            self.text = &self.text[..start];
+            Some(token)
+        } else {
+            self.done = true;
+            Some(self.text)
+        }
+    }
+}
+
+impl<'a, 'p, P: Pattern> Divisible for SplitProducer<'a, 'p, P> {
+    type Controlled = False;
+    fn should_be_divided(&self) -> bool {
+        !self.done && self.divisible && has_two_chars(self.text)
+    }
+    fn divide(self) -> (Self, Self) {
+        let mid = self.text.len() / 2;
+        self.divide_at(mid)
+    }
+    fn divide_at(mut self, index: usize) -> (Self, Self) {
+        // move the split point to the nearest separator, which we remove
+        let boundary = inner_char_boundary(self.text, index);
+        let separator = self
+            .pattern
+            .find_in(&self.text[boundary..])
+            .map(|(start, end)| (boundary + start, boundary + end))
+            .or_else(|| self.pattern.rfind_in(&self.text[..boundary]));
+        if let Some((start, end)) = separator {
+            (
+                SplitProducer {
+                    text: &self.text[..start],
+                    pattern: self.pattern,
+                    done: false,
+                    divisible: true,
+                },
+                SplitProducer {
+                    text: &self.text[end..],
+                    pattern: self.pattern,
+                    done: false,
+                    divisible: true,
+                },
+            )
+        } else {
+            self.divisible = false;
+            let empty = SplitProducer {
+                text: "",
+                pattern: self.pattern,
+                done: true,
+                divisible: false,
+            };
+            (self, empty)
+        }
+    }
+}
+
+impl<'a, 'p, P: Pattern> Producer for SplitProducer<'a, 'p, P> {
+    fn sizes(&self) -> (usize, Option<usize>) {
+        self.size_hint()
+    }
+    fn preview(&self, _index: usize) -> Self::Item {
+        panic!("splits are not previewable")
+    }
+    fn partial_fold<B, F>(&mut self, init: B, fold_op: F, limit: usize) -> B
+    where
+        B: Send,
+        F: Fn(B, Self::Item) -> B,
+    {
+        self.take(limit).fold(init, fold_op)
+    }
+}
+
+// lines and words //
+
+pub struct Lines<'a> {
+    split: Split<'a, char>,
+    // is the last line followed by a line ending
+    terminated: bool,
+}
+
+impl<'a> ParallelIterator for Lines<'a> {
+    type Item = &'a str;
+    type Controlled = False;
+    type Enumerable = False;
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        // a '\r' is only part of the line ending when followed by a '\n'
+        let end = self.split.text.as_ptr() as usize + self.split.text.len();
+        let terminated = self.terminated;
+        self.split
+            .map(move |line: &'a str| {
+                if !terminated && line.as_ptr() as usize + line.len() == end {
+                    line
+                } else {
+                    line.strip_suffix('\r').unwrap_or(line)
+                }
+            })
+            .with_producer(callback)
+    }
+}
+
+pub struct SplitWhitespace<'a> {
+    split: Split<'a, fn(char) -> bool>,
+}
+
+impl<'a> ParallelIterator for SplitWhitespace<'a> {
+    type Item = &'a str;
+    type Controlled = False;
+    type Enumerable = False;
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        self.split
+            .filter(|word: &&'a str| !word.is_empty())
+            .with_producer(callback)
+    }
+}
+
+// matches //
+
+pub struct Matches<'a, P> {
+    text: &'a str,
+    pattern: P,
+}
+
+impl<'a, P: Pattern> ParallelIterator for Matches<'a, P> {
+    type Item = &'a str;
+    type Controlled = True;
+    type Enumerable = False;
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        callback.call(MatchesProducer {
+            text: self.text,
+            pattern: &self.pattern,
+        })
+    }
+}
+
+/// Matches are single chars so we can divide at any char boundary.
+struct MatchesProducer<'a, 'p, P> {
+    text: &'a str,
+    pattern: &'p P,
+}
+
+impl<'a, 'p, P: Pattern> Iterator for MatchesProducer<'a, 'p, P> {
+    type Item = &'a str;
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some((start, end)) = self.pattern.find_in(self.text) {
+            let found = &self.text[start..end];
+            self.text = &self.text[end..];
+            Some(found)
+        } else {
+            self.text = "";
+            None
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.text.len()))
+    }
+}
+
+impl<'a, 'p, P: Pattern> DoubleEndedIterator for MatchesProducer<'a, 'p, P> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if let Some((start, end)) = self.pattern.rfind_in(self.text) {
+            let found = &self.text[start..end];
+            self.text = &self.text[..start];
+            Some(found)
+        } else {
+            self.text = "";
+            None
+        }
+    }
+}
+
+impl<'a, 'p, P: Pattern> Divisible for MatchesProducer<'a, 'p, P> {
+    type Controlled = True;
+    fn should_be_divided(&self) -> bool {
+        has_two_chars(self.text)
+    }
+    fn divide(self) -> (Self, Self) {
+        let mid = self.text.len() / 2;
+        self.divide_at(mid)
+    }
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        let (left, right) = self.text.split_at(inner_char_boundary(self.text, index));
+        (
+            MatchesProducer {
+                text: left,
+                pattern: self.pattern,
+            },
+            MatchesProducer {
+                text: right,
+                pattern: self.pattern,
+            },
+        )
+    }
+}
+
+impl<'a, 'p, P: Pattern> Producer for MatchesProducer<'a, 'p, P> {
+    fn sizes(&self) -> (usize, Option<usize>) {
+        self.size_hint()
+    }
+    fn preview(&self, _index: usize) -> Self::Item {
+        panic!("matches are not previewable")
+    }
+    fn partial_fold<B, F>(&mut self, init: B, fold_op: F, limit: usize) -> B
+    where
+        B: Send,
+        F: Fn(B, Self::Item) -> B,
+    {
+        self.take(limit).fold(init, fold_op)
+    }
+}
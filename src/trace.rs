@@ -0,0 +1,306 @@
+//! Native execution traces.
+//! A `Tracer` wraps another executor and records what happens on each thread.
+//! Traces can be exported to chrome's trace event format (chrome://tracing)
+//! or to a svg timeline.
+use crate::executor::{with_current, Executor};
+use std::fmt::Write;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// What happened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    /// A task (one side of a join) ran.
+    Task,
+    /// A join created two tasks.
+    Spawn,
+    /// A task was stolen by another thread.
+    Steal,
+    /// A producer was divided.
+    Division,
+    /// The adaptive scheduler folded a block of given size.
+    Block(usize),
+    /// A producer was sequentially folded.
+    Fold,
+    /// Two partial results were reduced.
+    Reduction,
+    /// A block of given size was folded inside a `log` adaptor of given name.
+    Subgraph(&'static str, usize),
+}
+
+impl EventKind {
+    fn name(&self) -> &'static str {
+        match self {
+            EventKind::Task => "task",
+            EventKind::Spawn => "spawn",
+            EventKind::Steal => "steal",
+            EventKind::Division => "division",
+            EventKind::Block(_) => "block",
+            EventKind::Fold => "fold",
+            EventKind::Reduction => "reduction",
+            EventKind::Subgraph(name, _) => name,
+        }
+    }
+    fn color(&self) -> &'static str {
+        match self {
+            EventKind::Task => "#dddddd",
+            EventKind::Spawn => "black",
+            EventKind::Steal => "red",
+            EventKind::Division => "orange",
+            EventKind::Block(_) => "steelblue",
+            EventKind::Fold => "seagreen",
+            EventKind::Reduction => "purple",
+            EventKind::Subgraph(..) => "gold",
+        }
+    }
+}
+
+/// Names given to `log` can be anything: escape them for json strings.
+fn json_escaped(name: &str) -> String {
+    let mut escaped = String::with_capacity(name.len());
+    for c in name.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if c.is_control() => write!(escaped, "\\u{:04x}", c as u32).unwrap(),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Names given to `log` can be anything: escape them for xml text.
+fn xml_escaped(name: &str) -> String {
+    let mut escaped = String::with_capacity(name.len());
+    for c in name.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// One recorded event. Instant events have the same start and end.
+#[derive(Debug, Clone, Copy)]
+pub struct Event {
+    pub kind: EventKind,
+    pub thread: usize,
+    /// Time since the tracer's creation.
+    pub start: Duration,
+    pub end: Duration,
+}
+
+/// Executor recording all events of computations running on it.
+///
+/// # Example:
+///
+/// ```
+/// use kvik::prelude::*;
+/// use kvik::{Executor, RayonExecutor, Tracer};
+/// let executor = RayonExecutor::global();
+/// let tracer = Tracer::new(&executor);
+/// let s: u64 = tracer.run(|| (0u64..100_000).into_par_iter().adaptive().sum());
+/// assert_eq!(s, 99_999 * 50_000);
+/// assert!(!tracer.events().is_empty());
+/// assert!(tracer.to_chrome_json().starts_with("{\"traceEvents\":["));
+/// assert!(tracer.to_svg().starts_with("<svg"));
+/// ```
+pub struct Tracer<'e> {
+    inner: &'e dyn Executor,
+    origin: Instant,
+    /// One buffer per thread so that recording never contends.
+    /// Threads outside of the pool share the last one.
+    buffers: Vec<Mutex<Vec<Event>>>,
+}
+
+impl<'e> Tracer<'e> {
+    /// Trace all computations running on given executor.
+    pub fn new(inner: &'e dyn Executor) -> Self {
+        Tracer {
+            inner,
+            origin: Instant::now(),
+            buffers: (0..=inner.current_num_threads())
+                .map(|_| Mutex::new(Vec::new()))
+                .collect(),
+        }
+    }
+    /// All events recorded so far, merged from all threads by starting time.
+    pub fn events(&self) -> Vec<Event> {
+        let mut events: Vec<Event> = self
+            .buffers
+            .iter()
+            .flat_map(|buffer| buffer.lock().unwrap().clone())
+            .collect();
+        events.sort_by_key(|event| event.start);
+        events
+    }
+    /// Forget all recorded events.
+    pub fn clear(&self) {
+        self.buffers
+            .iter()
+            .for_each(|buffer| buffer.lock().unwrap().clear())
+    }
+    fn thread(&self) -> usize {
+        // threads outside of the pool get an extra row
+        self.inner
+            .current_thread_index()
+            .unwrap_or_else(|| self.inner.current_num_threads())
+    }
+    fn span<R>(&self, kind: EventKind, op: impl FnOnce() -> R) -> R {
+        let start = Instant::now();
+        let result = op();
+        self.record(kind, start, Instant::now());
+        result
+    }
+    /// Export in chrome's trace event format.
+    pub fn to_chrome_json(&self) -> String {
+        let mut json = String::from("{\"traceEvents\":[");
+        for (index, event) in self.events().iter().enumerate() {
+            if index != 0 {
+                json.push(',');
+            }
+            let start = event.start.as_nanos() as f64 / 1000.0;
+            let duration = (event.end - event.start).as_nanos() as f64 / 1000.0;
+            write!(
+                json,
+                "{{\"name\":\"{}\",\"cat\":\"kvik\",\"pid\":0,\"tid\":{},\"ts\":{}",
+                json_escaped(event.kind.name()),
+                event.thread,
+                start
+            )
+            .unwrap();
+            if event.start == event.end {
+                json.push_str(",\"ph\":\"i\",\"s\":\"t\"");
+            } else {
+                write!(json, ",\"ph\":\"X\",\"dur\":{}", duration).unwrap();
+            }
+            if let EventKind::Block(size) | EventKind::Subgraph(_, size) = event.kind {
+                write!(json, ",\"args\":{{\"size\":{}}}", size).unwrap();
+            }
+            json.push('}');
+        }
+        json.push_str("]}");
+        json
+    }
+    /// Export as a svg timeline: one row per thread, one rectangle per span.
+    pub fn to_svg(&self) -> String {
+        const WIDTH: f64 = 1920.0;
+        const ROW: f64 = 40.0;
+        let events = self.events();
+        let threads = events.iter().map(|e| e.thread + 1).max().unwrap_or(0);
+        let end = events
+            .iter()
+            .map(|e| e.end)
+            .max()
+            .unwrap_or_default()
+            .as_nanos()
+            .max(1) as f64;
+        let x = |time: Duration| time.as_nanos() as f64 * WIDTH / end;
+        let mut svg = String::new();
+        write!(
+            svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\">",
+            WIDTH,
+            threads as f64 * ROW
+        )
+        .unwrap();
+        // tasks first so that everything else is drawn over them
+        let (tasks, others): (Vec<&Event>, Vec<&Event>) =
+            events.iter().partition(|e| e.kind == EventKind::Task);
+        for event in tasks.iter().chain(others.iter()) {
+            let y = event.thread as f64 * ROW;
+            let (top, height) = match event.kind {
+                EventKind::Task => (y, ROW - 2.0),
+                _ => (y + ROW / 4.0, ROW / 2.0),
+            };
+            write!(
+                svg,
+                "<rect x=\"{:.3}\" y=\"{}\" width=\"{:.3}\" height=\"{}\" fill=\"{}\"><title>{} {:?}</title></rect>",
+                x(event.start),
+                top,
+                (x(event.end) - x(event.start)).max(1.0),
+                height,
+                event.kind.color(),
+                xml_escaped(event.kind.name()),
+                event.end - event.start
+            )
+            .unwrap();
+        }
+        svg.push_str("</svg>");
+        svg
+    }
+    /// Save the chrome trace into given file.
+    pub fn save_chrome_json<P: AsRef<std::path::Path>>(&self, path: P) -> std::io::Result<()> {
+        std::fs::write(path, self.to_chrome_json())
+    }
+    /// Save the svg timeline into given file.
+    pub fn save_svg<P: AsRef<std::path::Path>>(&self, path: P) -> std::io::Result<()> {
+        std::fs::write(path, self.to_svg())
+    }
+}
+
+impl<'e> Executor for Tracer<'e> {
+    fn join_context(
+        &self,
+        oper_a: &mut (dyn FnMut(bool) + Send),
+        oper_b: &mut (dyn FnMut(bool) + Send),
+    ) {
+        let now = Instant::now();
+        self.record(EventKind::Spawn, now, now);
+        self.inner.join_context(
+            &mut |migrated| self.span(EventKind::Task, || oper_a(migrated)),
+            &mut |migrated| {
+                if migrated {
+                    let now = Instant::now();
+                    self.record(EventKind::Steal, now, now);
+                }
+                self.span(EventKind::Task, || oper_b(migrated))
+            },
+        )
+    }
+    fn install(&self, op: &mut (dyn FnMut() + Send)) {
+        self.inner.install(op)
+    }
+    fn current_num_threads(&self) -> usize {
+        self.inner.current_num_threads()
+    }
+    fn current_thread_index(&self) -> Option<usize> {
+        self.inner.current_thread_index()
+    }
+    fn steal_requested(&self) -> bool {
+        self.inner.steal_requested()
+    }
+    fn is_tracing(&self) -> bool {
+        true
+    }
+    fn record(&self, kind: EventKind, start: Instant, end: Instant) {
+        let thread = self.thread();
+        let event = Event {
+            kind,
+            thread,
+            start: start - self.origin,
+            end: end - self.origin,
+        };
+        let buffer = &self.buffers[thread.min(self.buffers.len() - 1)];
+        buffer.lock().unwrap().push(event)
+    }
+}
+
+/// Run `op`, recording it as a span if the current executor is tracing.
+pub(crate) fn traced<R>(kind: EventKind, op: impl FnOnce() -> R) -> R {
+    with_current(|executor| {
+        if executor.is_tracing() {
+            let start = Instant::now();
+            let result = op();
+            executor.record(kind, start, Instant::now());
+            result
+        } else {
+            op()
+        }
+    })
+}
@@ -2,11 +2,14 @@ use crate::adaptors::{
     all::All,
     bound_depth::BoundDepth,
     by_blocks::ByBlocks,
+    cancellable::{Cancellable, CancellationToken},
     cap::Cap,
+    chain::Chain,
     composition::Composed,
     composition::ComposedCounter,
     composition::ComposedSize,
     composition::ComposedTask,
+    enumerate::Enumerate,
     even_levels::EvenLevels,
     filter::Filter,
     flat_map::FlatMap,
@@ -17,22 +20,35 @@ use crate::adaptors::{
     merge::Merge,
     microblocks::MicroBlockSizes,
     next::Next,
+    on::On,
+    position::Position,
     rayon_policy::Rayon,
     rev::Rev,
+    scan::Scan,
     scheduler_adaptors::{Adaptive, DepJoin, Sequential},
+    set_operation::{Dedup, SetOperation},
     size_limit::SizeLimit,
+    skip::Skip,
+    step_by::StepBy,
+    take::Take,
     // try_fold::TryFold,
     zip::Zip,
 };
+use crate::algorithms::partition::concatenate_blocks;
+use crate::algorithms::select::BoundedHeap;
+use crate::algorithms::set_operations::Operation;
+use crate::collect::VecCollector;
+use crate::executor::Executor;
 use crate::prelude::*;
 use crate::schedulers::JoinScheduler;
 use crate::try_fold::try_fold;
+use crate::unwind::BlockPanic;
 use crate::worker::OwningWorker;
 use crate::wrap::Wrap;
 use crate::Try;
+use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::sync::atomic::{AtomicBool, AtomicIsize, Ordering};
 
-#[cfg(feature = "logs")]
 use crate::adaptors::log::Log;
 // Iterators have different properties
 // which allow for specialisation of some algorithms.
@@ -149,7 +165,7 @@ pub trait ParallelIterator: Sized {
     //it is only needed for SPECIALIZATION,
     //so is there a method which is implemented for everyone but
     //where implementations differ based on power ?
-    type Enumerable;
+    type Enumerable: VecCollector;
 
     fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
         let c = ConsumerCallback(consumer);
@@ -162,6 +178,23 @@ pub trait ParallelIterator: Sized {
     fn cap(self, limit: &AtomicIsize) -> Cap<Self> {
         Cap { base: self, limit }
     }
+    /// Stop the computation as soon as given token is cancelled.
+    /// Use it as the last adaptor: final calls then return a `Result`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// use kvik::CancellationToken;
+    /// let token = CancellationToken::new();
+    /// let r = (0u32..1000).into_par_iter().cancellable(&token).reduce(|| 0, |a, b| a + b);
+    /// assert_eq!(r, Ok(499_500));
+    /// token.cancel();
+    /// assert!((0u32..1000).into_par_iter().cancellable(&token).for_each(|_| ()).is_err());
+    /// ```
+    fn cancellable(self, token: &CancellationToken) -> Cancellable<Self> {
+        Cancellable { base: self, token }
+    }
     /// Use rayon's steals reducing scheduling policy.
     fn rayon(self, limit: usize) -> Rayon<Self> {
         Rayon {
@@ -182,6 +215,23 @@ pub trait ParallelIterator: Sized {
     fn depjoin(self) -> DepJoin<Self> {
         DepJoin { base: self }
     }
+    /// Run on given executor instead of rayon's global pool.
+    /// Must be called just before the final reduction.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// use kvik::SequentialExecutor;
+    /// let s = (0u32..100).into_par_iter().on(&SequentialExecutor).reduce(|| 0, |a, b| a + b);
+    /// assert_eq!(s, 4950);
+    /// ```
+    fn on(self, executor: &dyn Executor) -> On<Self> {
+        On {
+            base: self,
+            executor,
+        }
+    }
     /// Turn back an adaptive reducer.
     /// Must be called just before the final reduction.
     fn adaptive(self) -> Adaptive<Self> {
@@ -338,7 +388,10 @@ pub trait ParallelIterator: Sized {
     // this will cancel tasks to the right of the element.
     // it is useful for find_first.
     fn next(self) -> Next<Self> {
-        Next { base: self }
+        Next {
+            base: self,
+            ordered: true,
+        }
     }
 
     // This is without blocks.
@@ -349,6 +402,52 @@ pub trait ParallelIterator: Sized {
         self.filter(predicate).next().reduce_with(|a, _| a)
     }
 
+    /// Return any element matching the predicate (not necessarily the first one).
+    /// All tasks are cancelled as soon as one element is found.
+    fn find_any<P: Fn(&Self::Item) -> bool + Send + Sync>(
+        self,
+        predicate: P,
+    ) -> Option<Self::Item> {
+        Next {
+            base: self.filter(predicate),
+            ordered: false,
+        }
+        .reduce_with(|a, _| a)
+    }
+
+    /// Return whether any element satisfies the predicate.
+    /// Remaining tasks are cancelled as soon as one is found.
+    /// # Example:
+    /// ```
+    /// use kvik::prelude::*;
+    /// assert!((0u64..1000).into_par_iter().any(|e| e == 500));
+    /// ```
+    fn any<P>(self, predicate: P) -> bool
+    where
+        P: Fn(Self::Item) -> bool + Sync + Send,
+    {
+        Next {
+            base: self.map(predicate).filter(|&found| found),
+            ordered: false,
+        }
+        .reduce_with(|a, _| a)
+        .is_some()
+    }
+
+    /// Return the last element matching the predicate.
+    /// This is a `find_first` on the reversed iterator.
+    /// # Example:
+    /// ```
+    /// use kvik::prelude::*;
+    /// assert_eq!((0u32..1000).into_par_iter().find_last(|&e| e % 7 == 0), Some(994));
+    /// ```
+    fn find_last<P: Fn(&Self::Item) -> bool + Send + Sync>(
+        self,
+        predicate: P,
+    ) -> Option<Self::Item> {
+        self.rev().find_first(predicate)
+    }
+
     // fn try_fold<T, R, ID, F>(self, identity: ID, fold_op: F) -> TryFold<Self, R, ID, F>
     // where
     //     F: Fn(T, Self::Item) -> R + Sync + Send,
@@ -413,11 +512,171 @@ pub trait ParallelIterator: Sized {
         )
     }
 
-    #[cfg(feature = "logs")]
+    /// Return the maximum element according to given comparison function.
+    /// If several elements are equally maximum, the last one is returned.
+    fn max_by<F>(self, compare: F) -> Option<Self::Item>
+    where
+        F: Fn(&Self::Item, &Self::Item) -> std::cmp::Ordering + Sync + Send,
+    {
+        self.reduce_with(|a, b| match compare(&a, &b) {
+            std::cmp::Ordering::Greater => a,
+            _ => b,
+        })
+    }
+
+    /// Return the minimum element.
+    /// If several elements are equally minimum, the first one is returned.
+    /// # Example:
+    /// ```
+    /// use kvik::prelude::*;
+    /// assert_eq!((3u32..10).into_par_iter().adaptive().min(), Some(3));
+    /// ```
+    fn min(self) -> Option<Self::Item>
+    where
+        Self::Item: Ord,
+    {
+        self.min_by(Ord::cmp)
+    }
+
+    /// Return the maximum element.
+    /// If several elements are equally maximum, the last one is returned.
+    /// # Example:
+    /// ```
+    /// use kvik::prelude::*;
+    /// assert_eq!((3u32..10).into_par_iter().depjoin().max(), Some(9));
+    /// ```
+    fn max(self) -> Option<Self::Item>
+    where
+        Self::Item: Ord,
+    {
+        self.max_by(Ord::cmp)
+    }
+
+    /// Return the element giving the minimum value for the key function.
+    fn min_by_key<K, F>(self, f: F) -> Option<Self::Item>
+    where
+        K: Ord + Send,
+        F: Fn(&Self::Item) -> K + Sync + Send,
+    {
+        self.map(|e| (f(&e), e))
+            .min_by(|(ka, _), (kb, _)| ka.cmp(kb))
+            .map(|(_, e)| e)
+    }
+
+    /// Return the element giving the maximum value for the key function.
+    fn max_by_key<K, F>(self, f: F) -> Option<Self::Item>
+    where
+        K: Ord + Send,
+        F: Fn(&Self::Item) -> K + Sync + Send,
+    {
+        self.map(|e| (f(&e), e))
+            .max_by(|(ka, _), (kb, _)| ka.cmp(kb))
+            .map(|(_, e)| e)
+    }
+
+    /// Sum all elements.
+    /// # Example:
+    /// ```
+    /// use kvik::prelude::*;
+    /// let s: u64 = (0u64..100).into_par_iter().rayon(2).sum();
+    /// assert_eq!(s, 4950);
+    /// ```
+    fn sum<S>(self) -> S
+    where
+        S: Send + std::iter::Sum<Self::Item> + std::iter::Sum<S>,
+    {
+        self.map(|e| std::iter::once(e).sum::<S>()).reduce(
+            || std::iter::empty::<S>().sum(),
+            |a, b| std::iter::once(a).chain(std::iter::once(b)).sum(),
+        )
+    }
+
+    /// Multiply all elements.
+    /// # Example:
+    /// ```
+    /// use kvik::prelude::*;
+    /// let p: u64 = (1u64..11).into_par_iter().product();
+    /// assert_eq!(p, 3_628_800);
+    /// ```
+    fn product<P>(self) -> P
+    where
+        P: Send + std::iter::Product<Self::Item> + std::iter::Product<P>,
+    {
+        self.map(|e| std::iter::once(e).product::<P>()).reduce(
+            || std::iter::empty::<P>().product(),
+            |a, b| std::iter::once(a).chain(std::iter::once(b)).product(),
+        )
+    }
+
+    /// Count the number of elements.
+    fn count(self) -> usize {
+        self.map(|_| 1usize).reduce(|| 0, |a, b| a + b)
+    }
+
+    /// Record each sequential fold of this iterator under given name in traces
+    /// (see `Tracer`, and rayon_logs with the `logs` feature).
     fn log(self, name: &'static str) -> Log<Self> {
         Log { base: self, name }
     }
 
+    /// Split into elements satisfying the predicate and all others, preserving order.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// let (evens, odds) = (0u32..10_000).into_par_iter().partition_into(|e| e % 2 == 0);
+    /// assert!(evens.iter().enumerate().all(|(i, &e)| e == 2 * i as u32));
+    /// assert!(odds.iter().enumerate().all(|(i, &e)| e == 2 * i as u32 + 1));
+    /// ```
+    fn partition_into<P>(self, predicate: P) -> (Vec<Self::Item>, Vec<Self::Item>)
+    where
+        P: Fn(&Self::Item) -> bool + Sync + Send,
+    {
+        let (selected, rejected): (Vec<_>, Vec<_>) = self
+            .fold(
+                || (Vec::new(), Vec::new()),
+                |(mut selected, mut rejected), e| {
+                    if predicate(&e) {
+                        selected.push(e)
+                    } else {
+                        rejected.push(e)
+                    }
+                    (selected, rejected)
+                },
+            )
+            .map(|blocks| vec![blocks])
+            .reduce(Vec::new, |mut left, mut right| {
+                left.append(&mut right);
+                left
+            })
+            .into_iter()
+            .unzip();
+        (concatenate_blocks(selected), concatenate_blocks(rejected))
+    }
+
+    /// Return the `k` greatest elements according to `compare`, greatest first.
+    /// Each fold keeps its best elements in a heap of size `k`
+    /// and the reduction merges the heaps.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// let words = vec!["a", "bbbb", "cc", "ddd", "eeeee", "f"];
+    /// let longest = words.par_iter().map(|w| *w).top_k(3, |a, b| a.len().cmp(&b.len()));
+    /// assert_eq!(longest, vec!["eeeee", "bbbb", "ddd"]);
+    /// ```
+    fn top_k<F>(self, k: usize, compare: F) -> Vec<Self::Item>
+    where
+        F: Fn(&Self::Item, &Self::Item) -> std::cmp::Ordering + Sync + Send,
+    {
+        let compare = &compare;
+        self.fold(|| BoundedHeap::new(k, compare), BoundedHeap::push)
+            .reduce(|| BoundedHeap::new(k, compare), BoundedHeap::merge)
+            .into_sorted_vec()
+    }
+
     fn collect<T: FromParallelIterator<Self::Item>>(self) -> T
     where
         <Self as ParallelIterator>::Item: Sync,
@@ -498,6 +757,177 @@ pub trait EnumerableParallelIterator: ParallelIterator {
             b: other.into_par_iter(),
         }
     }
+    /// Yield each element together with its index.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// let v = vec![5, 6, 7];
+    /// let s = v.par_iter().enumerate().map(|(i, e)| i * e).reduce(|| 0, |a, b| a + b);
+    /// assert_eq!(s, 20)
+    /// ```
+    fn enumerate(self) -> Enumerate<Self> {
+        Enumerate { base: self }
+    }
+    /// Only keep the first `n` elements.
+    fn take(self, n: usize) -> Take<Self> {
+        Take { base: self, n }
+    }
+    /// Skip the first `n` elements.
+    fn skip(self, n: usize) -> Skip<Self> {
+        Skip { base: self, n }
+    }
+    /// Yield the first element and then one every `step` elements.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// let v: Vec<u32> = (0u32..10).into_par_iter().step_by(3).collect();
+    /// assert_eq!(v, vec![0, 3, 6, 9])
+    /// ```
+    fn step_by(self, step: usize) -> StepBy<Self> {
+        assert!(step != 0, "step_by needs a non zero step");
+        StepBy { base: self, step }
+    }
+    /// Iterate on all elements of `self` and then on all elements of `other`.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// let v: Vec<u32> = (0u32..3).into_par_iter().chain(5u32..7).collect();
+    /// assert_eq!(v, vec![0, 1, 2, 5, 6])
+    /// ```
+    fn chain<I>(self, other: I) -> Chain<Self, I::Iter>
+    where
+        I: IntoParallelIterator<Item = Self::Item>,
+        I::Iter: ParallelIterator<Enumerable = True>,
+    {
+        Chain {
+            a: self,
+            b: other.into_par_iter(),
+        }
+    }
+    /// Inclusive scan: iterate on all prefixes `op(identity, op(x0, ... xi))`.
+    /// `op` needs to be associative.
+    /// Prefixes are computed in place with the adaptive `par_prefix_sum_in_place`
+    /// when the iterator is driven.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// let v: Vec<u32> = (1u32..5).into_par_iter().scan(|| 10, |a, b| a + b).collect();
+    /// assert_eq!(v, vec![11, 13, 16, 20])
+    /// ```
+    fn scan<ID, OP>(self, identity: ID, op: OP) -> Scan<Self, ID, OP>
+    where
+        Self::Item: Sync,
+        ID: Fn() -> Self::Item + Send,
+        OP: Fn(&Self::Item, &Self::Item) -> Self::Item + Sync + Send,
+    {
+        Scan {
+            base: self,
+            identity,
+            op,
+            exclusive: false,
+        }
+    }
+    /// Exclusive scan: iterate on all prefixes `op(identity, op(x0, ... xi-1))`.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// let v: Vec<u32> = (1u32..5)
+    ///     .into_par_iter()
+    ///     .exclusive_scan(|| 0, |a, b| a + b)
+    ///     .collect();
+    /// assert_eq!(v, vec![0, 1, 3, 6])
+    /// ```
+    fn exclusive_scan<ID, OP>(self, identity: ID, op: OP) -> Scan<Self, ID, OP>
+    where
+        Self::Item: Sync,
+        ID: Fn() -> Self::Item + Send,
+        OP: Fn(&Self::Item, &Self::Item) -> Self::Item + Sync + Send,
+    {
+        Scan {
+            base: self,
+            identity,
+            op,
+            exclusive: true,
+        }
+    }
+    /// Apply `op` on all elements, catching panics.
+    /// A panicking block stops but all other blocks run to completion.
+    /// On failure we report the leftmost panicking block and element.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// let r = (0u32..1000).into_par_iter().try_for_each_unwind(|e| {
+    ///     if e == 500 {
+    ///         panic!("boom")
+    ///     }
+    /// });
+    /// let block_panic = r.unwrap_err();
+    /// assert_eq!(block_panic.index, 500);
+    /// assert!(block_panic.block_start <= 500);
+    /// ```
+    fn try_for_each_unwind<OP>(self, op: OP) -> Result<(), BlockPanic>
+    where
+        OP: Fn(Self::Item) + Sync + Send,
+    {
+        self.enumerate()
+            .fold(
+                || Ok(None),
+                |state: Result<Option<usize>, BlockPanic>, (index, e)| {
+                    let block_start = state?.unwrap_or(index);
+                    catch_unwind(AssertUnwindSafe(|| op(e)))
+                        .map(|_| Some(block_start))
+                        .map_err(|payload| BlockPanic {
+                            block_start,
+                            index,
+                            payload,
+                        })
+                },
+            )
+            .reduce(|| Ok(None), |left, right| left.and(right))
+            .map(|_| ())
+    }
+    /// Return the index of the first element satisfying the predicate.
+    /// Tasks to the right of a match are cancelled.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// let v = vec![1, 3, 5, 6, 7, 8];
+    /// assert_eq!(v.par_iter().position_first(|&e| e % 2 == 0), Some(3));
+    /// ```
+    fn position_first<P>(self, predicate: P) -> Option<usize>
+    where
+        P: Fn(Self::Item) -> bool + Sync + Send,
+    {
+        self.with_producer(Position {
+            predicate: &predicate,
+            ordered: true,
+        })
+    }
+    /// Return the index of any element satisfying the predicate.
+    /// All tasks are cancelled as soon as a match is found.
+    fn position_any<P>(self, predicate: P) -> Option<usize>
+    where
+        P: Fn(Self::Item) -> bool + Sync + Send,
+    {
+        self.with_producer(Position {
+            predicate: &predicate,
+            ordered: false,
+        })
+    }
 }
 
 pub trait PreviewableParallelIterator: ParallelIterator {
@@ -512,6 +942,119 @@ pub trait PreviewableParallelIterator: ParallelIterator {
             b: other.into_par_iter(),
         }
     }
+    /// Union of two sorted iterators.
+    /// Values appearing m times in self and n times in other appear max(m, n) times.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// let a: Vec<u32> = (0..1000).map(|i| 2 * i).collect();
+    /// let b: Vec<u32> = (0..1000).map(|i| 3 * i).collect();
+    /// let v: Vec<&u32> = a.par_iter().union(b.as_slice()).collect();
+    /// assert_eq!(v.len(), 1000 + 1000 - 334);
+    /// assert!(v.windows(2).all(|w| w[0] < w[1]));
+    /// ```
+    fn union<I, J>(self, other: J) -> SetOperation<Self, I>
+    where
+        I: PreviewableParallelIterator<Item = Self::Item>,
+        J: IntoParallelIterator<Item = Self::Item, Iter = I>,
+        Self::Item: Ord,
+    {
+        SetOperation {
+            a: self,
+            b: other.into_par_iter(),
+            operation: Operation::Union,
+        }
+    }
+    /// Intersection of two sorted iterators.
+    /// Values appearing m times in self and n times in other appear min(m, n) times.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// let a: Vec<u32> = (0..1000).map(|i| 2 * i).collect();
+    /// let b: Vec<u32> = (0..1000).map(|i| 3 * i).collect();
+    /// let v: Vec<&u32> = a.par_iter().intersection(b.as_slice()).collect();
+    /// assert!(v.iter().enumerate().all(|(i, &&e)| e == 6 * i as u32));
+    /// ```
+    fn intersection<I, J>(self, other: J) -> SetOperation<Self, I>
+    where
+        I: PreviewableParallelIterator<Item = Self::Item>,
+        J: IntoParallelIterator<Item = Self::Item, Iter = I>,
+        Self::Item: Ord,
+    {
+        SetOperation {
+            a: self,
+            b: other.into_par_iter(),
+            operation: Operation::Intersection,
+        }
+    }
+    /// Elements of self which are not in other (both sorted).
+    /// Values appearing m times in self and n times in other appear m - n times.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// let a: Vec<u32> = (0..1000).collect();
+    /// let b: Vec<u32> = (0..500).map(|i| 2 * i).collect();
+    /// let v: Vec<&u32> = a.par_iter().difference(b.as_slice()).collect();
+    /// assert!(v.iter().enumerate().all(|(i, &&e)| e == 2 * i as u32 + 1));
+    /// ```
+    fn difference<I, J>(self, other: J) -> SetOperation<Self, I>
+    where
+        I: PreviewableParallelIterator<Item = Self::Item>,
+        J: IntoParallelIterator<Item = Self::Item, Iter = I>,
+        Self::Item: Ord,
+    {
+        SetOperation {
+            a: self,
+            b: other.into_par_iter(),
+            operation: Operation::Difference,
+        }
+    }
+    /// Elements in exactly one of two sorted iterators.
+    /// Values appearing m times in self and n times in other appear |m - n| times.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// let v: Vec<u32> = (0..1000u32)
+    ///     .into_par_iter()
+    ///     .symmetric_difference(500..1500u32)
+    ///     .collect();
+    /// assert_eq!(v, (0..500).chain(1000..1500).collect::<Vec<u32>>());
+    /// ```
+    fn symmetric_difference<I, J>(self, other: J) -> SetOperation<Self, I>
+    where
+        I: PreviewableParallelIterator<Item = Self::Item>,
+        J: IntoParallelIterator<Item = Self::Item, Iter = I>,
+        Self::Item: Ord,
+    {
+        SetOperation {
+            a: self,
+            b: other.into_par_iter(),
+            operation: Operation::SymmetricDifference,
+        }
+    }
+    /// Remove consecutive duplicates of a sorted iterator.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use kvik::prelude::*;
+    /// let v: Vec<u32> = (0..10_000u32).into_par_iter().map(|i| i / 10).dedup().collect();
+    /// assert_eq!(v, (0..1000).collect::<Vec<u32>>());
+    /// ```
+    fn dedup(self) -> Dedup<Self>
+    where
+        Self::Item: Ord,
+    {
+        Dedup { base: self }
+    }
 }
 
 impl<I> EnumerableParallelIterator for I where I: ParallelIterator<Enumerable = True> {}
@@ -575,34 +1118,27 @@ pub trait FromParallelIterator<A: Sync + Send> {
 }
 
 impl<A: Sync + Send> FromParallelIterator<A> for Vec<A> {
+    /// Enumerable iterators are collected directly into their final places,
+    /// all others through a list of blocks.
     fn from_par_iter<T: ParallelIterator<Item = A>>(iter: T) -> Self {
-        use std::collections::LinkedList;
-
-        let l = iter
-            .fold(Vec::new, |mut v, e| {
-                v.push(e);
-                v
-            })
-            .map(|v| std::iter::once(v).collect::<LinkedList<Vec<T::Item>>>())
-            .reduce(LinkedList::new, |mut l1, mut l2| {
-                l1.append(&mut l2);
-                l1
-            });
-
-        let mut iter_list = l.into_iter();
-        let first = iter_list.next();
-
-        if let Some(first) = first {
-            iter_list.fold(first, |mut v, mut v2| {
-                v.append(&mut v2);
-                v
-            })
-        } else {
-            Vec::new()
-        }
+        T::Enumerable::collect_vec(iter)
     }
 }
 
+/// Extend a collection with all elements of a parallel iterator.
+///
+/// # Example:
+///
+/// ```
+/// use kvik::prelude::*;
+/// let mut v = vec![0u32, 1];
+/// v.par_extend((2u32..5).into_par_iter());
+/// assert_eq!(v, vec![0, 1, 2, 3, 4]);
+/// ```
+pub trait ParallelExtend<A: Sync + Send> {
+    fn par_extend<T: ParallelIterator<Item = A>>(&mut self, iter: T);
+}
+
 //TODO: we could separate folder and reducer to remove one pointer level
 pub trait Reducer<Result>: Sync {
     // we need this guy for the adaptive scheduler
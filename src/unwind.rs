@@ -0,0 +1,48 @@
+//! Recover from panics happening inside user closures.
+use std::any::Any;
+use std::fmt;
+
+/// A panic caught while processing a sequential block of elements.
+pub struct BlockPanic {
+    /// Index of the first element of the block.
+    pub block_start: usize,
+    /// Index of the element on which we panicked.
+    pub index: usize,
+    /// What was given to `panic!`.
+    pub payload: Box<dyn Any + Send>,
+}
+
+impl fmt::Debug for BlockPanic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BlockPanic")
+            .field("block_start", &self.block_start)
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+impl BlockPanic {
+    /// The panic message, if `panic!` was given a string.
+    pub fn message(&self) -> Option<&str> {
+        self.payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| self.payload.downcast_ref::<String>().map(|s| s.as_str()))
+    }
+}
+
+impl fmt::Display for BlockPanic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "panicked on element {} (block starting at {})",
+            self.index, self.block_start
+        )?;
+        if let Some(message) = self.message() {
+            write!(f, ": {}", message)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for BlockPanic {}
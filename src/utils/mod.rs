@@ -1 +1,2 @@
 pub mod slice_utils;
+pub mod windowed;
@@ -110,3 +110,7 @@ pub fn subslice_without_last_value<T: Eq>(slice: &[T]) -> &[T] {
         None => slice,
     }
 }
+
+/// Raw pointer into a slice, shared between tasks writing at disjoint positions.
+pub(crate) struct SharedSlice<T>(pub(crate) *mut T);
+unsafe impl<T: Send> Sync for SharedSlice<T> {}
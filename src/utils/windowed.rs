@@ -0,0 +1,78 @@
+//! Read-only pieces of a slice overlapping with their right neighbour.
+use crate::prelude::*;
+use std::ops::Range;
+
+/// Divisible over the positions of a slice.
+/// Each piece owns a range of positions but can read `overlap` more elements after it,
+/// so that anything starting in the piece can be examined without any second pass.
+///
+/// # Example:
+///
+/// ```
+/// use kvik::prelude::*;
+/// use kvik::utils::windowed::windowed;
+/// let v: Vec<u32> = (0..1000).collect();
+/// // count all consecutive pairs, each pair starting in exactly one piece
+/// let pairs = windowed(&v, 1)
+///     .wrap_iter()
+///     .rayon(2)
+///     .map(|w| w.window().windows(2).count().min(w.owned().len()))
+///     .reduce(|| 0, |a, b| a + b);
+/// assert_eq!(pairs, 999);
+/// ```
+#[derive(Debug)]
+pub struct Windowed<'a, T> {
+    data: &'a [T],
+    start: usize,
+    end: usize,
+    overlap: usize,
+}
+
+/// Cut given slice in overlapping pieces, each reading `overlap` elements into the next one.
+pub fn windowed<T>(data: &[T], overlap: usize) -> Windowed<'_, T> {
+    Windowed {
+        data,
+        start: 0,
+        end: data.len(),
+        overlap,
+    }
+}
+
+impl<'a, T> Windowed<'a, T> {
+    /// Positions owned by this piece.
+    pub fn owned(&self) -> Range<usize> {
+        self.start..self.end
+    }
+    /// All elements we can read: the owned ones and the overlap.
+    pub fn window(&self) -> &'a [T] {
+        &self.data[self.start..(self.end + self.overlap).min(self.data.len())]
+    }
+}
+
+impl<'a, T: Sync> Divisible for Windowed<'a, T> {
+    type Controlled = True;
+    fn should_be_divided(&self) -> bool {
+        self.end - self.start >= 2
+    }
+    fn divide(self) -> (Self, Self) {
+        let mid = (self.end - self.start) / 2;
+        self.divide_at(mid)
+    }
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        let mid = self.start + index.min(self.end - self.start);
+        (
+            Windowed {
+                data: self.data,
+                start: self.start,
+                end: mid,
+                overlap: self.overlap,
+            },
+            Windowed {
+                data: self.data,
+                start: mid,
+                end: self.end,
+                overlap: self.overlap,
+            },
+        )
+    }
+}
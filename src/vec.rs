@@ -0,0 +1,142 @@
+//! Owning parallel iterators: elements are moved out of the collection.
+use crate::prelude::*;
+use crate::try_fold::try_fold;
+use crate::Try;
+use std::collections::VecDeque;
+
+pub struct IntoIter<T> {
+    vec: Vec<T>,
+}
+
+impl<T: Send> IntoParallelIterator for Vec<T> {
+    type Item = T;
+    type Iter = IntoIter<T>;
+    fn into_par_iter(self) -> Self::Iter {
+        IntoIter { vec: self }
+    }
+}
+
+impl<T: Send> IntoParallelIterator for VecDeque<T> {
+    type Item = T;
+    type Iter = IntoIter<T>;
+    fn into_par_iter(self) -> Self::Iter {
+        Vec::from(self).into_par_iter()
+    }
+}
+
+impl<T: Send> IntoParallelIterator for Box<[T]> {
+    type Item = T;
+    type Iter = IntoIter<T>;
+    fn into_par_iter(self) -> Self::Iter {
+        self.into_vec().into_par_iter()
+    }
+}
+
+impl<T: Send> ParallelIterator for IntoIter<T> {
+    type Item = T;
+    type Controlled = True;
+    type Enumerable = True;
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        let mut vec = self.vec;
+        let len = vec.len();
+        // the vector gives up ownership of its elements, the producers will move
+        // them out or drop them. the vector itself only frees its buffer.
+        unsafe {
+            vec.set_len(0);
+            let slice = std::slice::from_raw_parts_mut(vec.as_mut_ptr(), len);
+            callback.call(DrainProducer { slice })
+        }
+    }
+}
+
+/// Owns all elements in the slice.
+/// Elements are read out when yielded and the remaining ones are dropped with us.
+struct DrainProducer<'a, T> {
+    slice: &'a mut [T],
+}
+
+impl<'a, T> Iterator for DrainProducer<'a, T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        let slice = std::mem::take(&mut self.slice);
+        slice.split_first_mut().map(|(first, remaining)| {
+            self.slice = remaining;
+            unsafe { std::ptr::read(first) }
+        })
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.slice.len(), Some(self.slice.len()))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for DrainProducer<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let slice = std::mem::take(&mut self.slice);
+        slice.split_last_mut().map(|(last, remaining)| {
+            self.slice = remaining;
+            unsafe { std::ptr::read(last) }
+        })
+    }
+}
+
+impl<'a, T> Drop for DrainProducer<'a, T> {
+    fn drop(&mut self) {
+        // drop all elements we did not yield
+        unsafe { std::ptr::drop_in_place(self.slice) }
+    }
+}
+
+impl<'a, T> Divisible for DrainProducer<'a, T> {
+    type Controlled = True;
+    fn should_be_divided(&self) -> bool {
+        self.slice.len() >= 2
+    }
+    fn divide(self) -> (Self, Self) {
+        let mid = self.slice.len() / 2;
+        self.divide_at(mid)
+    }
+    fn divide_at(mut self, index: usize) -> (Self, Self) {
+        let slice = std::mem::take(&mut self.slice);
+        let index = index.min(slice.len());
+        let (left, right) = slice.split_at_mut(index);
+        (
+            DrainProducer { slice: left },
+            DrainProducer { slice: right },
+        )
+    }
+}
+
+impl<'a, T: Send> Producer for DrainProducer<'a, T> {
+    fn sizes(&self) -> (usize, Option<usize>) {
+        self.size_hint()
+    }
+    fn preview(&self, _index: usize) -> Self::Item {
+        panic!("owned elements are not peekable");
+    }
+    fn partial_fold<B, F>(&mut self, init: B, fold_op: F, limit: usize) -> B
+    where
+        B: Send,
+        F: Fn(B, Self::Item) -> B,
+    {
+        let slice = std::mem::take(&mut self.slice);
+        let limit = limit.min(slice.len());
+        let (left, right) = slice.split_at_mut(limit);
+        self.slice = right;
+        DrainProducer { slice: left }.fold(init, fold_op)
+    }
+    fn partial_try_fold<B, F, R>(&mut self, init: B, f: F, limit: usize) -> R
+    where
+        F: FnMut(B, Self::Item) -> R,
+        R: Try<Ok = B>,
+    {
+        let slice = std::mem::take(&mut self.slice);
+        let limit = limit.min(slice.len());
+        let (left, right) = slice.split_at_mut(limit);
+        self.slice = right;
+        // on early exit, whatever is left in the block is dropped with the producer
+        try_fold(&mut DrainProducer { slice: left }, init, f)
+    }
+}
@@ -0,0 +1,144 @@
+use kvik::prelude::*;
+use kvik::{CancellationToken, Cancelled};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[test]
+fn test_cancel_while_running() {
+    let token = CancellationToken::new();
+    let seen = AtomicUsize::new(0);
+    let r = (0u64..1_000_000)
+        .into_par_iter()
+        .adaptive()
+        .cancellable(&token)
+        .for_each(|e| {
+            seen.fetch_add(1, Ordering::Relaxed);
+            if e == 10 {
+                token.cancel()
+            }
+        });
+    assert_eq!(r, Err(Cancelled));
+    assert!(seen.load(Ordering::Relaxed) < 1_000_000);
+}
+
+#[test]
+fn test_all_final_calls_report_cancellation() {
+    let token = CancellationToken::new();
+    token.cancel();
+    let input: Vec<u32> = (0..10_000).collect();
+    let iter = || input.par_iter().map(|e| *e).cancellable(&token);
+    assert_eq!(iter().sum::<u32>(), Err(Cancelled));
+    assert_eq!(iter().product::<u32>(), Err(Cancelled));
+    assert_eq!(iter().count(), Err(Cancelled));
+    assert_eq!(iter().min(), Err(Cancelled));
+    assert_eq!(iter().max_by_key(|e| e % 7), Err(Cancelled));
+    assert_eq!(iter().find_first(|e| *e == 5), Err(Cancelled));
+    assert_eq!(iter().find_any(|e| *e == 5), Err(Cancelled));
+    assert_eq!(iter().any(|e| e == 5), Err(Cancelled));
+    assert_eq!(iter().all(|e| e < 10_000), Err(Cancelled));
+    assert_eq!(iter().position_first(|e| e == 5), Err(Cancelled));
+    assert_eq!(iter().top_k(3, |a, b| a.cmp(b)), Err(Cancelled));
+    assert_eq!(iter().collect::<Vec<u32>>(), Err(Cancelled));
+    assert!(iter().collect::<std::collections::HashSet<u32>>().is_err());
+}
+
+#[test]
+fn test_final_calls_without_cancellation() {
+    let token = CancellationToken::new();
+    let input: Vec<u32> = (0..10_000).collect();
+    let iter = || input.par_iter().map(|e| *e).cancellable(&token);
+    assert_eq!(iter().sum::<u32>(), Ok(9_999 * 5_000));
+    assert_eq!(iter().count(), Ok(10_000));
+    assert_eq!(iter().max(), Ok(Some(9_999)));
+    assert_eq!(iter().find_first(|e| e % 1000 == 999), Ok(Some(999)));
+    assert_eq!(iter().position_any(|e| e == 1234), Ok(Some(1234)));
+    assert_eq!(iter().collect::<Vec<u32>>(), Ok(input.clone()));
+    let (even, odd) = iter().partition_into(|e| e % 2 == 0).unwrap();
+    assert_eq!(even.len(), odd.len());
+}
+
+#[test]
+fn test_collect_cancelled_after_division() {
+    let tp = rayon::ThreadPoolBuilder::new()
+        .num_threads(4)
+        .build()
+        .expect("Thread pool build failed");
+    let token = CancellationToken::new();
+    let collected = tp.install(|| {
+        (0u32..1_000_000)
+            .into_par_iter()
+            .map(|e| {
+                if e == 500_000 {
+                    token.cancel()
+                }
+                e.to_string()
+            })
+            .cancellable(&token)
+            .collect::<Vec<String>>()
+    });
+    assert_eq!(collected, Err(Cancelled));
+}
+
+#[test]
+fn test_cancellation_stops_upstream_work() {
+    for cancel_at in &[0, 10, 500_000] {
+        let token = CancellationToken::new();
+        let calls = AtomicUsize::new(0);
+        let r = (0u64..1_000_000)
+            .into_par_iter()
+            .map(|e| {
+                calls.fetch_add(1, Ordering::Relaxed);
+                e
+            })
+            .adaptive()
+            .cancellable(&token)
+            .for_each(|e| {
+                if e == *cancel_at {
+                    token.cancel()
+                }
+            });
+        assert_eq!(r, Err(Cancelled));
+        // only blocks already started when cancelling complete
+        assert!(calls.load(Ordering::Relaxed) < 1_000_000);
+    }
+    // nothing at all runs once cancelled
+    let token = CancellationToken::new();
+    token.cancel();
+    let calls = AtomicUsize::new(0);
+    let r = (0u64..1_000_000)
+        .into_par_iter()
+        .map(|e| {
+            calls.fetch_add(1, Ordering::Relaxed);
+            e
+        })
+        .cancellable(&token)
+        .collect::<Vec<u64>>();
+    assert_eq!(r, Err(Cancelled));
+    assert_eq!(calls.load(Ordering::Relaxed), 0);
+}
+
+#[test]
+fn test_collect_after_cancellation() {
+    // yielding less elements than the base must not break collections
+    let token = CancellationToken::new();
+    let r = (0u64..1_000_000)
+        .into_par_iter()
+        .cancellable(&token)
+        .map(|e| {
+            if e == 10 {
+                token.cancel()
+            }
+            e
+        })
+        .collect::<Vec<u64>>();
+    assert!(r.len() < 1_000_000);
+    let token = CancellationToken::new();
+    let r = (0u32..10_000)
+        .into_par_iter()
+        .cancellable(&token)
+        .try_for_each_unwind(|e| {
+            if e == 5_000 {
+                panic!("boom")
+            }
+        });
+    assert_eq!(r.unwrap().unwrap_err().index, 5_000);
+}
@@ -0,0 +1,92 @@
+use kvik::prelude::*;
+
+#[test]
+fn test_partial_chunks() {
+    let v: Vec<u32> = (0..10_001).collect();
+    // the last chunk is shorter, even when dividing at arbitrary indices
+    let chunks: Vec<&[u32]> = v.par_chunks(100).skip(37).collect();
+    assert_eq!(chunks, v.chunks(100).skip(37).collect::<Vec<_>>());
+    assert_eq!(chunks.last().unwrap(), &[10_000]);
+    let chunks: Vec<&[u32]> = v.par_chunks(7).rev().take(2).collect();
+    assert_eq!(chunks, [&v[9_996..], &v[9_989..9_996]]);
+    // rchunks have the short chunk first
+    let rchunks: Vec<&[u32]> = v.par_rchunks(100).collect();
+    assert_eq!(rchunks, v.rchunks(100).collect::<Vec<_>>());
+    assert_eq!(rchunks.last().unwrap(), &[0]);
+    let rchunks: Vec<&[u32]> = v.par_rchunks(3).rev().collect();
+    assert_eq!(rchunks, v.rchunks(3).rev().collect::<Vec<_>>());
+    // chunks larger than the slice and empty slices
+    let chunks: Vec<&[u32]> = v.par_chunks(20_000).collect();
+    assert_eq!(chunks, [&v[..]]);
+    let empty: &[u32] = &[];
+    assert_eq!(empty.par_chunks(3).count(), 0);
+    assert_eq!(empty.par_rchunks(3).count(), 0);
+}
+
+#[test]
+fn test_exact_chunks_remainder() {
+    let v: Vec<u32> = (0..10_001).collect();
+    let exact = v.par_chunks_exact(100);
+    assert_eq!(exact.remainder(), &[10_000]);
+    let chunks: Vec<&[u32]> = exact.rev().collect();
+    assert_eq!(chunks, v.chunks_exact(100).rev().collect::<Vec<_>>());
+    // everything is in the remainder, or nothing
+    let exact = v.par_chunks_exact(20_000);
+    assert_eq!(exact.remainder().len(), 10_001);
+    assert_eq!(exact.count(), 0);
+    let exact = v[..10_000].par_chunks_exact(10);
+    assert!(exact.remainder().is_empty());
+    assert_eq!(exact.count(), 1_000);
+}
+
+#[test]
+fn test_windows_sizes() {
+    let v: Vec<u32> = (0..1_000).collect();
+    for &size in &[1, 2, 999, 1_000, 1_001] {
+        let windows: Vec<&[u32]> = v.par_windows(size).collect();
+        assert_eq!(windows, v.windows(size).collect::<Vec<_>>());
+    }
+    // windows overlap: divisions share elements between both sides
+    let sums: Vec<u32> = v.par_windows(3).rev().map(|w| w.iter().sum()).collect();
+    assert!(sums.into_iter().eq((0..998).rev().map(|i| 3 * i + 3)));
+}
+
+#[test]
+fn test_mutable_chunks_kernels() {
+    let v: Vec<u32> = (0..10_001).collect();
+    let mut doubled = vec![0; v.len()];
+    doubled
+        .par_chunks_mut(64)
+        .zip(v.par_chunks(64))
+        .for_each(|(out, input)| out.iter_mut().zip(input).for_each(|(o, i)| *o = 2 * i));
+    assert!(doubled.iter().zip(&v).all(|(d, e)| *d == 2 * e));
+    // chunks numbered from the end
+    let mut indices = vec![0; 10];
+    indices
+        .par_chunks_mut(3)
+        .rev()
+        .enumerate()
+        .for_each(|(i, c)| c.iter_mut().for_each(|e| *e = i));
+    assert_eq!(indices, [3, 3, 3, 2, 2, 2, 1, 1, 1, 0]);
+}
+
+#[test]
+fn test_chunks_previews() {
+    let v: Vec<u32> = (0..100).collect();
+    let merged: Vec<&[u32]> = v.par_chunks(10).merge(v.par_windows(10)).collect();
+    let mut expected: Vec<&[u32]> = v.chunks(10).chain(v.windows(10)).collect();
+    expected.sort();
+    assert_eq!(merged, expected);
+}
+
+#[test]
+#[should_panic(expected = "chunk size must be non-zero")]
+fn test_zero_sized_chunks() {
+    [1u32, 2, 3].par_chunks(0);
+}
+
+#[test]
+#[should_panic(expected = "window size must be non-zero")]
+fn test_zero_sized_windows() {
+    [1u32, 2, 3].par_windows(0);
+}
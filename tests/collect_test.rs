@@ -0,0 +1,129 @@
+use kvik::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+fn check_collects(size: u32) {
+    let expected: Vec<u32> = (0..size).collect();
+    let v: Vec<u32> = (0..size).into_par_iter().collect();
+    assert_eq!(v, expected);
+    let v: Vec<u32> = expected.par_iter().map(|e| e * 2).collect();
+    assert_eq!(v, expected.iter().map(|e| e * 2).collect::<Vec<_>>());
+    let v: Vec<(u32, u32)> = (0..size)
+        .into_par_iter()
+        .zip(expected.as_slice())
+        .map(|(a, b)| (a, *b))
+        .collect();
+    assert!(v.iter().all(|(a, b)| a == b));
+    let v: Vec<u32> = (0..size).into_par_iter().rev().collect();
+    assert_eq!(v, expected.iter().rev().copied().collect::<Vec<_>>());
+    let v: Vec<u32> = (0..size).into_par_iter().chain(0..size).collect();
+    assert_eq!(
+        v,
+        expected
+            .iter()
+            .chain(&expected)
+            .copied()
+            .collect::<Vec<_>>()
+    );
+    let v: Vec<u32> = (0..size)
+        .into_par_iter()
+        .skip(3)
+        .step_by(7)
+        .take(100)
+        .collect();
+    assert_eq!(
+        v,
+        expected
+            .iter()
+            .skip(3)
+            .step_by(7)
+            .take(100)
+            .copied()
+            .collect::<Vec<_>>()
+    );
+    // non enumerable iterators still go through blocks
+    let v: Vec<u32> = (0..size).into_par_iter().filter(|e| e % 3 == 0).collect();
+    assert_eq!(
+        v,
+        expected
+            .iter()
+            .filter(|e| *e % 3 == 0)
+            .copied()
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_exact_collect() {
+    for &size in &[0, 1, 2, 1_000, 100_000] {
+        check_collects(size);
+    }
+}
+
+#[test]
+fn test_exact_collect_non_copy_elements() {
+    let v: Vec<String> = (0u32..100_000)
+        .into_par_iter()
+        .map(|i| i.to_string())
+        .collect();
+    assert!(v.iter().enumerate().all(|(i, s)| *s == i.to_string()));
+    // zero sized elements never move the writing position
+    let v: Vec<()> = (0u32..100_000).into_par_iter().map(|_| ()).collect();
+    assert_eq!(v.len(), 100_000);
+}
+
+#[test]
+fn test_exact_collect_adaptive() {
+    // blocks of the adaptive scheduler write in the middle of their slots
+    let v: Vec<u64> = (0u64..1_000_000)
+        .into_par_iter()
+        .map(|e| e * 3)
+        .adaptive()
+        .collect();
+    assert!(v.iter().enumerate().all(|(i, &e)| e == 3 * i as u64));
+}
+
+static CREATED: AtomicUsize = AtomicUsize::new(0);
+static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+struct Counted;
+
+impl Counted {
+    fn new() -> Self {
+        CREATED.fetch_add(1, Ordering::SeqCst);
+        Counted
+    }
+}
+
+impl Drop for Counted {
+    fn drop(&mut self) {
+        DROPPED.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn test_exact_collect_panic_drops_written_elements() {
+    let mapped = |i: u32| {
+        if i == 77_777 {
+            panic!("bad element")
+        }
+        Counted::new()
+    };
+    let result = std::panic::catch_unwind(|| {
+        let _v: Vec<Counted> = (0..100_000u32).into_par_iter().map(mapped).collect();
+    });
+    assert!(result.is_err());
+    let result = std::panic::catch_unwind(|| {
+        let _v: Vec<Counted> = (0..100_000u32)
+            .into_par_iter()
+            .map(mapped)
+            .adaptive()
+            .collect();
+    });
+    assert!(result.is_err());
+    // each written element is dropped exactly once, uninitialized slots never are
+    assert!(CREATED.load(Ordering::SeqCst) > 0);
+    assert_eq!(
+        DROPPED.load(Ordering::SeqCst),
+        CREATED.load(Ordering::SeqCst)
+    );
+}
@@ -0,0 +1,164 @@
+use kvik::prelude::*;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::sync::Arc;
+
+#[test]
+fn test_hashbrown_sparse_buckets() {
+    // most buckets are left empty after removals, some blocks yield nothing
+    let mut map: hashbrown::HashMap<u32, u32> = (0..100_000).map(|i| (i, 2 * i)).collect();
+    map.retain(|k, _| k % 1_000 == 0);
+    let mut keys: Vec<u32> = map.par_iter().map(|(k, _)| *k).collect();
+    keys.sort_unstable();
+    assert!(keys.into_iter().eq((0..100).map(|i| 1_000 * i)));
+    map.par_iter_mut().for_each(|(k, v)| *v += k);
+    assert!(map.iter().all(|(k, v)| *v == 3 * k));
+    let mut owned: Vec<(u32, u32)> = map.into_par_iter().collect();
+    owned.sort_unstable();
+    assert!(owned
+        .into_iter()
+        .eq((0..100).map(|i| (1_000 * i, 3_000 * i))));
+    // tables with no or a single element
+    let empty: hashbrown::HashSet<u32> = hashbrown::HashSet::new();
+    assert_eq!(empty.par_iter().count(), 0);
+    assert_eq!(empty.into_par_iter().count(), 0);
+    let single: hashbrown::HashSet<String> = std::iter::once("a".to_string()).collect();
+    assert_eq!(
+        single.par_iter().map(|s| s.as_str()).collect::<String>(),
+        "a"
+    );
+    assert_eq!(single.into_par_iter().collect::<Vec<String>>(), ["a"]);
+}
+
+#[test]
+fn test_hashbrown_owned_drops_remaining_elements() {
+    let token = Arc::new(());
+    let map: hashbrown::HashMap<u32, Arc<()>> = (0..10_000).map(|i| (i, token.clone())).collect();
+    // we stop early: elements not yielded are dropped with the producers
+    assert!(map.into_par_iter().find_first(|(k, _)| *k == 5).is_some());
+    assert_eq!(Arc::strong_count(&token), 1);
+    let set: hashbrown::HashSet<Vec<u32>> = (0..10_000).map(|i| vec![i]).collect();
+    let result = std::panic::catch_unwind(|| {
+        set.into_par_iter().for_each(|v| {
+            if v[0] == 5_000 {
+                panic!("bad element")
+            }
+        })
+    });
+    assert!(result.is_err());
+    let map: hashbrown::HashMap<u32, Arc<()>> = (0..10_000).map(|i| (i, token.clone())).collect();
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        map.into_par_iter().for_each(|(k, _)| {
+            if k == 5_000 {
+                panic!("bad element")
+            }
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!(Arc::strong_count(&token), 1);
+}
+
+#[test]
+fn test_std_hash_tables() {
+    let mut map: HashMap<u32, u32> = (0..10_000).map(|i| (i, i)).collect();
+    map.par_iter_mut().for_each(|(_, v)| *v *= 2);
+    let sum = map
+        .par_iter()
+        .map(|(_, v)| *v as u64)
+        .reduce(|| 0, |a, b| a + b);
+    assert_eq!(sum, 2 * (0..10_000u64).sum::<u64>());
+    assert_eq!(
+        map.into_par_iter().filter(|(k, v)| 2 * k == *v).count(),
+        10_000
+    );
+    let set: HashSet<u32> = (0..10_000).collect();
+    assert_eq!(set.par_iter().max(), Some(&9_999));
+    assert_eq!(set.into_par_iter().min(), Some(0));
+}
+
+#[test]
+fn test_btree_previews() {
+    // zip and skip divide and preview at given indices
+    let tree: BTreeMap<u32, String> = (0..10_000).map(|i| (3 * i, i.to_string())).collect();
+    let v: Vec<u32> = tree
+        .par_iter()
+        .zip(0..10_000u32)
+        .skip(3)
+        .map(|((k, _), i)| k - 3 * i)
+        .collect();
+    assert_eq!(v, vec![0; 9_997]);
+    let v: Vec<u32> = tree.par_iter().rev().take(3).map(|(k, _)| *k).collect();
+    assert_eq!(v, [29_997, 29_994, 29_991]);
+    let set: BTreeSet<u32> = tree.keys().copied().collect();
+    assert_eq!(set.par_iter().find_first(|e| **e > 10), Some(&12));
+    let v: Vec<(usize, u32)> = set
+        .par_iter()
+        .enumerate()
+        .skip(9_998)
+        .map(|(i, e)| (i, *e))
+        .collect();
+    assert_eq!(v, [(9_998, 29_994), (9_999, 29_997)]);
+    // ranges of a single element and empty trees
+    let single: BTreeSet<u32> = std::iter::once(7).collect();
+    assert_eq!(single.par_iter().rev().collect::<Vec<_>>(), [&7]);
+    let empty: BTreeMap<u32, u32> = BTreeMap::new();
+    assert_eq!(empty.par_iter().count(), 0);
+}
+
+#[test]
+fn test_btree_owned_and_mutable() {
+    let mut tree: BTreeMap<String, u32> = (0..10_000).map(|i| (format!("{:05}", i), i)).collect();
+    tree.par_iter_mut().for_each(|(_, v)| *v += 1);
+    let v: Vec<u32> = tree.clone().into_par_iter().map(|(_, v)| v).collect();
+    assert!(v.into_iter().eq(1..10_001));
+    // keys owning allocations are moved, never duplicated
+    let v: Vec<String> = tree
+        .into_par_iter()
+        .rev()
+        .skip(9_997)
+        .map(|(k, _)| k)
+        .collect();
+    assert_eq!(v, ["00002", "00001", "00000"]);
+    let set: BTreeSet<Vec<u32>> = (0..10_000).map(|i| vec![i / 100, i % 100]).collect();
+    let v: Vec<Vec<u32>> = set.into_par_iter().skip(5_000).take(2).collect();
+    assert_eq!(v, [vec![50, 0], vec![50, 1]]);
+    let boxes: BTreeSet<Box<u32>> = (0..10_000).map(Box::new).collect();
+    let v: Vec<Box<u32>> = boxes.into_par_iter().adaptive().collect();
+    assert!(v.into_iter().map(|b| *b).eq(0..10_000));
+    // remaining elements are dropped with the trees
+    let token = Arc::new(());
+    let tree: BTreeMap<u32, Arc<()>> = (0..10_000).map(|i| (i, token.clone())).collect();
+    assert_eq!(tree.into_par_iter().take(10).count(), 10);
+    assert_eq!(Arc::strong_count(&token), 1);
+}
+
+#[test]
+fn test_collections_schedulers() {
+    let tree: BTreeMap<u32, u32> = (0..10_000).map(|i| (i, i)).collect();
+    let hash_map: hashbrown::HashMap<u32, u32> = tree.iter().map(|(k, v)| (*k, *v)).collect();
+    let expected: u64 = (0..10_000u64).sum();
+    let sum = |a: u64, b: u64| a + b;
+    let adaptive = tree
+        .par_iter()
+        .map(|(_, v)| *v as u64)
+        .adaptive()
+        .reduce(|| 0, sum);
+    let rayon = hash_map
+        .par_iter()
+        .map(|(_, v)| *v as u64)
+        .rayon(2)
+        .reduce(|| 0, sum);
+    let depjoin = tree
+        .into_par_iter()
+        .map(|(_, v)| v as u64)
+        .depjoin()
+        .reduce(|| 0, sum);
+    let hashed = hash_map
+        .into_par_iter()
+        .map(|(_, v)| v as u64)
+        .adaptive()
+        .reduce(|| 0, sum);
+    assert_eq!(adaptive, expected);
+    assert_eq!(rayon, expected);
+    assert_eq!(depjoin, expected);
+    assert_eq!(hashed, expected);
+}
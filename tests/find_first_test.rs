@@ -21,13 +21,13 @@ fn test_exists() {
         let input = (0..size).collect::<Vec<_>>();
         tp.install(|| {
             assert!(input
-                .into_par_iter()
+                .par_iter()
                 .find_first(|elem| **elem == 1)
                 .is_some());
         });
         tp.install(|| {
             assert!(input
-                .into_par_iter()
+                .par_iter()
                 .by_blocks((1..).map(|elem| 2_usize.pow(elem)))
                 .find_first(|elem| **elem == 1)
                 .is_some());
@@ -44,7 +44,7 @@ fn test_dne() {
         let input = (0..size).collect::<Vec<_>>();
         tp.install(|| {
             assert!(input
-                .into_par_iter()
+                .par_iter()
                 .find_first(|elem| **elem == 7)
                 .is_none());
         });
@@ -69,7 +69,7 @@ fn test_first() {
             .collect();
         tp.install(|| {
             assert_eq!(
-                v.into_par_iter()
+                v.par_iter()
                     .by_blocks((1..).map(|elem| 2_usize.pow(elem)))
                     .find_first(|elem| elem.first == 0)
                     .unwrap()
@@ -77,7 +77,7 @@ fn test_first() {
                 0
             );
             assert_eq!(
-                v.into_par_iter()
+                v.par_iter()
                     .by_blocks((1..).map(|elem| 2_usize.pow(elem)))
                     .find_first(|elem| elem.first == 1)
                     .unwrap()
@@ -85,7 +85,7 @@ fn test_first() {
                 1
             );
             assert_eq!(
-                v.into_par_iter()
+                v.par_iter()
                     .by_blocks((1..).map(|elem| 2_usize.pow(elem)))
                     .find_first(|elem| elem.first == 2)
                     .unwrap()
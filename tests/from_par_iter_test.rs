@@ -0,0 +1,91 @@
+use kvik::prelude::*;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, LinkedList};
+
+#[test]
+fn test_maps_keep_last_values() {
+    // each key appears in many blocks: merges must keep the last value like `extend`
+    let pairs = || (0u32..100_000).into_par_iter().map(|i| (i % 100, i));
+    let expected: HashMap<u32, u32> = (0u32..100_000).map(|i| (i % 100, i)).collect();
+    let map: HashMap<u32, u32> = pairs().collect();
+    assert_eq!(map, expected);
+    let tree: BTreeMap<u32, u32> = pairs().collect();
+    assert!(tree.iter().eq(expected.iter().collect::<BTreeMap<_, _>>()));
+    // one huge block on the right and a small one on the left
+    let right: Vec<(u32, u32)> = (0u32..100_000).map(|i| (i % 10, i)).collect();
+    let map: HashMap<u32, u32> = vec![(0, 0)].into_par_iter().chain(right).collect();
+    assert_eq!(map[&0], 99_990);
+}
+
+#[test]
+fn test_sets() {
+    let set: HashSet<u32> = (0u32..100_000).into_par_iter().map(|i| i % 1_000).collect();
+    assert_eq!(set, (0..1_000).collect());
+    let tree_set: BTreeSet<u32> = (0u32..100_000)
+        .into_par_iter()
+        .rev()
+        .map(|i| i % 1_000)
+        .collect();
+    assert!(tree_set.iter().copied().eq(0..1_000));
+    let empty: HashSet<u32> = (0u32..0).into_par_iter().collect();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn test_linked_list_order() {
+    let list: LinkedList<u32> = (0u32..100_000)
+        .into_par_iter()
+        .filter(|e| e % 2 == 1)
+        .collect();
+    assert!(list.iter().copied().eq((0..100_000).filter(|e| e % 2 == 1)));
+    // blocks of the caller's scheduler are appended in order
+    let list: LinkedList<u32> = (0u32..100_000)
+        .into_par_iter()
+        .filter(|e| e % 2 == 1)
+        .adaptive()
+        .collect();
+    assert!(list.iter().copied().eq((0..100_000).filter(|e| e % 2 == 1)));
+}
+
+#[test]
+fn test_strings() {
+    let words: Vec<String> = (0..10_000).map(|i| i.to_string()).collect();
+    let s: String = words.par_iter().map(|w| w.as_str()).collect();
+    assert_eq!(s, words.concat());
+    let s: String = words.par_iter().map(|w| w.clone()).collect();
+    assert_eq!(s, words.concat());
+    // multi bytes characters
+    let text: String = (0..10_000)
+        .map(|i| if i % 3 == 0 { 'é' } else { '€' })
+        .collect();
+    let chars: Vec<char> = text.chars().collect();
+    let s: String = chars.par_iter().map(|c| *c).collect();
+    assert_eq!(s, text);
+    let s: String = chars.par_iter().map(|c| *c).depjoin().collect();
+    assert_eq!(s, text);
+    let empty: String = (0u32..0).into_par_iter().map(|_| 'a').collect();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn test_extend_non_empty_collections() {
+    let mut v = vec![7u32];
+    v.par_extend((0u32..100_000).into_par_iter());
+    assert_eq!(v[0], 7);
+    assert!(v[1..].iter().copied().eq(0..100_000));
+    // existing values are overwritten like with `extend`
+    let mut map: HashMap<u32, u32> = vec![(0, 0), (1_000_000, 0)].into_iter().collect();
+    map.par_extend((0u32..100_000).into_par_iter().map(|i| (i % 100, i)));
+    assert_eq!(map.len(), 101);
+    assert_eq!(map[&0], 99_900);
+    assert_eq!(map[&1_000_000], 0);
+    let mut tree: BTreeMap<u32, u32> = vec![(5, 0)].into_iter().collect();
+    tree.par_extend((0u32..1_000).into_par_iter().map(|i| (i % 10, i)));
+    assert_eq!(tree[&5], 995);
+    let mut list: LinkedList<u32> = std::iter::once(7).collect();
+    list.par_extend((0u32..1_000).into_par_iter());
+    assert!(list.iter().copied().eq(std::iter::once(7).chain(0..1_000)));
+    let mut s = String::from(">");
+    s.par_extend(vec!["a", "b", "c"].par_iter().map(|w| *w));
+    s.par_extend(vec!['d', 'é'].par_iter().map(|c| *c));
+    assert_eq!(s, ">abcdé");
+}
@@ -0,0 +1,130 @@
+use kvik::prelude::*;
+use kvik::{Grid2, Grid3};
+
+fn sorted<T: Ord>(mut v: Vec<T>) -> Vec<T> {
+    v.sort_unstable();
+    v
+}
+
+/// All points of a 2-D grid in row-major order.
+fn points(rows: std::ops::Range<usize>, columns: std::ops::Range<usize>) -> Vec<[usize; 2]> {
+    rows.flat_map(|i| columns.clone().map(move |j| [i, j]))
+        .collect()
+}
+
+#[test]
+fn test_degenerate_grids() {
+    // an empty axis empties the whole grid
+    assert_eq!(Grid2::new([0..0, 0..100]).into_par_iter().count(), 0);
+    assert_eq!(Grid3::new([0..5, 3..3, 0..5]).into_par_iter().count(), 0);
+    // single rows and columns only divide along the other axis
+    let row: Vec<[usize; 2]> = Grid2::new([7..8, 0..1_000]).into_par_iter().collect();
+    assert_eq!(row, points(7..8, 0..1_000));
+    let column: Vec<[usize; 2]> = Grid2::new([0..1_000, 5..6])
+        .into_par_iter()
+        .adaptive()
+        .collect();
+    assert_eq!(sorted(column), points(0..1_000, 5..6));
+    assert_eq!(
+        Grid3::new([2..3, 4..5, 6..7])
+            .into_par_iter()
+            .collect::<Vec<_>>(),
+        [[2, 4, 6]]
+    );
+}
+
+#[test]
+fn test_grids_order_and_schedulers() {
+    // offset origins and elongated shapes
+    let grid = || Grid2::new([3..103, 1..201]);
+    let expected = points(3..103, 1..201);
+    assert_eq!(grid().collect::<Vec<_>>(), expected);
+    assert_eq!(sorted(grid().into_par_iter().collect()), expected);
+    assert_eq!(
+        sorted(grid().into_par_iter().bound_depth(3).collect()),
+        expected
+    );
+    assert_eq!(
+        sorted(grid().into_par_iter().force_depth(4).collect()),
+        expected
+    );
+    let count = grid()
+        .into_par_iter()
+        .rayon(2)
+        .map(|_| 1)
+        .reduce(|| 0, |a, b| a + b);
+    assert_eq!(count, 100 * 200);
+    let expected: Vec<[usize; 3]> = (0..3)
+        .flat_map(|p| (0..7).flat_map(move |i| (0..40).map(move |j| [p, i, j])))
+        .collect();
+    let grid = || Grid3::new([0..3, 0..7, 0..40]);
+    assert!(grid().rev().eq(expected.iter().rev().copied()));
+    assert_eq!(sorted(grid().into_par_iter().depjoin().collect()), expected);
+}
+
+#[test]
+fn test_grid_divisions() {
+    // rectangles are cut along their longest axis
+    let (left, right) = Grid2::new([0..4, 0..10]).divide();
+    assert_eq!(
+        left.collect::<Vec<_>>(),
+        Grid2::new([0..4, 0..5]).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        right.collect::<Vec<_>>(),
+        Grid2::new([0..4, 5..10]).collect::<Vec<_>>()
+    );
+    let (left, _) = Grid3::new([0..3, 0..9, 0..4]).divide();
+    assert_eq!(left.count(), 3 * 5 * 4);
+    // divide_at follows the linearized order
+    let all: Vec<[usize; 2]> = Grid2::new([0..3, 0..4]).collect();
+    for index in 0..=12 {
+        let (left, right) = Grid2::new([0..3, 0..4]).divide_at(index);
+        assert_eq!(left.sizes(), (index, Some(index)));
+        assert_eq!(left.collect::<Vec<_>>(), all[..index]);
+        let (middle, right) = right.divide_at(1);
+        assert_eq!(middle.chain(right).collect::<Vec<_>>(), all[index..]);
+    }
+    // linear pieces keep on dividing in linearized order
+    let (_, right) = Grid2::new([0..3, 0..4]).divide_at(3);
+    let (left, right) = right.divide();
+    assert_eq!(left.collect::<Vec<_>>(), all[3..8]);
+    assert_eq!(right.collect::<Vec<_>>(), all[8..]);
+}
+
+#[test]
+fn test_tiles() {
+    for &(width, height, tile_width, tile_height) in &[
+        (1, 1, 3, 3),
+        (10, 7, 3, 2),
+        (64, 64, 8, 8),
+        (33, 100, 5, 17),
+    ] {
+        let mut matrix = vec![0usize; width * height];
+        matrix
+            .par_tiles(width, tile_width, tile_height)
+            .adaptive()
+            .for_each(|mut tile| {
+                let [row, column] = tile.origin();
+                assert!(tile.width() <= tile_width && tile.height() <= tile_height);
+                for (i, r) in tile.rows_mut().enumerate() {
+                    for (j, e) in r.iter_mut().enumerate() {
+                        *e += (row + i) * width + column + j + 1;
+                    }
+                }
+            });
+        assert!(matrix.iter().copied().eq(1..=width * height));
+        let tiles = matrix
+            .par_tiles(width, tile_width, tile_height)
+            .map(|tile| tile.rows().map(|r| r.len()).sum::<usize>())
+            .reduce(|| 0, |a, b| a + b);
+        assert_eq!(tiles, width * height);
+    }
+}
+
+#[test]
+#[should_panic(expected = "slice length must be a multiple of the stride")]
+fn test_tiles_bad_stride() {
+    let mut v = vec![0u8; 10];
+    v.par_tiles(3, 2, 2).for_each(|_| ());
+}
@@ -0,0 +1,106 @@
+use kvik::prelude::*;
+use kvik::{adaptive_kway_merge, merge_all};
+#[cfg(feature = "steal-simulator")]
+use kvik::{Executor, StealSimulator};
+#[cfg(feature = "steal-simulator")]
+use rand::prelude::*;
+
+/// (key, run, position) triples only compared on their keys:
+/// stability must come from the merge.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct Keyed((u32, usize, usize));
+impl PartialOrd for Keyed {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for Keyed {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.0).0.cmp(&(other.0).0)
+    }
+}
+
+/// Turn sorted runs of keys into runs of tagged elements.
+fn tagged(keys: Vec<Vec<u32>>) -> Vec<Vec<Keyed>> {
+    keys.into_iter()
+        .enumerate()
+        .map(|(run, keys)| {
+            keys.into_iter()
+                .enumerate()
+                .map(|(position, key)| Keyed((key, run, position)))
+                .collect()
+        })
+        .collect()
+}
+
+/// Merge with both interfaces and check the results are stably merged.
+fn check(keys: Vec<Vec<u32>>) {
+    let runs = tagged(keys);
+    let slices: Vec<&[Keyed]> = runs.iter().map(|r| r.as_slice()).collect();
+    let total = slices.iter().map(|s| s.len()).sum();
+    let mut output = vec![Keyed((0, 0, 0)); total];
+    adaptive_kway_merge(&slices, &mut output);
+    let mut expected: Vec<(u32, usize, usize)> = runs.iter().flatten().map(|k| k.0).collect();
+    expected.sort();
+    assert!(output.iter().map(|k| k.0).eq(expected.iter().copied()));
+    let merged: Vec<Keyed> = merge_all(runs.iter().map(|r| r.par_iter().map(|k| *k))).collect();
+    assert!(merged.iter().map(|k| k.0).eq(expected.iter().copied()));
+}
+
+#[test]
+fn test_empty_runs() {
+    check(vec![]);
+    check(vec![vec![]]);
+    check(vec![vec![], vec![], vec![]]);
+    // empty runs interleaved with full ones
+    check(
+        (0..50)
+            .map(|r| {
+                if r % 3 == 0 {
+                    (0..1_000).collect()
+                } else {
+                    vec![]
+                }
+            })
+            .collect(),
+    );
+}
+
+#[test]
+fn test_equal_keys() {
+    // every split falls inside a block of equal keys spanning all runs
+    check(vec![vec![5; 10_000]; 7]);
+    check((0..100).map(|r| vec![1; r * 50]).collect());
+}
+
+#[test]
+fn test_disjoint_runs() {
+    // runs given in decreasing order of values
+    check(
+        (0..20)
+            .map(|r| ((19 - r) * 1_000..(20 - r) * 1_000).collect())
+            .collect(),
+    );
+    // one huge run among many single elements
+    let mut keys: Vec<Vec<u32>> = (0..300).map(|r| vec![r * 300]).collect();
+    keys.push((0..100_000).collect());
+    check(keys);
+}
+
+#[test]
+#[cfg(feature = "steal-simulator")]
+fn test_kway_merge_simulated_steals() {
+    for seed in 0..500 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let keys: Vec<Vec<u32>> = (0..1 + seed as usize % 20)
+            .map(|_| {
+                let len = rng.next_u32() as usize % 501;
+                let mut keys: Vec<u32> = (0..len).map(|_| rng.next_u32() % 100).collect();
+                keys.sort();
+                keys
+            })
+            .collect();
+        let simulator = StealSimulator::new(seed);
+        simulator.run(|| check(keys));
+    }
+}
@@ -0,0 +1,127 @@
+use kvik::prelude::*;
+use kvik::{matmul_blocks, par_matmul, par_transpose, transpose_blocks, Kernel};
+
+fn matrix(rows: usize, columns: usize) -> Vec<u64> {
+    (0..rows * columns)
+        .map(|i| (i as u64 * 7_919) % 100)
+        .collect()
+}
+
+fn naive_transpose(input: &[u64], rows: usize, columns: usize) -> Vec<u64> {
+    (0..columns)
+        .flat_map(|j| (0..rows).map(move |i| input[i * columns + j]))
+        .collect()
+}
+
+fn naive_matmul(a: &[u64], b: &[u64], n: usize, k: usize, m: usize) -> Vec<u64> {
+    (0..n)
+        .flat_map(|i| (0..m).map(move |j| (0..k).map(|l| a[i * k + l] * b[l * m + j]).sum()))
+        .collect()
+}
+
+fn check_matmul(n: usize, k: usize, m: usize) {
+    let (a, b) = (matrix(n, k), matrix(k, m));
+    // garbage in the output must be overwritten
+    let mut c = vec![7; n * m];
+    par_matmul(&a, &b, &mut c, n, k, m);
+    assert_eq!(c, naive_matmul(&a, &b, n, k, m));
+}
+
+#[test]
+fn test_degenerate_shapes() {
+    // empty matrices and an empty inner dimension which still clears the output
+    check_matmul(0, 0, 0);
+    check_matmul(0, 5, 3);
+    check_matmul(4, 0, 6);
+    // vectors: outer and inner products
+    check_matmul(300, 1, 200);
+    check_matmul(1, 5_000, 1);
+    check_matmul(1, 1, 1);
+    for &(rows, columns) in &[(0, 7), (1, 10_000), (10_000, 1), (1, 1)] {
+        let input = matrix(rows, columns);
+        let mut output = vec![0; rows * columns];
+        par_transpose(&input, &mut output, rows, columns);
+        assert_eq!(output, naive_transpose(&input, rows, columns));
+    }
+}
+
+#[test]
+fn test_shapes_around_base_case() {
+    // blocks of 32 x 32 cells and 32 x 32 x 32 products are not divided
+    for &(n, k, m) in &[
+        (32, 32, 32),
+        (33, 32, 31),
+        (31, 33, 32),
+        (64, 1, 64),
+        (97, 130, 65),
+    ] {
+        check_matmul(n, k, m);
+        let input = matrix(n, m);
+        let mut output = vec![0; n * m];
+        par_transpose(&input, &mut output, n, m);
+        assert_eq!(output, naive_transpose(&input, n, m));
+    }
+}
+
+#[test]
+fn test_schedulers_and_policies() {
+    let (n, k, m) = (129, 257, 33);
+    let (a, b) = (matrix(n, k), matrix(k, m));
+    let expected = naive_matmul(&a, &b, n, k, m);
+    // adaptive blocks are cut off the rows of the kernels
+    let mut c = vec![7; n * m];
+    matmul_blocks(&a, &b, &mut c, n, k, m)
+        .adaptive()
+        .for_each(|block| block.run());
+    assert_eq!(c, expected);
+    let mut c = vec![7; n * m];
+    matmul_blocks(&a, &b, &mut c, n, k, m)
+        .bound_depth(2)
+        .depjoin()
+        .for_each(|block| block.run());
+    assert_eq!(c, expected);
+    let input = matrix(500, 1_000);
+    let mut output = vec![0; 500_000];
+    transpose_blocks(&input, &mut output, 500, 1_000)
+        .join_context_policy(3)
+        .rayon(2)
+        .for_each(|block| block.run());
+    assert_eq!(output, naive_transpose(&input, 500, 1_000));
+}
+
+#[test]
+fn test_policies_control_blocks() {
+    let input = vec![0u8; 256 * 256];
+    let mut output = vec![0u8; 256 * 256];
+    // without any policy blocks are divided down to the base case
+    let blocks = transpose_blocks(&input, &mut output, 256, 256)
+        .map(|block| block.cells())
+        .fold(Vec::new, |mut v, c| {
+            v.push(c);
+            v
+        })
+        .reduce(Vec::new, |mut l, mut r| {
+            l.append(&mut r);
+            l
+        });
+    assert!(blocks.iter().all(|&c| c <= 32 * 32));
+    assert_eq!(blocks.iter().sum::<usize>(), 256 * 256);
+    let blocks = transpose_blocks(&input, &mut output, 256, 256)
+        .bound_depth(2)
+        .map(|_| 1)
+        .reduce(|| 0, |a, b| a + b);
+    assert_eq!(blocks, 4);
+    // the number of cells bounds the number of blocks so sizes can be limited
+    let smallest = transpose_blocks(&input, &mut output, 256, 256)
+        .size_limit(10_000)
+        .map(|block| block.cells())
+        .reduce(|| usize::MAX, |a, b| a.min(b));
+    assert_eq!(smallest, 128 * 64);
+}
+
+#[test]
+#[should_panic(expected = "invalid matrix dimensions")]
+fn test_bad_dimensions() {
+    let mut c = vec![0u32; 4];
+    par_matmul(&[1, 2, 3], &[1, 2, 3, 4], &mut c, 2, 2, 2);
+}
@@ -1,7 +1,7 @@
+use kvik::adaptive_slice_merge;
 use rand::prelude::*;
 use rand::random;
 use rayon::prelude::*;
-use kvik::adaptive_slice_merge;
 
 #[derive(Copy, Clone, Debug)]
 struct OpaqueTuple {
@@ -139,3 +139,28 @@ fn test_stability_random_equal() {
         });
     }
 }
+
+#[test]
+#[cfg(feature = "steal-simulator")]
+fn test_merge_simulated_steals() {
+    use kvik::{Executor, StealSimulator};
+    for seed in 0..2_000 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let size = 2_000 + (seed as usize * 7) % 3_000;
+        let mut input = (0..size as u64 / 3).cycle().take(size).collect::<Vec<_>>();
+        input.shuffle(&mut rng);
+        let mid = (seed as usize * 13) % size;
+        let (left, right) = input.split_at_mut(mid);
+        left.sort();
+        right.sort();
+        let mut output = vec![0; size];
+        let simulator = StealSimulator::new(seed);
+        simulator.run(|| adaptive_slice_merge(left, right, output.as_mut_slice()));
+        assert!(
+            output.windows(2).all(|w| w[0] <= w[1]),
+            "merge failed for seed {} with schedule {:?}",
+            seed,
+            simulator.schedule()
+        );
+    }
+}
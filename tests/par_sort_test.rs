@@ -0,0 +1,122 @@
+use kvik::prelude::*;
+#[cfg(feature = "steal-simulator")]
+use kvik::{Executor, StealSimulator};
+use rand::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Elements remembering their initial position to check stability.
+#[derive(Debug, Clone)]
+struct Record {
+    key: u32,
+    name: String,
+}
+
+fn records<I: Iterator<Item = u32>>(keys: I) -> Vec<Record> {
+    keys.enumerate()
+        .map(|(i, key)| Record {
+            key,
+            name: format!("record {}", i),
+        })
+        .collect()
+}
+
+fn is_stably_sorted(v: &[Record]) -> bool {
+    let index = |r: &Record| r.name[7..].parse::<usize>().unwrap();
+    v.windows(2)
+        .all(|w| w[0].key < w[1].key || (w[0].key == w[1].key && index(&w[0]) < index(&w[1])))
+}
+
+#[test]
+fn test_sorted_inputs() {
+    // sorted and reversed inputs and a single repeated key
+    let mut v = records(0..100_000);
+    v.par_sort_by_key(|r| r.key);
+    assert!(is_stably_sorted(&v));
+    let mut v = records((0..100_000).rev());
+    v.par_sort_by_key(|r| r.key);
+    assert!(is_stably_sorted(&v));
+    let mut v = records(std::iter::repeat(7).take(100_000));
+    v.par_sort_by_key(|r| r.key);
+    assert!(is_stably_sorted(&v));
+    // alternating runs
+    let mut v = records((0..100_000).map(|i| if i % 2 == 0 { i } else { 100_000 - i }));
+    v.par_sort_by_key(|r| r.key);
+    assert!(is_stably_sorted(&v));
+}
+
+#[test]
+fn test_tiny_and_zero_sized() {
+    for size in 0..10 {
+        let mut v = records((0..size).rev().map(|i| i % 3));
+        v.par_sort_by_key(|r| r.key);
+        assert!(is_stably_sorted(&v));
+        let mut u: Vec<u32> = (0..size).rev().collect();
+        u.par_sort_unstable_by(|a, b| a.cmp(b));
+        assert!(u.into_iter().eq(0..size));
+    }
+    let mut v = vec![(); 10_000];
+    v.par_sort_by(|a, b| a.cmp(b));
+    assert_eq!(v.len(), 10_000);
+}
+
+#[test]
+fn test_comparators() {
+    // many ties on the length: the order of the ties is kept
+    let mut v: Vec<String> = (0..100_000).rev().map(|i| i.to_string()).collect();
+    let mut expected = v.clone();
+    expected.sort_by_key(|s| s.len());
+    v.par_sort_by(|a, b| a.len().cmp(&b.len()));
+    assert_eq!(v, expected);
+    v.par_sort_unstable_by(|a, b| b.cmp(a));
+    expected.sort_unstable_by(|a, b| b.cmp(a));
+    assert_eq!(v, expected);
+}
+
+#[test]
+fn test_sort_panicking_comparator() {
+    struct Counted(u32, Arc<AtomicUsize>);
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            self.1.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+    for panic_after in &[10, 5_000, 20_000, 60_000] {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let comparisons = AtomicUsize::new(0);
+        let mut rng = StdRng::seed_from_u64(*panic_after as u64);
+        let mut v: Vec<Counted> = (0..10_000)
+            .map(|_| Counted(rng.next_u32(), drops.clone()))
+            .collect();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            v.par_sort_by(|a, b| {
+                if comparisons.fetch_add(1, Ordering::SeqCst) == *panic_after {
+                    panic!("comparator failed")
+                }
+                a.0.cmp(&b.0)
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(drops.load(Ordering::SeqCst), 0);
+        drop(v);
+        assert_eq!(drops.load(Ordering::SeqCst), 10_000);
+    }
+}
+
+#[test]
+#[cfg(feature = "steal-simulator")]
+fn test_sort_simulated_steals() {
+    for seed in 0..300 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let size = 1_000 + (seed as usize * 17) % 5_000;
+        let mut v = records((0..size).map(|_| rng.next_u32() % 100));
+        let simulator = StealSimulator::new(seed);
+        simulator.run(|| v.par_sort_by_key(|r| r.key));
+        assert!(
+            is_stably_sorted(&v),
+            "sort failed for seed {} with schedule {:?}",
+            seed,
+            simulator.schedule()
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use kvik::par_partition_in_place;
+use kvik::prelude::*;
+
+#[test]
+fn test_partition_in_place() {
+    // non Copy elements: nothing may be duplicated or lost
+    let v: Vec<String> = (0..100_000).map(|i| (i % 1_000).to_string()).collect();
+    let (selected, rejected): (Vec<String>, Vec<String>) =
+        v.iter().cloned().partition(|s| s.ends_with('7'));
+    let mut partitioned = v.clone();
+    let size = par_partition_in_place(&mut partitioned, |s| s.ends_with('7'));
+    assert_eq!(partitioned[..size], selected[..]);
+    assert_eq!(partitioned[size..], rejected[..]);
+    // degenerate predicates and sizes
+    for size in &[0, 1, 2, 10_000] {
+        let original: Vec<u32> = (0..*size).collect();
+        let mut v = original.clone();
+        assert_eq!(par_partition_in_place(&mut v, |_| true), original.len());
+        assert_eq!(v, original);
+        assert_eq!(par_partition_in_place(&mut v, |_| false), 0);
+        assert_eq!(v, original);
+    }
+}
+
+#[test]
+fn test_partition_into() {
+    let (evens, odds): (Vec<String>, Vec<String>) = (0u32..100_000)
+        .into_par_iter()
+        .map(|i| i.to_string())
+        .partition_into(|s| s.ends_with(|c: char| c.to_digit(10).unwrap() % 2 == 0));
+    assert_eq!(evens.len(), 50_000);
+    assert!(evens
+        .iter()
+        .enumerate()
+        .all(|(i, s)| *s == (2 * i).to_string()));
+    assert!(odds
+        .iter()
+        .enumerate()
+        .all(|(i, s)| *s == (2 * i + 1).to_string()));
+    // blocks of the caller's scheduler keep their order
+    let (small, large): (Vec<u32>, Vec<u32>) = (0u32..100_000)
+        .into_par_iter()
+        .adaptive()
+        .partition_into(|e| *e % 1_000 < 10);
+    assert!(small
+        .into_iter()
+        .eq((0..100_000).filter(|e| e % 1_000 < 10)));
+    assert!(large
+        .into_iter()
+        .eq((0..100_000).filter(|e| e % 1_000 >= 10)));
+    let (selected, rejected): (Vec<u32>, Vec<u32>) =
+        (0u32..0).into_par_iter().partition_into(|_| true);
+    assert!(selected.is_empty() && rejected.is_empty());
+}
+
+#[test]
+fn test_collect_into_slice() {
+    // output values are overwritten (and dropped), the tail is left untouched
+    let v: Vec<u32> = (0..100_000).collect();
+    let mut output = vec![String::from("untouched"); 100_000];
+    let size = v
+        .par_iter()
+        .map(|e| e.to_string())
+        .filter(|s| s.starts_with('9'))
+        .collect_into_slice(&mut output);
+    let expected: Vec<String> = v
+        .iter()
+        .map(|e| e.to_string())
+        .filter(|s| s.starts_with('9'))
+        .collect();
+    assert_eq!(output[..size], expected[..]);
+    assert!(output[size..].iter().all(|s| s == "untouched"));
+    // very few selected elements, spread over the blocks
+    let mut output = vec![0u64; 10];
+    let size = (0u64..1_000_000)
+        .into_par_iter()
+        .filter(|e| e % 100_000 == 99_999)
+        .collect_into_slice(&mut output);
+    assert_eq!(size, 10);
+    assert!(output
+        .iter()
+        .enumerate()
+        .all(|(i, &e)| e == 100_000 * i as u64 + 99_999));
+    // nothing to divide
+    let mut output = vec![0u32; 1];
+    assert_eq!(
+        (0u32..1)
+            .into_par_iter()
+            .filter(|_| true)
+            .collect_into_slice(&mut output),
+        1
+    );
+    assert_eq!(
+        (0u32..0)
+            .into_par_iter()
+            .filter(|_| true)
+            .collect_into_slice(&mut output),
+        0
+    );
+}
+
+#[test]
+fn test_partition_panicking_predicate() {
+    let original: Vec<String> = (0..10_000).map(|i| i.to_string()).collect();
+    let mut v = original.clone();
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        par_partition_in_place(&mut v, |s| {
+            if s == "5000" {
+                panic!("bad element")
+            }
+            s.len() % 2 == 0
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!(v, original);
+}
+
+#[test]
+#[should_panic(expected = "output slice is too small")]
+fn test_collect_into_small_slice() {
+    let mut output = vec![0u32; 10];
+    (0u32..100)
+        .into_par_iter()
+        .filter(|e| e % 2 == 0)
+        .collect_into_slice(&mut output);
+}
@@ -0,0 +1,85 @@
+use kvik::prelude::*;
+use kvik::utils::windowed::windowed;
+use kvik::{par_find_all, par_replace_in_place, par_replace_str_in_place};
+
+fn sequential_find_all(haystack: &[u8], needle: &[u8]) -> Vec<usize> {
+    haystack
+        .windows(needle.len())
+        .enumerate()
+        .filter(|(_, w)| *w == needle)
+        .map(|(i, _)| i)
+        .collect()
+}
+
+fn check(haystack: &[u8], needle: &[u8]) {
+    assert_eq!(
+        par_find_all(haystack, needle),
+        sequential_find_all(haystack, needle)
+    );
+    // compare replacements with the standard library on texts
+    let text = String::from_utf8(haystack.to_vec()).unwrap();
+    let needle = std::str::from_utf8(needle).unwrap();
+    let replacement: String = needle.chars().map(|_| '_').collect();
+    let mut replaced = text.clone();
+    let count = par_replace_str_in_place(&mut replaced, needle, &replacement);
+    assert_eq!(replaced, text.replace(needle, &replacement));
+    assert_eq!(count, text.matches(needle).count());
+}
+
+#[test]
+fn test_overlapping_matches() {
+    // every position matches: replacements must not overlap
+    let haystack = vec![b'a'; 10_000];
+    for needle in &[&b"a"[..], b"aa", b"aaa", b"aaaaaaa"] {
+        check(&haystack, needle);
+    }
+    // self overlapping needles on periodic texts
+    let haystack = b"aba".repeat(5_000);
+    check(&haystack, b"aba");
+    check(&haystack, b"abaab");
+    let haystack = b"aab".repeat(5_000);
+    check(&haystack, b"aabaab");
+}
+
+#[test]
+fn test_matches_positions() {
+    // matches at both ends and straddling every division point
+    let mut haystack = b"xyz".to_vec();
+    haystack.extend(std::iter::repeat(b'-').take(9_994));
+    haystack.extend_from_slice(b"xyz");
+    check(&haystack, b"xyz");
+    let haystack = b"-xy".repeat(10_000);
+    check(&haystack, b"y-x");
+    // needles as long as the haystack, longer, or never found
+    check(b"abc", b"abc");
+    check(b"ab", b"abc");
+    check(b"", b"a");
+    check(&b"ab".repeat(10_000), b"ba ba");
+}
+
+#[test]
+fn test_replace_non_bytes() {
+    let mut v: Vec<u32> = (0..10_000).map(|i| i % 10).collect();
+    assert_eq!(par_replace_in_place(&mut v, &[8, 9, 0], &[1, 1, 1]), 999);
+    assert_eq!(v[7..11], [7, 1, 1, 1]);
+    assert_eq!(v[9_998..], [8, 9]);
+}
+
+#[test]
+fn test_windowed_pieces() {
+    let v: Vec<usize> = (0..10_000).collect();
+    let owned = windowed(&v, 3)
+        .wrap_iter()
+        .rayon(2)
+        .map(|piece| {
+            let window = piece.window();
+            assert_eq!(window[0], piece.owned().start);
+            assert_eq!(
+                window.len(),
+                (piece.owned().len() + 3).min(10_000 - window[0])
+            );
+            piece.owned().len()
+        })
+        .reduce(|| 0, |a, b| a + b);
+    assert_eq!(owned, 10_000);
+}
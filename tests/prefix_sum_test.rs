@@ -0,0 +1,120 @@
+use kvik::par_prefix_sum_in_place;
+use kvik::prelude::*;
+
+#[test]
+fn test_prefix_sum() {
+    let tp = rayon::ThreadPoolBuilder::new()
+        .num_threads(4)
+        .build()
+        .expect("Thread pool build failed");
+    for size in (0..10)
+        .chain(100..110)
+        .chain(10_000..10_010)
+        .chain(std::iter::once(1_000_000))
+    {
+        let mut input: Vec<u64> = (0..size).map(|i| i % 7).collect();
+        let expected: Vec<u64> = input
+            .iter()
+            .scan(0, |acc, e| {
+                *acc += e;
+                Some(*acc)
+            })
+            .collect();
+        tp.install(|| {
+            par_prefix_sum_in_place(&mut input, |a, b| a + b);
+        });
+        assert_eq!(input, expected);
+    }
+}
+
+#[test]
+fn test_prefix_non_commutative() {
+    let tp = rayon::ThreadPoolBuilder::new()
+        .num_threads(4)
+        .build()
+        .expect("Thread pool build failed");
+    let mut input: Vec<String> = (0..10_000).map(|i| (i % 10).to_string()).collect();
+    tp.install(|| {
+        par_prefix_sum_in_place(&mut input, |a, b| a.clone() + b);
+    });
+    let last: String = (0..10_000).map(|i| (i % 10).to_string()).collect();
+    assert_eq!(input.last(), Some(&last));
+    assert!(input.windows(2).all(|w| w[1].starts_with(&w[0])));
+}
+
+#[test]
+fn test_prefix_fixes_each_element_once() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    let tp = rayon::ThreadPoolBuilder::new()
+        .num_threads(4)
+        .build()
+        .expect("Thread pool build failed");
+    let calls = AtomicUsize::new(0);
+    let mut input = vec![1u64; 1_000_000];
+    tp.install(|| {
+        par_prefix_sum_in_place(&mut input, |a, b| {
+            calls.fetch_add(1, Ordering::Relaxed);
+            a + b
+        })
+    });
+    assert!(input.iter().copied().eq(1..=1_000_000));
+    // one call per element during the scan, at most one more when fixing stolen parts
+    assert!(calls.load(Ordering::Relaxed) < 2 * input.len());
+}
+
+#[test]
+fn test_scans() {
+    let tp = rayon::ThreadPoolBuilder::new()
+        .num_threads(4)
+        .build()
+        .expect("Thread pool build failed");
+    tp.install(|| {
+        let inclusive: Vec<u64> = (0u64..100_000)
+            .into_par_iter()
+            .scan(|| 0, |a, b| a + b)
+            .collect();
+        let exclusive: Vec<u64> = (0u64..100_000)
+            .into_par_iter()
+            .exclusive_scan(|| 0, |a, b| a + b)
+            .collect();
+        assert_eq!(exclusive[0], 0);
+        assert!(inclusive
+            .iter()
+            .zip(exclusive.iter())
+            .enumerate()
+            .all(|(i, (inc, exc))| inc - exc == i as u64));
+        assert_eq!(inclusive.last(), Some(&(99_999 * 50_000)));
+    });
+}
+
+#[test]
+fn test_scan_adaptor() {
+    // the identity is not necessarily neutral
+    let exclusive: Vec<u32> = (1u32..5)
+        .into_par_iter()
+        .exclusive_scan(|| 10, |a, b| a + b)
+        .collect();
+    assert_eq!(exclusive, vec![10, 11, 13, 16]);
+    let empty: Vec<u32> = (0u32..0)
+        .into_par_iter()
+        .exclusive_scan(|| 10, |a, b| a + b)
+        .collect();
+    assert!(empty.is_empty());
+    // prefixes can be zipped, reversed and consumed by any other adaptor
+    let words: Vec<String> = (0..1000).map(|i| (i % 10).to_string()).collect();
+    let offsets = words
+        .par_iter()
+        .map(|w| w.len())
+        .exclusive_scan(|| 0, |a, b| a + b);
+    assert!(offsets
+        .zip(0..1000usize)
+        .rev()
+        .all(|(offset, i)| offset == i));
+    let last = words
+        .into_par_iter()
+        .scan(String::new, |a, b| a.clone() + b)
+        .reduce_with(|_, b| b)
+        .unwrap();
+    assert_eq!(last.len(), 1000);
+    assert!(last.starts_with("0123456789012"));
+}
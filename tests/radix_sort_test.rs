@@ -0,0 +1,117 @@
+use kvik::{par_radix_sort, par_radix_sort_by_key};
+#[cfg(feature = "steal-simulator")]
+use kvik::{Executor, StealSimulator};
+use rand::prelude::*;
+
+fn is_stably_sorted<K: PartialOrd>(v: &[(K, usize)]) -> bool {
+    v.windows(2)
+        .all(|w| w[0].0 < w[1].0 || (w[0].0 == w[1].0 && w[0].1 < w[1].1))
+}
+
+#[test]
+fn test_radix_sort_integers() {
+    let mut rng = StdRng::seed_from_u64(42);
+    for size in (0..10).chain(1_000..1_010).chain(std::iter::once(100_000)) {
+        let mut v: Vec<u64> = (0..size).map(|_| rng.next_u64()).collect();
+        let mut expected = v.clone();
+        expected.sort();
+        par_radix_sort(&mut v);
+        assert_eq!(v, expected);
+    }
+}
+
+#[test]
+fn test_radix_sort_skipped_passes() {
+    // all digits constant: nothing moves
+    let mut v = vec![0x0101_0101u32; 10_000];
+    par_radix_sort(&mut v);
+    assert!(v.iter().all(|&e| e == 0x0101_0101));
+    // only the last byte varies: one single pass
+    let mut v: Vec<u64> = (0..10_000u64).rev().map(|i| (i % 256) << 56).collect();
+    let mut expected = v.clone();
+    expected.sort();
+    par_radix_sort(&mut v);
+    assert_eq!(v, expected);
+    // only the first byte varies
+    let mut v: Vec<u64> = (0..10_000u64).rev().map(|i| i % 256 + (7 << 40)).collect();
+    let mut expected = v.clone();
+    expected.sort();
+    par_radix_sort(&mut v);
+    assert_eq!(v, expected);
+}
+
+#[test]
+fn test_radix_sort_signed_extremes() {
+    let mut v: Vec<i64> = vec![0, -1, 1, i64::MAX, i64::MIN, i64::MIN + 1, i64::MAX - 1]
+        .into_iter()
+        .cycle()
+        .take(5_000)
+        .collect();
+    let mut expected = v.clone();
+    expected.sort();
+    par_radix_sort(&mut v);
+    assert_eq!(v, expected);
+    let mut v: Vec<i8> = (0..5_000).map(|i| (i * 7919) as i8).collect();
+    let mut expected = v.clone();
+    expected.sort();
+    par_radix_sort(&mut v);
+    assert_eq!(v, expected);
+}
+
+#[test]
+fn test_radix_sort_floats() {
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut v: Vec<f32> = (0..50_000)
+        .map(|_| (rng.next_u32() as f32 - 2_147_483_648.0) / 1000.0)
+        .chain(vec![0.0, -0.0, std::f32::INFINITY, std::f32::NEG_INFINITY])
+        .collect();
+    let mut expected = v.clone();
+    expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    par_radix_sort(&mut v);
+    assert_eq!(v, expected);
+    assert_eq!(v[0], std::f32::NEG_INFINITY);
+    assert_eq!(v[v.len() - 1], std::f32::INFINITY);
+    // total order: -0.0 comes first, nans go to the ends depending on their sign
+    let mut v = vec![0.0f64, std::f64::NAN, -0.0, 1.0, -std::f64::NAN, -1.0];
+    par_radix_sort(&mut v);
+    assert!(v[0].is_nan() && v[0].is_sign_negative());
+    assert_eq!(v[1], -1.0);
+    assert!(v[2] == 0.0 && v[2].is_sign_negative());
+    assert!(v[3] == 0.0 && v[3].is_sign_positive());
+    assert_eq!(v[4], 1.0);
+    assert!(v[5].is_nan() && v[5].is_sign_positive());
+}
+
+#[test]
+fn test_radix_sort_by_key_stability() {
+    // few distinct keys: long runs of equal keys spread over all blocks
+    for size in &[1, 2, 255, 256, 257, 100_000] {
+        let mut v: Vec<(u16, usize)> = (0..*size).map(|i| ((i * 7919 % 5) as u16, i)).collect();
+        par_radix_sort_by_key(&mut v, |e| e.0);
+        assert!(is_stably_sorted(&v));
+    }
+    let mut v: Vec<(i32, usize)> = (0..100_000)
+        .map(|i| ((i as i32 * 7919) % 1_000 - 500, i))
+        .collect();
+    par_radix_sort_by_key(&mut v, |e| e.0);
+    assert!(is_stably_sorted(&v));
+}
+
+#[test]
+#[cfg(feature = "steal-simulator")]
+fn test_radix_sort_by_key_simulated_steals() {
+    for seed in 0..200 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut v: Vec<(u16, usize)> = (0..1_000 + (seed as usize * 37) % 5_000)
+            .map(|i| (rng.next_u32() as u16, i))
+            .collect();
+        let simulator = StealSimulator::new(seed);
+        simulator.run(|| par_radix_sort_by_key(&mut v, |e| e.0));
+        assert!(
+            is_stably_sorted(&v),
+            "sort failed for seed {} with schedule {:?}",
+            seed,
+            simulator.schedule()
+        );
+    }
+}
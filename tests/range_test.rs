@@ -0,0 +1,165 @@
+use kvik::par_range_step;
+use kvik::prelude::*;
+
+#[test]
+fn test_full_width_ranges() {
+    // more than usize::MAX integers: not enumerable but reversible
+    assert_eq!(
+        (0..=u64::MAX).into_par_iter().rev().find_first(|_| true),
+        Some(u64::MAX)
+    );
+    assert_eq!(
+        (0..=u64::MAX)
+            .into_par_iter()
+            .rev()
+            .find_first(|e| e % 1_000 == 0),
+        Some(u64::MAX - u64::MAX % 1_000)
+    );
+    assert_eq!(
+        (i128::MIN..=i128::MAX)
+            .into_par_iter()
+            .rev()
+            .find_first(|e| e % 7 == 0),
+        Some(i128::MAX - i128::MAX % 7)
+    );
+    assert_eq!(
+        (i128::MIN..=i128::MAX).into_par_iter().find_first(|_| true),
+        Some(i128::MIN)
+    );
+    let v: Vec<u128> = (0..=u128::MAX)
+        .into_par_iter()
+        .rev()
+        .filter(|e| *e > u128::MAX - 3)
+        .find_first(|_| true)
+        .into_iter()
+        .collect();
+    assert_eq!(v, [u128::MAX]);
+    assert_eq!((0..=u64::MAX).sizes(), (usize::MAX, None));
+    assert_eq!((i128::MIN..=i128::MAX).sizes(), (usize::MAX, None));
+}
+
+#[test]
+fn test_widest_enumerable_ranges() {
+    // exactly usize::MAX integers
+    let v: Vec<(usize, u64)> = (0..u64::MAX)
+        .into_par_iter()
+        .rev()
+        .enumerate()
+        .take(3)
+        .collect();
+    assert_eq!(v, [(0, u64::MAX - 1), (1, u64::MAX - 2), (2, u64::MAX - 3)]);
+    let v: Vec<(i64, u64)> = (i64::MIN..i64::MAX)
+        .into_par_iter()
+        .zip(0..u64::MAX)
+        .skip(usize::MAX - 2)
+        .collect();
+    assert_eq!(
+        v,
+        [(i64::MAX - 2, u64::MAX - 2), (i64::MAX - 1, u64::MAX - 1)]
+    );
+    let v: Vec<(usize, isize)> = (isize::MIN..isize::MAX)
+        .into_par_iter()
+        .enumerate()
+        .rev()
+        .take(2)
+        .collect();
+    assert_eq!(
+        v,
+        [
+            (usize::MAX - 1, isize::MAX - 1),
+            (usize::MAX - 2, isize::MAX - 2)
+        ]
+    );
+    assert_eq!((0..usize::MAX).sizes(), (usize::MAX, Some(usize::MAX)));
+    // inclusive ranges of narrower types stay enumerable
+    let v: Vec<(usize, u32)> = (0..=u32::MAX)
+        .into_par_iter()
+        .enumerate()
+        .skip(u32::MAX as usize)
+        .collect();
+    assert_eq!(v, [(u32::MAX as usize, u32::MAX)]);
+    let v: Vec<(i8, u8)> = (i8::MIN..=i8::MAX)
+        .into_par_iter()
+        .zip(0..=u8::MAX)
+        .rev()
+        .take(2)
+        .collect();
+    assert_eq!(v, [(i8::MAX, u8::MAX), (i8::MAX - 1, u8::MAX - 1)]);
+}
+
+#[test]
+fn test_extreme_divisions() {
+    let (left, right) = (0..=u64::MAX).divide();
+    assert_eq!(
+        (left, right),
+        (0..=u64::MAX / 2, u64::MAX / 2 + 1..=u64::MAX)
+    );
+    let (left, right) = (i128::MIN..=i128::MAX).divide_at(usize::MAX);
+    assert_eq!(left, i128::MIN..=i128::MIN + usize::MAX as i128 - 1);
+    assert_eq!(right, i128::MIN + usize::MAX as i128..=i128::MAX);
+    let (left, right) = (i64::MIN..i64::MAX).divide();
+    assert_eq!((left, right), (i64::MIN..0, 0..i64::MAX));
+    // divisions past the end
+    let (left, right) = (u8::MAX - 1..=u8::MAX).divide_at(5);
+    assert_eq!(left, u8::MAX - 1..=u8::MAX);
+    assert!(right.is_empty());
+    let (left, right) = (0..=u64::MAX).divide_at(0);
+    assert!(left.is_empty());
+    assert_eq!(right, 0..=u64::MAX);
+}
+
+#[test]
+fn test_empty_and_reversed_bounds() {
+    let (start, end) = (10u32, 3u32);
+    assert_eq!((start..end).into_par_iter().count(), 0);
+    assert_eq!((start..=end).into_par_iter().count(), 0);
+    assert_eq!(par_range_step(start, end, 2).count(), 0);
+    let (left, right) = (start..end).divide_at(2);
+    assert!(left.is_empty() && right.is_empty());
+    // single element ranges at the limits of the types
+    let v: Vec<i8> = (i8::MAX..=i8::MAX).into_par_iter().rev().collect();
+    assert_eq!(v, [i8::MAX]);
+    let v: Vec<u128> = (u128::MAX..=u128::MAX).into_par_iter().collect();
+    assert_eq!(v, [u128::MAX]);
+    let v: Vec<u8> = (0..=u8::MAX).into_par_iter().collect();
+    assert!(v.into_iter().eq(0..=u8::MAX));
+}
+
+#[test]
+fn test_steps() {
+    let v: Vec<i64> = par_range_step(i64::MIN, i64::MAX, 1 << 62).collect();
+    assert_eq!(v, (i64::MIN..i64::MAX).step_by(1 << 62).collect::<Vec<_>>());
+    let v: Vec<u128> = par_range_step(u128::MAX - 100, u128::MAX, 7)
+        .rev()
+        .collect();
+    let mut expected: Vec<u128> = (u128::MAX - 100..u128::MAX).step_by(7).collect();
+    expected.reverse();
+    assert_eq!(v, expected);
+    // steps larger than the range and the last element on the end
+    let v: Vec<i8> = par_range_step(i8::MIN, i8::MAX, 300).collect();
+    assert_eq!(v, [i8::MIN]);
+    let v: Vec<(usize, u8)> = par_range_step(0u8, 255, 5).enumerate().skip(50).collect();
+    assert_eq!(v, [(50, 250)]);
+}
+
+#[test]
+fn test_wide_ranges_schedulers() {
+    let expected = (i64::MAX as u64 - 99_999..=i64::MAX as u64).fold(0, u64::wrapping_add);
+    let range = || {
+        (i64::MAX - 99_999..=i64::MAX)
+            .into_par_iter()
+            .map(|e| e as u64)
+    };
+    let sum = |a: u64, b: u64| a.wrapping_add(b);
+    assert_eq!(range().adaptive().reduce(|| 0, sum), expected);
+    assert_eq!(range().rayon(2).reduce(|| 0, sum), expected);
+    assert_eq!(range().depjoin().reduce(|| 0, sum), expected);
+    let total = (u128::MAX - 10_000..=u128::MAX)
+        .into_par_iter()
+        .adaptive()
+        .reduce(|| 0, u128::wrapping_add);
+    assert_eq!(
+        total,
+        (u128::MAX - 10_000..=u128::MAX).fold(0, u128::wrapping_add)
+    );
+}
@@ -0,0 +1,83 @@
+use kvik::par_select_nth_unstable;
+use kvik::prelude::*;
+
+fn check_selection<T: Ord + Clone + Send + Sync + std::fmt::Debug>(v: &[T], index: usize) {
+    let mut sorted = v.to_vec();
+    sorted.sort_unstable();
+    let mut selected = v.to_vec();
+    let (smaller, nth, greater) = par_select_nth_unstable(&mut selected, index);
+    assert_eq!(*nth, sorted[index]);
+    assert_eq!(smaller.len(), index);
+    assert!(smaller.iter().all(|e| e <= nth));
+    assert!(greater.iter().all(|e| e >= nth));
+    // nothing was lost or duplicated
+    selected.sort_unstable();
+    assert_eq!(selected, sorted);
+}
+
+#[test]
+fn test_repeated_pivots() {
+    // all elements are equal to the pivot
+    let v = vec![3u32; 50_000];
+    for &index in &[0, 25_000, 49_999] {
+        check_selection(&v, index);
+    }
+    // two values: the pivot run covers half of the slice
+    let v: Vec<u32> = (0..50_000).map(|i| (i * 7_919) % 2).collect();
+    for &index in &[0, 24_999, 25_000, 49_999] {
+        check_selection(&v, index);
+    }
+}
+
+#[test]
+fn test_sorted_inputs_and_extremes() {
+    let sorted: Vec<u32> = (0..100_000).collect();
+    for &index in &[0, 77_777, 99_999] {
+        check_selection(&sorted, index);
+    }
+    let reversed: Vec<u32> = (0..100_000).rev().collect();
+    for &index in &[0, 12_345, 99_999] {
+        check_selection(&reversed, index);
+    }
+    check_selection(&[42u32], 0);
+    // non Copy elements
+    let strings: Vec<String> = (0..30_000)
+        .map(|i| ((i * 7_919) % 30_000).to_string())
+        .collect();
+    check_selection(&strings, 15_000);
+}
+
+#[test]
+fn test_top_k() {
+    let v: Vec<u32> = (0..50_000).map(|i| (i * 7_919) % 1_000).collect();
+    let mut expected = v.clone();
+    expected.sort_unstable_by(|a, b| b.cmp(a));
+    for &k in &[0, 1, 10, 50_000, 50_005] {
+        let top = v.par_iter().map(|e| *e).top_k(k, |a, b| a.cmp(b));
+        assert_eq!(top, expected[..k.min(50_000)]);
+    }
+    let top = v.par_iter().adaptive().top_k(4, |a, b| a.cmp(b));
+    assert_eq!(top, [&999; 4]);
+    // smallest elements of a non enumerable iterator
+    let smallest = v
+        .par_iter()
+        .filter(|e| *e % 2 == 1)
+        .top_k(3, |a, b| b.cmp(a));
+    assert_eq!(smallest, [&1, &1, &1]);
+    let empty = (0u32..0).into_par_iter().top_k(5, |a, b| a.cmp(b));
+    assert!(empty.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "selection index out of bounds")]
+fn test_selection_out_of_bounds() {
+    let mut v = vec![1u32, 2, 3];
+    par_select_nth_unstable(&mut v, 3);
+}
+
+#[test]
+#[should_panic(expected = "selection index out of bounds")]
+fn test_selection_in_empty_slice() {
+    let mut v: Vec<u32> = Vec::new();
+    par_select_nth_unstable(&mut v, 0);
+}
@@ -0,0 +1,185 @@
+use kvik::prelude::*;
+use kvik::{
+    adaptive_dedup, adaptive_difference, adaptive_intersection, adaptive_symmetric_difference,
+    adaptive_union,
+};
+#[cfg(feature = "steal-simulator")]
+use kvik::{Executor, StealSimulator};
+use rand::prelude::*;
+use std::cmp::Ordering;
+
+fn sorted(size: usize, range: u32, rng: &mut StdRng) -> Vec<u32> {
+    let mut v: Vec<u32> = (0..size).map(|_| rng.next_u32() % range).collect();
+    v.sort();
+    v
+}
+
+/// Sequential multiset operation: keep smaller a, smaller b, common.
+fn reference(a: &[u32], b: &[u32], keeps: (bool, bool, bool)) -> Vec<u32> {
+    let (mut i, mut j) = (0, 0);
+    let mut result = Vec::new();
+    while i < a.len() || j < b.len() {
+        let ordering = if j == b.len() {
+            Ordering::Less
+        } else if i == a.len() {
+            Ordering::Greater
+        } else {
+            a[i].cmp(&b[j])
+        };
+        match ordering {
+            Ordering::Less => {
+                if keeps.0 {
+                    result.push(a[i])
+                }
+                i += 1
+            }
+            Ordering::Greater => {
+                if keeps.1 {
+                    result.push(b[j])
+                }
+                j += 1
+            }
+            Ordering::Equal => {
+                if keeps.2 {
+                    result.push(a[i])
+                }
+                i += 1;
+                j += 1
+            }
+        }
+    }
+    result
+}
+
+const UNION: (bool, bool, bool) = (true, true, true);
+const INTERSECTION: (bool, bool, bool) = (false, false, true);
+const DIFFERENCE: (bool, bool, bool) = (true, false, false);
+const SYMMETRIC_DIFFERENCE: (bool, bool, bool) = (true, true, false);
+
+type BufferOperation = fn(&[u32], &[u32], &mut [u32]) -> usize;
+
+fn check_buffers(a: &[u32], b: &[u32]) {
+    let operations: [(BufferOperation, (bool, bool, bool)); 4] = [
+        (adaptive_union, UNION),
+        (adaptive_intersection, INTERSECTION),
+        (adaptive_difference, DIFFERENCE),
+        (adaptive_symmetric_difference, SYMMETRIC_DIFFERENCE),
+    ];
+    for (operation, keeps) in operations.iter() {
+        let mut output = vec![0; a.len() + b.len()];
+        let size = operation(a, b, &mut output);
+        assert_eq!(output[..size], reference(a, b, *keeps)[..]);
+    }
+    let mut output = vec![0; a.len()];
+    let size = adaptive_dedup(a, &mut output);
+    let mut expected = a.to_vec();
+    expected.dedup();
+    assert_eq!(output[..size], expected[..]);
+}
+
+fn check_iterators(a: &[u32], b: &[u32]) {
+    let union: Vec<u32> = a.par_iter().union(b).map(|e| *e).collect();
+    assert_eq!(union, reference(a, b, UNION));
+    let intersection: Vec<u32> = a.par_iter().intersection(b).map(|e| *e).collect();
+    assert_eq!(intersection, reference(a, b, INTERSECTION));
+    let difference: Vec<u32> = a.par_iter().difference(b).map(|e| *e).collect();
+    assert_eq!(difference, reference(a, b, DIFFERENCE));
+    let symmetric_difference: Vec<u32> = a.par_iter().symmetric_difference(b).map(|e| *e).collect();
+    assert_eq!(symmetric_difference, reference(a, b, SYMMETRIC_DIFFERENCE));
+    let dedup: Vec<u32> = a.par_iter().dedup().map(|e| *e).collect();
+    let mut expected = a.to_vec();
+    expected.dedup();
+    assert_eq!(dedup, expected);
+}
+
+fn check(a: &[u32], b: &[u32]) {
+    check_buffers(a, b);
+    check_iterators(a, b);
+}
+
+#[test]
+fn test_empty_inputs() {
+    check(&[], &[]);
+    check(&[], &[1, 1, 2]);
+    check(&[1, 1, 2], &[]);
+}
+
+#[test]
+fn test_single_value() {
+    // nothing can be divided at a value boundary
+    let a = vec![7; 10_000];
+    let b = vec![7; 3_000];
+    check(&a, &b);
+    check(&b, &a);
+}
+
+#[test]
+fn test_disjoint_inputs() {
+    let a: Vec<u32> = (0..50_000).collect();
+    let b: Vec<u32> = (50_000..100_000).collect();
+    let mut output = vec![0; 100_000];
+    assert_eq!(adaptive_union(&a, &b, &mut output), 100_000);
+    assert!(output.iter().enumerate().all(|(i, &e)| e == i as u32));
+    assert_eq!(adaptive_intersection(&a, &b, &mut output), 0);
+    check(&a, &b);
+    check(&b, &a);
+}
+
+#[test]
+fn test_multisets() {
+    // value i appears i % 4 times in a and i % 3 times in b
+    let a: Vec<u32> = (0..10_000u32)
+        .flat_map(|i| vec![i; i as usize % 4])
+        .collect();
+    let b: Vec<u32> = (0..10_000u32)
+        .flat_map(|i| vec![i; i as usize % 3])
+        .collect();
+    check(&a, &b);
+    check(&b, &a);
+}
+
+#[test]
+fn test_sparse_results() {
+    // most parts write nothing and the few results must still be compacted in order
+    let a: Vec<u32> = (0..200_000).map(|i| 2 * i).collect();
+    let b: Vec<u32> = (0..200_000)
+        .map(|i| if i % 10_000 == 0 { 2 * i } else { 2 * i + 1 })
+        .collect();
+    let mut output = vec![0; 200_000];
+    let size = adaptive_intersection(&a, &b, &mut output);
+    assert_eq!(
+        output[..size],
+        (0..20).map(|i| 20_000 * i).collect::<Vec<u32>>()[..]
+    );
+    let mut rng = StdRng::seed_from_u64(42);
+    let a = sorted(100_000, 1_000_000, &mut rng);
+    let b = sorted(100_000, 1_000_000, &mut rng);
+    check(&a, &b);
+}
+
+#[test]
+#[should_panic(expected = "output buffer is too small")]
+fn test_small_output() {
+    let a: Vec<u32> = (0..100).collect();
+    let mut output = vec![0; 150];
+    adaptive_union(&a, &a, &mut output);
+}
+
+#[test]
+#[cfg(feature = "steal-simulator")]
+fn test_set_operations_simulated_steals() {
+    for seed in 0..300 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let a = sorted(
+            rng.next_u32() as usize % 5_000,
+            1 + seed as u32 * 10,
+            &mut rng,
+        );
+        let b = sorted(
+            rng.next_u32() as usize % 5_000,
+            1 + seed as u32 * 10,
+            &mut rng,
+        );
+        StealSimulator::new(seed).run(|| check(&a, &b));
+    }
+}
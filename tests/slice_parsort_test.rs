@@ -1,7 +1,7 @@
+use kvik::slice_par_sort;
 use rand::random;
 use rand::seq::SliceRandom;
 use rayon::prelude::*;
-use kvik::slice_par_sort;
 
 #[derive(Copy, Clone, Debug)]
 struct OpaqueTuple {
@@ -94,3 +94,24 @@ fn test_stability_random_equal() {
         });
     }
 }
+
+#[test]
+#[cfg(feature = "steal-simulator")]
+fn test_slice_sort_simulated_steals() {
+    use kvik::{Executor, StealSimulator};
+    use rand::{rngs::StdRng, SeedableRng};
+    for seed in 0..1_000 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let size = 1_000 + (seed as usize * 7) % 4_000;
+        let mut input = (0..size as u32).collect::<Vec<_>>();
+        input.shuffle(&mut rng);
+        let simulator = StealSimulator::new(seed);
+        simulator.run(|| slice_par_sort(&mut input));
+        assert!(
+            input.windows(2).all(|w| w[0] <= w[1]),
+            "sort failed for seed {} with schedule {:?}",
+            seed,
+            simulator.schedule()
+        );
+    }
+}
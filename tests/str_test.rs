@@ -0,0 +1,86 @@
+use kvik::prelude::*;
+
+/// Text of `size` characters cycling through `alphabet`.
+fn text(alphabet: &[char], size: usize) -> String {
+    (0..size)
+        .map(|i| alphabet[(i * 7) % alphabet.len()])
+        .collect()
+}
+
+#[test]
+fn test_chars_of_multibyte_texts() {
+    // every division point falls inside a character
+    let text = text(&['𝄞', 'é', '€'], 100_000);
+    let chars: Vec<char> = text.par_chars().collect();
+    assert_eq!(chars, text.chars().collect::<Vec<_>>());
+    let reversed: Vec<(usize, char)> = text.par_char_indices().rev().collect();
+    assert_eq!(reversed, text.char_indices().rev().collect::<Vec<_>>());
+    assert_eq!("".par_chars().count(), 0);
+    assert_eq!("𝄞".par_char_indices().collect::<Vec<_>>(), [(0, '𝄞')]);
+}
+
+#[test]
+fn test_lines_endings() {
+    for text in &[
+        "",
+        "\n",
+        "\n\n",
+        "a\r\nb\n\n",
+        "a\r",
+        "\r\n\r\n",
+        "no newline",
+    ] {
+        let lines: Vec<&str> = text.par_lines().collect();
+        assert_eq!(lines, text.lines().collect::<Vec<_>>(), "{:?}", text);
+    }
+    // "\r\n" on every division point
+    let text = "line\r\n".repeat(20_000);
+    let lines: Vec<&str> = text.par_lines().collect();
+    assert_eq!(lines, vec!["line"; 20_000]);
+}
+
+#[test]
+fn test_split_separators_positions() {
+    // empty fields between adjacent separators and at both ends
+    for text in &["", ",", ",,", ",a,", "a,,b", "no separators here"] {
+        let fields: Vec<&str> = text.par_split(',').collect();
+        assert_eq!(fields, text.split(',').collect::<Vec<_>>(), "{:?}", text);
+    }
+    let text = text(&['a', ',', ',', 'é', '𝄞', ' '], 100_000);
+    let fields: Vec<&str> = text.par_split(',').collect();
+    assert_eq!(fields, text.split(',').collect::<Vec<_>>());
+    // multibyte separators and all pattern kinds
+    let fields: Vec<&str> = text.par_split(['é', '𝄞']).collect();
+    assert_eq!(fields, text.split(['é', '𝄞']).collect::<Vec<_>>());
+    let separators: &[char] = &['a', ' '];
+    let fields: Vec<&str> = text.par_split(separators).collect();
+    assert_eq!(fields, text.split(separators).collect::<Vec<_>>());
+    let fields: Vec<&str> = text.par_split(|c: char| !c.is_ascii()).rev().collect();
+    assert_eq!(
+        fields,
+        text.split(|c: char| !c.is_ascii())
+            .rev()
+            .collect::<Vec<_>>()
+    );
+    // no separator at all: nothing to divide at
+    let long = "x".repeat(100_000);
+    let fields: Vec<&str> = long.par_split(',').rayon(2).collect();
+    assert_eq!(fields, vec![long.as_str()]);
+}
+
+#[test]
+fn test_whitespace_and_matches() {
+    for text in &["", " \t ", " a ", "a"] {
+        let words: Vec<&str> = text.par_split_whitespace().collect();
+        assert_eq!(words, text.split_whitespace().collect::<Vec<_>>());
+    }
+    let text = text(&['a', 'b', 'é', '𝄞', ' ', '\t', '\n', ','], 100_000);
+    let words: Vec<&str> = text.par_split_whitespace().adaptive().collect();
+    assert_eq!(words, text.split_whitespace().collect::<Vec<_>>());
+    let matches: Vec<&str> = text.par_matches(|c: char| !c.is_ascii()).collect();
+    assert_eq!(
+        matches,
+        text.matches(|c: char| !c.is_ascii()).collect::<Vec<_>>()
+    );
+    assert_eq!("aaaa".par_matches('b').count(), 0);
+}